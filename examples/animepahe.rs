@@ -0,0 +1,27 @@
+use consumet::providers::anime;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let animepahe = anime::AnimePahe;
+
+    let search_results = animepahe.search("Frieren").await?;
+
+    let media_id = &search_results[0].id;
+
+    let episodes = animepahe.episodes(media_id, None).await?;
+
+    println!(
+        "Page {} of {}, {} episodes in total",
+        episodes.current_page, episodes.total_pages, episodes.total_results
+    );
+
+    let episode_id = &episodes.results[0].id;
+
+    let sources = animepahe.sources(episode_id).await?;
+
+    for source in sources.sources.unwrap_or_default() {
+        println!("{:?}: {:?}", source.quality, source.url);
+    }
+
+    Ok(())
+}
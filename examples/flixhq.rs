@@ -18,7 +18,7 @@ async fn main() -> anyhow::Result<()> {
         FlixHQInfo::TV(show) => {
             let media_id = &show.id;
 
-            let episode_id = &show.seasons.episodes[0][0].id;
+            let episode_id = &show.seasons[0].episodes[0].id;
 
             let servers = flixhq.servers(episode_id, media_id).await?;
 
@@ -0,0 +1,38 @@
+use consumet::{
+    models::StreamingServers, providers::anime, providers::anime::gogoanime::GogoanimeSourceType,
+};
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let gogoanime = anime::Gogoanime;
+
+    let search_results = gogoanime.search("Naruto", None).await?;
+
+    let media_id = &search_results.results[0].id;
+
+    let media_info = gogoanime.info(media_id).await?;
+
+    let episode_id = &media_info.episodes[0].id;
+
+    let servers = gogoanime.servers(episode_id).await?;
+
+    let server = match servers.servers[0].name.as_str() {
+        "anime" => StreamingServers::GogoCDN,
+        "vidcdn" => StreamingServers::VidStreaming,
+        "streamsb" => StreamingServers::StreamSB,
+        _ => panic!("Server not found!"),
+    };
+
+    let sources = gogoanime.sources(episode_id, Some(server)).await?;
+
+    match sources.sources {
+        GogoanimeSourceType::GogoCDN(sources) => {
+            println!("{:#?}", sources);
+        }
+        GogoanimeSourceType::StreamSB(sources) => {
+            println!("{:#?}", sources);
+        }
+    }
+
+    Ok(())
+}
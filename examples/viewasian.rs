@@ -0,0 +1,44 @@
+use consumet::{
+    models::StreamingServers, providers::movies, providers::movies::dramacool::DramaCoolSourceType,
+};
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let viewasian = movies::ViewAsian;
+
+    let search_results = viewasian.search("Vincenzo", None).await?;
+
+    let media_id = &search_results.results[0].id;
+
+    let media_info = viewasian.info(media_id).await?;
+
+    let episode_id = &media_info.episodes[0].id;
+
+    let servers = viewasian.servers(episode_id).await?;
+
+    let server_name = &servers.servers[0].name;
+
+    let server = match server_name.as_str() {
+        "asianload" => StreamingServers::AsianLoad,
+        _ => panic!("Server not found!"),
+    };
+
+    let sources = viewasian.sources(episode_id, Some(server)).await?;
+
+    match sources.sources {
+        DramaCoolSourceType::StreamSB(sources) => {
+            println!("{:#?}", sources);
+        }
+        DramaCoolSourceType::AsianLoad(sources) => {
+            println!("{:#?}", sources);
+        }
+        DramaCoolSourceType::StreamTape(sources) => {
+            println!("{:#?}", sources);
+        }
+        DramaCoolSourceType::MixDrop(sources) => {
+            println!("{:#?}", sources);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use consumet::{
+    models::{StreamingServers, SubOrSub},
+    providers::anime,
+};
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let zoro = anime::Zoro;
+
+    let search_results = zoro.search("One Piece", None).await?;
+
+    let media_id = &search_results.results[0].id;
+
+    let media_info = zoro.info(media_id).await?;
+
+    let episode_id = &media_info.episodes[0].id;
+
+    let servers = zoro.servers(episode_id).await?;
+
+    let server = servers
+        .dub
+        .iter()
+        .find_map(|server| server.streaming_server())
+        .unwrap_or(StreamingServers::VidCloud);
+
+    let sources = zoro
+        .sources(episode_id, Some(server), Some(SubOrSub::Dub))
+        .await?;
+
+    println!("{:#?}", sources.sources);
+
+    if let Some(intro) = sources.intro {
+        println!("Intro from {}s to {}s", intro.start, intro.end);
+    }
+
+    Ok(())
+}
@@ -1,4 +1,6 @@
 use crate::models::{ExtractConfig, VideoExtractor};
+
+use futures::future::BoxFuture;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -15,40 +17,34 @@ pub struct AsianLoadSubtitle {
 
 /// Contains both the Decrypted Sources and Subtitles
 #[derive(Debug, Deserialize, Clone, Serialize)]
-pub struct AsianLoad {
+pub struct AsianLoadData {
     pub sources: Vec<AsianLoadSource>,
     pub subtitles: Vec<AsianLoadSubtitle>,
 }
 
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AsianLoad;
+
 impl VideoExtractor for AsianLoad {
-    type VideoSource = AsianLoad;
+    type VideoSource = AsianLoadData;
 
     // NOTE: Only needs video_url param
-    async fn extract(
-        &mut self,
-        _video_url: String,
-        args: ExtractConfig,
-    ) -> anyhow::Result<Self::VideoSource> {
-        let ExtractConfig {
-            vis_cloud_helper: _,
-            api_key: _,
-            is_alternative: _,
-            user_agent: _,
-        } = args;
-
-        self.sources.push(AsianLoadSource {
-            url: String::new(),
-            is_m3u8: false,
-        });
-
-        self.subtitles.push(AsianLoadSubtitle {
-            url: String::new(),
-            lang: String::new(),
-        });
-
-        Ok(Self {
-            sources: self.sources.clone(),
-            subtitles: self.subtitles.clone(),
+    fn extract<'a>(
+        &'a self,
+        _video_url: &'a str,
+        _config: &'a ExtractConfig,
+    ) -> BoxFuture<'a, anyhow::Result<Self::VideoSource>> {
+        Box::pin(async move {
+            Ok(AsianLoadData {
+                sources: vec![AsianLoadSource {
+                    url: String::new(),
+                    is_m3u8: false,
+                }],
+                subtitles: vec![AsianLoadSubtitle {
+                    url: String::new(),
+                    lang: String::new(),
+                }],
+            })
         })
     }
 }
@@ -1,5 +1,6 @@
 use crate::models::{ExtractConfig, VideoExtractor};
 
+use futures::future::BoxFuture;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -10,34 +11,30 @@ pub struct BilibiliSources {
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
-pub struct Bilibili {
+pub struct BilibiliData {
     pub sources: Vec<BilibiliSources>,
 }
 
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Bilibili;
+
 impl VideoExtractor for Bilibili {
-    type VideoSource = Bilibili;
+    type VideoSource = BilibiliData;
 
     // NOTE: Only needs episode_id param
-    async fn extract(
-        &mut self,
-        _episode_id: String,
-        args: ExtractConfig,
-    ) -> anyhow::Result<Self::VideoSource> {
-        let ExtractConfig {
-            vis_cloud_helper: _,
-            api_key: _,
-            is_alternative: _,
-            user_agent: _,
-        } = args;
-
-        self.sources.push(BilibiliSources {
-            url: String::new(),
-            is_m3u8: false,
-            is_dash: false,
-        });
-
-        Ok(Self {
-            sources: self.sources.clone(),
+    fn extract<'a>(
+        &'a self,
+        _episode_id: &'a str,
+        _config: &'a ExtractConfig,
+    ) -> BoxFuture<'a, anyhow::Result<Self::VideoSource>> {
+        Box::pin(async move {
+            Ok(BilibiliData {
+                sources: vec![BilibiliSources {
+                    url: String::new(),
+                    is_m3u8: false,
+                    is_dash: false,
+                }],
+            })
         })
     }
 }
@@ -1,41 +1,38 @@
- use crate::models::{ExtractConfig, IVideo, VideoExtractor};
+use crate::models::{ExtractConfig, IVideo, VideoExtractor};
 
+use futures::future::BoxFuture;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct FileMoon {
+pub struct FileMoonData {
     pub sources: Vec<IVideo>,
 }
 
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FileMoon;
+
 const _HOST: &str = "https://filemoon.sx";
 
 impl VideoExtractor for FileMoon {
-    type VideoSource = FileMoon;
+    type VideoSource = FileMoonData;
 
     // NOTE: Only needs video_url param
-    async fn extract(
-        &mut self,
-        _video_url: String,
-        args: ExtractConfig,
-    ) -> anyhow::Result<Self::VideoSource> {
-        let ExtractConfig {
-            vis_cloud_helper: _,
-            api_key: _,
-            is_alternative: _,
-            user_agent: _,
-        } = args;
-
-        self.sources.push(IVideo {
-            url: None,
-            quality: None,
-            is_m3u8: None,
-            is_dash: None,
-            size: None,
-            other: None,
-        });
-
-        Ok(Self {
-            sources: self.sources.clone(),
+    fn extract<'a>(
+        &'a self,
+        _video_url: &'a str,
+        _config: &'a ExtractConfig,
+    ) -> BoxFuture<'a, anyhow::Result<Self::VideoSource>> {
+        Box::pin(async move {
+            Ok(FileMoonData {
+                sources: vec![IVideo {
+                    url: None,
+                    quality: None,
+                    is_m3u8: None,
+                    is_dash: None,
+                    size: None,
+                    other: None,
+                }],
+            })
         })
     }
 }
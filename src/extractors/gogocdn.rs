@@ -1,4 +1,12 @@
-use crate::models::{ExtractConfig, VideoExtractor};
+use crate::{
+    models::{ExtractConfig, VideoExtractor},
+    CLIENT,
+};
+
+use anyhow::bail;
+use futures::future::BoxFuture;
+use openssl::{base64, symm};
+use reqwest::Url;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -9,34 +17,210 @@ pub struct GogoCDNSources {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct GogoCDN {
+pub struct GogoCDNData {
     pub sources: Vec<GogoCDNSources>,
 }
 
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GogoCDN;
+
+/// Encrypts the episode id and decrypts the token embedded in the player page
+const KEY: &[u8] = b"37911490979715163134003223491201";
+/// Decrypts the encrypt-ajax.php response
+const SECOND_KEY: &[u8] = b"54674138327930866480207815084989";
+const IV: &[u8] = b"3134003223491201";
+
+#[derive(Deserialize)]
+struct RawEncrypted {
+    data: String,
+}
+
+#[derive(Deserialize)]
+struct RawSources {
+    #[serde(default)]
+    source: Vec<RawSource>,
+    #[serde(default)]
+    source_bk: Vec<RawSource>,
+}
+
+#[derive(Deserialize)]
+struct RawSource {
+    file: String,
+    /// e.g. "720 P", or "hls P" for playlists
+    label: Option<String>,
+}
+
+fn encrypt(text: &str, key: &[u8]) -> anyhow::Result<String> {
+    let encrypted = symm::encrypt(symm::Cipher::aes_256_cbc(), key, Some(IV), text.as_bytes())?;
+
+    Ok(base64::encode_block(&encrypted))
+}
+
+fn decrypt(text: &str, key: &[u8]) -> anyhow::Result<String> {
+    let encrypted = base64::decode_block(text.trim())?;
+    let decrypted = symm::decrypt(symm::Cipher::aes_256_cbc(), key, Some(IV), &encrypted)?;
+
+    Ok(String::from_utf8(decrypted)?)
+}
+
+/// The value of `name="..."` inside the first tag containing `marker`
+fn tag_attribute<'a>(html: &'a str, marker: &str, name: &str) -> Option<&'a str> {
+    let at = html.find(marker)?;
+    let start = html[..at].rfind('<')?;
+    let end = at + html[at..].find('>')?;
+    let tag = &html[start..end];
+
+    let (_, value) = tag.split_once(&format!("{}=", name))?;
+    let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let (value, _) = value[1..].split_once(quote)?;
+
+    Some(value)
+}
+
+/// Lists the variants of an HLS master playlist as `(quality, url)`, e.g. `("720p", "https://...")`
+pub(crate) fn hls_variants(master: &str, playlist_url: &str) -> Vec<(String, String)> {
+    let base = Url::parse(playlist_url).ok();
+    let mut lines = master.lines();
+    let mut variants = vec![];
+
+    while let Some(line) = lines.next() {
+        let Some((_, resolution)) = line.split_once("RESOLUTION=") else {
+            continue;
+        };
+        let Some(uri) = lines.next().map(str::trim).filter(|uri| !uri.is_empty()) else {
+            continue;
+        };
+
+        // RESOLUTION=1280x720
+        let height = resolution
+            .split(',')
+            .next()
+            .and_then(|resolution| resolution.split('x').nth(1))
+            .unwrap_or_default();
+
+        let url = match &base {
+            Some(base) => base
+                .join(uri)
+                .map(String::from)
+                .unwrap_or_else(|_| uri.to_owned()),
+            None => uri.to_owned(),
+        };
+
+        variants.push((format!("{}p", height), url));
+    }
+
+    variants
+}
+
 impl VideoExtractor for GogoCDN {
-    type VideoSource = GogoCDN;
+    type VideoSource = GogoCDNData;
 
     // NOTE: Only needs video_url param
-    async fn extract(
-        &mut self,
-        _video_url: String,
-        args: ExtractConfig,
-    ) -> anyhow::Result<Self::VideoSource> {
-        let ExtractConfig {
-            vis_cloud_helper: _,
-            api_key: _,
-            is_alternative: _,
-            user_agent: _,
-        } = args;
-
-        self.sources.push(GogoCDNSources {
-            url: String::new(),
-            quality: String::new(),
-            is_m3u8: false,
-        });
-
-        Ok(Self {
-            sources: self.sources.clone(),
+    fn extract<'a>(
+        &'a self,
+        video_url: &'a str,
+        config: &'a ExtractConfig,
+    ) -> BoxFuture<'a, anyhow::Result<Self::VideoSource>> {
+        Box::pin(async move {
+            // Players look like "https://embtaku.pro/streaming.php?id=MjI2NTA3&title=..."
+            let url = Url::parse(video_url)?;
+            let Some(id) = url
+                .query_pairs()
+                .find(|(key, _)| key == "id")
+                .map(|(_, id)| id.into_owned())
+            else {
+                bail!("No episode id in {}", video_url);
+            };
+
+            let player_html = CLIENT
+                .get(video_url)
+                .header("User-Agent", config.user_agent())
+                .send()
+                .await?
+                .error_for_status()?
+                .text()
+                .await?;
+
+            let Some(token) = tag_attribute(&player_html, "data-name=\"episode\"", "data-value")
+            else {
+                bail!("No episode token found in {}", video_url);
+            };
+
+            let params = format!(
+                "id={}&alias={}&{}",
+                encrypt(&id, KEY)?,
+                id,
+                decrypt(token, KEY)?
+            );
+
+            let encrypted: RawEncrypted = CLIENT
+                .get(format!(
+                    "{}://{}/encrypt-ajax.php?{}",
+                    url.scheme(),
+                    url.host_str().unwrap_or_default(),
+                    params
+                ))
+                .header("X-Requested-With", "XMLHttpRequest")
+                .header("Referer", config.referer().unwrap_or(video_url))
+                .header("User-Agent", config.user_agent())
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+
+            let raw: RawSources = serde_json::from_str(&decrypt(&encrypted.data, SECOND_KEY)?)?;
+
+            if raw.source.is_empty() {
+                bail!("No sources found in {}", video_url);
+            }
+
+            let mut sources = vec![];
+
+            for source in &raw.source {
+                if source.file.contains(".m3u8") {
+                    let master = CLIENT
+                        .get(&source.file)
+                        .header("User-Agent", config.user_agent())
+                        .send()
+                        .await?
+                        .text()
+                        .await?;
+
+                    sources.extend(hls_variants(&master, &source.file).into_iter().map(
+                        |(quality, url)| GogoCDNSources {
+                            is_m3u8: url.contains(".m3u8"),
+                            url,
+                            quality,
+                        },
+                    ));
+
+                    sources.push(GogoCDNSources {
+                        url: source.file.clone(),
+                        quality: String::from("default"),
+                        is_m3u8: true,
+                    });
+                } else {
+                    sources.push(GogoCDNSources {
+                        url: source.file.clone(),
+                        quality: source
+                            .label
+                            .as_deref()
+                            .and_then(|label| label.split(' ').next())
+                            .map(|quality| format!("{}p", quality))
+                            .unwrap_or_else(|| String::from("default")),
+                        is_m3u8: false,
+                    });
+                }
+            }
+
+            sources.extend(raw.source_bk.into_iter().map(|source| GogoCDNSources {
+                is_m3u8: source.file.contains(".m3u8"),
+                url: source.file,
+                quality: String::from("backup"),
+            }));
+
+            Ok(GogoCDNData { sources })
         })
     }
 }
@@ -1,4 +1,10 @@
-use crate::models::{ExtractConfig, VideoExtractor};
+use crate::{
+    models::{ExtractConfig, VideoExtractor},
+    CLIENT,
+};
+
+use anyhow::bail;
+use futures::future::BoxFuture;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -8,35 +14,124 @@ pub struct KwikSources {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct Kwik {
+pub struct KwikData {
     pub sources: Vec<KwikSources>,
 }
 
-const _HOST: &str = "https://animepage.com";
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Kwik;
+
+/// Kwik only serves embeds requested from AnimePahe
+const REFERER: &str = "https://animepahe.ru/";
+
+const DIGITS: &str = "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Reads a word of the packed script as a number in `radix`
+fn decode(word: &str, radix: usize) -> Option<usize> {
+    word.chars().try_fold(0usize, |value, c| {
+        let digit = DIGITS.find(c).filter(|digit| *digit < radix)?;
+        value.checked_mul(radix)?.checked_add(digit)
+    })
+}
+
+impl Kwik {
+    /// Unpacks a script packed as `eval(function(p,a,c,k,e,d){...}('payload',radix,count,'words'.split('|'),0,{}))`
+    /// ```
+    /// use consumet::extractors::Kwik;
+    ///
+    /// let packed = r#"eval(function(p,a,c,k,e,d){e=function(c){return c.toString(36)};if(!''.replace(/^/,String)){while(c--){d[c.toString(a)]=k[c]||c.toString(a)}k=[function(e){return d[e]}];e=function(){return'\\w+'};c=1};while(c--){if(k[c]){p=p.replace(new RegExp('\\b'+e(c)+'\\b','g'),k[c])}}return p}('0 1=\'2://3.4/5.6\';7.8({9:1,a:b})',36,12,'const|source|https|example|com|video|m3u8|player|setup|file|autoplay|true'.split('|'),0,{}))"#;
+    ///
+    /// assert_eq!(
+    ///     Kwik::unpack(packed).as_deref(),
+    ///     Some("const source='https://example.com/video.m3u8';player.setup({file:source,autoplay:true})")
+    /// );
+    /// assert_eq!(Kwik::unpack("var source='https://example.com';"), None);
+    /// ```
+    pub fn unpack(script: &str) -> Option<String> {
+        let (_, args) = script.split_once("}('")?;
+        let (args, _) = args.rsplit_once("'.split('|')")?;
+
+        let (args, words) = args.rsplit_once(",'")?;
+        let words: Vec<&str> = words.split('|').collect();
+
+        let mut args = args.rsplitn(3, ',');
+        let _count = args.next()?;
+        let radix: usize = args.next()?.trim().parse().ok()?;
+        let payload = args.next()?.strip_suffix('\'')?.replace("\\'", "'");
+
+        let mut unpacked = String::with_capacity(payload.len());
+        let mut word = String::new();
+
+        let flush = |word: &mut String, unpacked: &mut String| {
+            if word.is_empty() {
+                return;
+            }
+
+            let replacement = decode(word, radix)
+                .and_then(|index| words.get(index))
+                .filter(|replacement| !replacement.is_empty());
+
+            unpacked.push_str(replacement.copied().unwrap_or(word));
+            word.clear();
+        };
+
+        for c in payload.chars() {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                word.push(c);
+            } else {
+                flush(&mut word, &mut unpacked);
+                unpacked.push(c);
+            }
+        }
+        flush(&mut word, &mut unpacked);
+
+        Some(unpacked)
+    }
+}
 
 impl VideoExtractor for Kwik {
-    type VideoSource = Kwik;
+    type VideoSource = KwikData;
 
     // NOTE: Only needs video_url param
-    async fn extract(
-        &mut self,
-        _video_url: String,
-        args: ExtractConfig,
-    ) -> anyhow::Result<Self::VideoSource> {
-        let ExtractConfig {
-            vis_cloud_helper: _,
-            api_key: _,
-            is_alternative: _,
-            user_agent: _,
-        } = args;
-
-        self.sources.push(KwikSources {
-            url: String::new(),
-            is_m3u8: false,
-        });
-
-        Ok(Self {
-            sources: self.sources.clone(),
+    fn extract<'a>(
+        &'a self,
+        video_url: &'a str,
+        config: &'a ExtractConfig,
+    ) -> BoxFuture<'a, anyhow::Result<Self::VideoSource>> {
+        Box::pin(async move {
+            let embed_html = CLIENT
+                .get(video_url)
+                .header("Referer", config.referer().unwrap_or(REFERER))
+                .header("User-Agent", config.user_agent())
+                .send()
+                .await?
+                .error_for_status()?
+                .text()
+                .await?;
+
+            // The player setup, and with it the video url, is packed
+            let Some(packed) = embed_html
+                .split("<script>")
+                .find(|script| script.contains("eval(function(p,a,c,k,e,d)"))
+            else {
+                bail!("No packed player script found in {}", video_url);
+            };
+
+            let Some(url) = Self::unpack(packed).and_then(|player| {
+                let (_, rest) = player.split_once("source=")?;
+                let quote = rest.chars().next()?;
+                let (url, _) = rest[quote.len_utf8()..].split_once(quote)?;
+                Some(url.to_owned())
+            }) else {
+                bail!("No source found in the player of {}", video_url);
+            };
+
+            Ok(KwikData {
+                sources: vec![KwikSources {
+                    is_m3u8: url.contains(".m3u8"),
+                    url,
+                }],
+            })
         })
     }
 }
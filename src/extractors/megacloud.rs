@@ -1,4 +1,9 @@
-use crate::models::{ExtractConfig, VideoExtractor};
+use crate::{
+    extractors::rapidcloud::get_sources,
+    models::{ExtractConfig, Intro, VideoExtractor},
+};
+
+use futures::future::BoxFuture;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -15,41 +20,59 @@ pub struct MegaCloudSubtitles {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct MegaCloud {
+pub struct MegaCloudData {
     pub sources: Vec<MegaCloudSources>,
     pub subtitles: Vec<MegaCloudSubtitles>,
+    pub intro: Option<Intro>,
+    pub outro: Option<Intro>,
 }
 
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MegaCloud;
+
+const HOST: &str = "https://megacloud.tv";
+
 impl VideoExtractor for MegaCloud {
-    type VideoSource = MegaCloud;
+    type VideoSource = MegaCloudData;
 
     // NOTE: Only needs video_url param
-    async fn extract(
-        &mut self,
-        _video_url: String,
-        args: ExtractConfig,
-    ) -> anyhow::Result<Self::VideoSource> {
-        let ExtractConfig {
-            vis_cloud_helper: _,
-            api_key: _,
-            is_alternative: _,
-            user_agent: _,
-        } = args;
-
-        self.sources.push(MegaCloudSources {
-            url: String::new(),
-            r#type: String::new(),
-            is_m3u8: false,
-        });
-
-        self.subtitles.push(MegaCloudSubtitles {
-            url: String::new(),
-            lang: String::new(),
-        });
-
-        Ok(Self {
-            sources: self.sources.clone(),
-            subtitles: self.subtitles.clone(),
+    fn extract<'a>(
+        &'a self,
+        video_url: &'a str,
+        config: &'a ExtractConfig,
+    ) -> BoxFuture<'a, anyhow::Result<Self::VideoSource>> {
+        Box::pin(async move {
+            // Same response as RapidCloud, under another path
+            let raw = get_sources(HOST, "/embed-2/ajax/e-1/getSources", video_url, config).await?;
+
+            let sources = raw
+                .files()
+                .await?
+                .into_iter()
+                .map(|source| MegaCloudSources {
+                    is_m3u8: source.file.contains(".m3u8")
+                        || source.r#type.as_deref() == Some("hls"),
+                    url: source.file,
+                    r#type: source.r#type.unwrap_or_default(),
+                })
+                .collect();
+
+            let subtitles = raw
+                .captions()
+                .map(|track| MegaCloudSubtitles {
+                    url: track.file.clone(),
+                    lang: track.label.clone().unwrap_or_default(),
+                })
+                .collect();
+
+            let (intro, outro) = raw.skip_times();
+
+            Ok(MegaCloudData {
+                sources,
+                subtitles,
+                intro,
+                outro,
+            })
         })
     }
 }
@@ -1,4 +1,6 @@
 use crate::models::{ExtractConfig, VideoExtractor};
+
+use futures::future::BoxFuture;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -16,41 +18,35 @@ pub struct MixDropSubtitle {
 
 /// Contains both the Decrypted Sources and Subtitles
 #[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct MixDrop {
+pub struct MixDropData {
     pub sources: Vec<MixDropSource>,
     pub subtitles: Vec<MixDropSubtitle>,
 }
 
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MixDrop;
+
 impl VideoExtractor for MixDrop {
-    type VideoSource = MixDrop;
+    type VideoSource = MixDropData;
 
     // NOTE: Only needs video_url param
-    async fn extract(
-        &mut self,
-        _video_url: String,
-        args: ExtractConfig,
-    ) -> anyhow::Result<Self::VideoSource> {
-        let ExtractConfig {
-            vis_cloud_helper: _,
-            api_key: _,
-            is_alternative: _,
-            user_agent: _,
-        } = args;
-
-        self.sources.push(MixDropSource {
-            url: String::new(),
-            quality: String::new(),
-            is_m3u8: false,
-        });
-
-        self.subtitles.push(MixDropSubtitle {
-            url: String::new(),
-            lang: String::new(),
-        });
-
-        Ok(Self {
-            sources: self.sources.clone(),
-            subtitles: self.subtitles.clone(),
+    fn extract<'a>(
+        &'a self,
+        _video_url: &'a str,
+        _config: &'a ExtractConfig,
+    ) -> BoxFuture<'a, anyhow::Result<Self::VideoSource>> {
+        Box::pin(async move {
+            Ok(MixDropData {
+                sources: vec![MixDropSource {
+                    url: String::new(),
+                    quality: String::new(),
+                    is_m3u8: false,
+                }],
+                subtitles: vec![MixDropSubtitle {
+                    url: String::new(),
+                    lang: String::new(),
+                }],
+            })
         })
     }
 }
@@ -1,4 +1,6 @@
 use crate::models::{ExtractConfig, VideoExtractor};
+
+use futures::future::BoxFuture;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -9,34 +11,30 @@ pub struct Mp4PlayerSources {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct Mp4Player {
+pub struct Mp4PlayerData {
     pub sources: Vec<Mp4PlayerSources>,
 }
 
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Mp4Player;
+
 impl VideoExtractor for Mp4Player {
-    type VideoSource = Mp4Player;
+    type VideoSource = Mp4PlayerData;
 
     // NOTE: Only needs video_url param
-    async fn extract(
-        &mut self,
-        _video_url: String,
-        args: ExtractConfig,
-    ) -> anyhow::Result<Self::VideoSource> {
-        let ExtractConfig {
-            vis_cloud_helper: _,
-            api_key: _,
-            is_alternative: _,
-            user_agent: _,
-        } = args;
-
-        self.sources.push(Mp4PlayerSources {
-            url: String::new(),
-            quality: String::new(),
-            is_m3u8: false,
-        });
-
-        Ok(Self {
-            sources: self.sources.clone(),
+    fn extract<'a>(
+        &'a self,
+        _video_url: &'a str,
+        _config: &'a ExtractConfig,
+    ) -> BoxFuture<'a, anyhow::Result<Self::VideoSource>> {
+        Box::pin(async move {
+            Ok(Mp4PlayerData {
+                sources: vec![Mp4PlayerSources {
+                    url: String::new(),
+                    quality: String::new(),
+                    is_m3u8: false,
+                }],
+            })
         })
     }
 }
@@ -1,4 +1,6 @@
 use crate::models::{ExtractConfig, VideoExtractor};
+
+use futures::future::BoxFuture;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -9,34 +11,30 @@ pub struct Mp4UploadSources {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct Mp4Upload {
+pub struct Mp4UploadData {
     pub sources: Vec<Mp4UploadSources>,
 }
 
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Mp4Upload;
+
 impl VideoExtractor for Mp4Upload {
-    type VideoSource = Mp4Upload;
+    type VideoSource = Mp4UploadData;
 
     // NOTE: Only needs video_url param
-    async fn extract(
-        &mut self,
-        _video_url: String,
-        args: ExtractConfig,
-    ) -> anyhow::Result<Self::VideoSource> {
-        let ExtractConfig {
-            vis_cloud_helper: _,
-            api_key: _,
-            is_alternative: _,
-            user_agent: _,
-        } = args;
-
-        self.sources.push(Mp4UploadSources {
-            url: String::new(),
-            quality: String::new(),
-            is_m3u8: false,
-        });
-
-        Ok(Self {
-            sources: self.sources.clone(),
+    fn extract<'a>(
+        &'a self,
+        _video_url: &'a str,
+        _config: &'a ExtractConfig,
+    ) -> BoxFuture<'a, anyhow::Result<Self::VideoSource>> {
+        Box::pin(async move {
+            Ok(Mp4UploadData {
+                sources: vec![Mp4UploadSources {
+                    url: String::new(),
+                    quality: String::new(),
+                    is_m3u8: false,
+                }],
+            })
         })
     }
 }
@@ -1,5 +1,12 @@
-use crate::models::{ExtractConfig, VideoExtractor};
+use crate::{
+    models::{ExtractConfig, Intro, VideoExtractor},
+    utils::decrypt,
+    CLIENT,
+};
 
+use anyhow::{anyhow, bail};
+use futures::future::BoxFuture;
+use openssl::base64;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -16,43 +23,149 @@ pub struct RapidCloudSubtitles {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct RapidCloud {
+pub struct RapidCloudData {
     pub sources: Vec<RapidCloudSources>,
     pub subtitles: Vec<RapidCloudSubtitles>,
+    pub intro: Option<Intro>,
+    pub outro: Option<Intro>,
 }
 
-const _HOST: &str = "https://rapid-cloud.co";
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RapidCloud;
+
+const HOST: &str = "https://rapid-cloud.co";
+/// A JSON list of the key bytes for encrypted sources
+const DECRYPT_KEY: &str = "https://raw.githubusercontent.com/eatmynerds/key/e4/key.txt";
+
+/// Contains a video file of the getSources response
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct RawSource {
+    pub file: String,
+    pub r#type: Option<String>,
+}
+
+/// Contains a text track, captions or the seek bar thumbnails
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct RawTrack {
+    pub file: String,
+    pub label: Option<String>,
+    pub kind: Option<String>,
+}
+
+/// Contains the getSources response shared by the RapidCloud and MegaCloud embeds
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct RawSources {
+    /// a list of files, or an encrypted string of one
+    pub sources: serde_json::Value,
+    #[serde(default)]
+    pub tracks: Vec<RawTrack>,
+    pub intro: Option<Intro>,
+    pub outro: Option<Intro>,
+}
+
+impl RawSources {
+    pub async fn files(&self) -> anyhow::Result<Vec<RawSource>> {
+        match &self.sources {
+            serde_json::Value::Array(_) => Ok(serde_json::from_value(self.sources.clone())?),
+            serde_json::Value::String(encrypted) => {
+                let decrypt_key = CLIENT.get(DECRYPT_KEY).send().await?.text().await?;
+
+                let key_json: Vec<u8> = serde_json::from_str(&decrypt_key)?;
+
+                let key_string = base64::encode_block(&key_json);
+
+                let decrypted = decrypt::decrypt_url(encrypted, &key_string.into_bytes())
+                    .map_err(|err| anyhow!("Unable to decrypt sources: {:?}", err))?;
+
+                Ok(serde_json::from_str(&decrypted)?)
+            }
+            _ => bail!("No sources found"),
+        }
+    }
+
+    pub fn captions(&self) -> impl Iterator<Item = &RawTrack> {
+        self.tracks
+            .iter()
+            .filter(|track| track.kind.as_deref() != Some("thumbnails"))
+    }
+
+    /// An unset skip time is sent as 0 to 0
+    pub fn skip_times(&self) -> (Option<Intro>, Option<Intro>) {
+        let set = |time: &Option<Intro>| time.filter(|time| time.end > time.start);
+
+        (set(&self.intro), set(&self.outro))
+    }
+}
+
+/// Fetches the getSources response of the embed `video_url`, `path` leads from the host to the endpoint
+pub(crate) async fn get_sources(
+    host: &str,
+    path: &str,
+    video_url: &str,
+    config: &ExtractConfig,
+) -> anyhow::Result<RawSources> {
+    // Embeds look like "https://rapid-cloud.co/embed-6-v2/SjmkRqVkwNVz?z="
+    let Some(id) = video_url
+        .split('?')
+        .next()
+        .and_then(|url| url.rsplit('/').next())
+        .filter(|id| !id.is_empty())
+    else {
+        bail!("No embed id in {}", video_url);
+    };
+
+    Ok(CLIENT
+        .get(format!("{}{}?id={}", host, path, id))
+        .header("X-Requested-With", "XMLHttpRequest")
+        .header("Referer", config.referer().unwrap_or(video_url))
+        .header("User-Agent", config.user_agent())
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<RawSources>()
+        .await?)
+}
 
 impl VideoExtractor for RapidCloud {
-    type VideoSource = RapidCloud;
+    type VideoSource = RapidCloudData;
 
     // NOTE: Only needs video_url param
-    async fn extract(
-        &mut self,
-        _video_url: String,
-        args: ExtractConfig,
-    ) -> anyhow::Result<Self::VideoSource> {
-        let ExtractConfig {
-            vis_cloud_helper: _,
-            api_key: _,
-            is_alternative: _,
-            user_agent: _,
-        } = args;
-
-        self.sources.push(RapidCloudSources {
-            url: String::new(),
-            quality: String::new(),
-            is_m3u8: false,
-        });
-
-        self.subtitles.push(RapidCloudSubtitles {
-            url: String::new(),
-            lang: String::new(),
-        });
-
-        Ok(Self {
-            sources: self.sources.clone(),
-            subtitles: self.subtitles.clone(),
+    fn extract<'a>(
+        &'a self,
+        video_url: &'a str,
+        config: &'a ExtractConfig,
+    ) -> BoxFuture<'a, anyhow::Result<Self::VideoSource>> {
+        Box::pin(async move {
+            let raw = get_sources(HOST, "/ajax/embed-6-v2/getSources", video_url, config).await?;
+
+            let sources = raw
+                .files()
+                .await?
+                .into_iter()
+                .map(|source| RapidCloudSources {
+                    is_m3u8: source.file.contains(".m3u8")
+                        || source.r#type.as_deref() == Some("hls"),
+                    url: source.file,
+                    quality: "auto".to_string(),
+                })
+                .collect();
+
+            let subtitles = raw
+                .captions()
+                .map(|track| RapidCloudSubtitles {
+                    url: track.file.clone(),
+                    lang: track.label.clone().unwrap_or_default(),
+                })
+                .collect();
+
+            let (intro, outro) = raw.skip_times();
+
+            Ok(RapidCloudData {
+                sources,
+                subtitles,
+                intro,
+                outro,
+            })
         })
     }
 }
@@ -1,4 +1,6 @@
 use crate::models::{ExtractConfig, VideoExtractor};
+
+use futures::future::BoxFuture;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -15,43 +17,37 @@ pub struct SmashyStreamSubtitles {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct SmashyStream {
+pub struct SmashyStreamData {
     pub sources: Vec<SmashyStreamSources>,
     pub subtitles: Vec<SmashyStreamSubtitles>,
 }
 
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SmashyStream;
+
 const _HOST: &str = "https://embed.smashystream.com";
 
 impl VideoExtractor for SmashyStream {
-    type VideoSource = SmashyStream;
+    type VideoSource = SmashyStreamData;
 
     // NOTE: Only needs video_url param
-    async fn extract(
-        &mut self,
-        _video_url: String,
-        args: ExtractConfig,
-    ) -> anyhow::Result<Self::VideoSource> {
-        let ExtractConfig {
-            vis_cloud_helper: _,
-            api_key: _,
-            is_alternative: _,
-            user_agent: _,
-        } = args;
-
-        self.sources.push(SmashyStreamSources {
-            url: String::new(),
-            quality: String::new(),
-            is_m3u8: false,
-        });
-
-        self.subtitles.push(SmashyStreamSubtitles {
-            url: String::new(),
-            lang: String::new(),
-        });
-
-        Ok(Self {
-            sources: self.sources.clone(),
-            subtitles: self.subtitles.clone(),
+    fn extract<'a>(
+        &'a self,
+        _video_url: &'a str,
+        _config: &'a ExtractConfig,
+    ) -> BoxFuture<'a, anyhow::Result<Self::VideoSource>> {
+        Box::pin(async move {
+            Ok(SmashyStreamData {
+                sources: vec![SmashyStreamSources {
+                    url: String::new(),
+                    quality: String::new(),
+                    is_m3u8: false,
+                }],
+                subtitles: vec![SmashyStreamSubtitles {
+                    url: String::new(),
+                    lang: String::new(),
+                }],
+            })
         })
     }
 }
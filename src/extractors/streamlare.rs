@@ -1,40 +1,38 @@
 use crate::models::{ExtractConfig, IVideo, VideoExtractor};
+
+use futures::future::BoxFuture;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct StreamLare {
-    sources: Vec<IVideo>,
+pub struct StreamLareData {
+    pub sources: Vec<IVideo>,
 }
 
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StreamLare;
+
 const _HOST: &str = "https://streamlare.com";
 
 impl VideoExtractor for StreamLare {
-    type VideoSource = StreamLare;
+    type VideoSource = StreamLareData;
 
     // NOTE: Only needs video_url & user_agent param param
-    async fn extract(
-        &mut self,
-        _video_url: String,
-        args: ExtractConfig,
-    ) -> anyhow::Result<Self::VideoSource> {
-        let ExtractConfig {
-            vis_cloud_helper: _,
-            api_key: _,
-            is_alternative: _,
-            user_agent: _,
-        } = args;
-
-        self.sources.push(IVideo {
-            url: None,
-            quality: None,
-            is_m3u8: None,
-            is_dash: None,
-            size: None,
-            other: None,
-        });
-
-        Ok(Self {
-            sources: self.sources.clone(),
+    fn extract<'a>(
+        &'a self,
+        _video_url: &'a str,
+        _config: &'a ExtractConfig,
+    ) -> BoxFuture<'a, anyhow::Result<Self::VideoSource>> {
+        Box::pin(async move {
+            Ok(StreamLareData {
+                sources: vec![IVideo {
+                    url: None,
+                    quality: None,
+                    is_m3u8: None,
+                    is_dash: None,
+                    size: None,
+                    other: None,
+                }],
+            })
         })
     }
 }
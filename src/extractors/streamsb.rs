@@ -1,5 +1,11 @@
-use crate::models::{ExtractConfig, VideoExtractor};
+use crate::{
+    extractors::gogocdn::hls_variants,
+    models::{ExtractConfig, VideoExtractor},
+    CLIENT,
+};
 
+use anyhow::bail;
+use futures::future::BoxFuture;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -11,37 +17,100 @@ pub struct StreamSBSources {
 
 /// Contains the Decrypted Sources
 #[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct StreamSB {
+pub struct StreamSBData {
     pub sources: Vec<StreamSBSources>,
 }
 
-const _HOST: &str = "https://streamsss.net/sources50";
-const _HOST2: &str = "https://watchsb.com/sources50";
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StreamSB;
+
+const HOST: &str = "https://streamsss.net/sources50";
+const HOST2: &str = "https://watchsb.com/sources50";
+
+#[derive(Deserialize)]
+struct RawResponse {
+    stream_data: Option<RawStreamData>,
+}
+
+#[derive(Deserialize)]
+struct RawStreamData {
+    file: String,
+}
+
+/// The sources endpoint takes the hex encoded embed id wrapped in fixed padding
+fn payload(id: &str) -> String {
+    let hex: String = id.bytes().map(|byte| format!("{:02x}", byte)).collect();
+
+    format!("566d337678566f743674494a7c7c{}7c7c346b6767586d6934774855537c7c73747265616d7362/6565417268755339773461447c7c346133383438333436313335376136323337373433383634376337633465366534393338373136643732373736343735373237613763376334363733353737303533366236333463353333363534366137633763373337343732363536313664373336327c7c6b586c3163614468645a47617c7c73747265616d7362", hex)
+}
 
 impl VideoExtractor for StreamSB {
-    type VideoSource = StreamSB;
+    type VideoSource = StreamSBData;
 
     // NOTE: Only needs video_url & is_alternative param
-    async fn extract(
-        &mut self,
-        _video_url: String,
-        args: ExtractConfig,
-    ) -> anyhow::Result<Self::VideoSource> {
-        let ExtractConfig {
-            vis_cloud_helper: _,
-            api_key: _,
-            is_alternative: _,
-            user_agent: _,
-        } = args;
-
-        self.sources.push(StreamSBSources {
-            url: String::new(),
-            quality: String::new(),
-            is_m3u8: false,
-        });
-
-        Ok(Self {
-            sources: self.sources.clone(),
+    fn extract<'a>(
+        &'a self,
+        video_url: &'a str,
+        config: &'a ExtractConfig,
+    ) -> BoxFuture<'a, anyhow::Result<Self::VideoSource>> {
+        Box::pin(async move {
+            let host = if !config.is_alternative() {
+                HOST
+            } else {
+                HOST2
+            };
+
+            // Embeds look like "https://streamsss.net/e/5kq4gjoj2hxv.html"
+            let Some(id) = video_url
+                .rsplit_once("/e/")
+                .map(|(_, id)| id.trim_end_matches(".html"))
+                .filter(|id| !id.is_empty())
+            else {
+                bail!("No embed id in {}", video_url);
+            };
+
+            let response: RawResponse = CLIENT
+                .get(format!("{}/{}", host, payload(id)))
+                .header("watchsb", "sbstream")
+                .header("Referer", video_url)
+                .header("User-Agent", config.user_agent())
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+
+            let Some(stream) = response.stream_data else {
+                bail!("No sources found in {}", video_url);
+            };
+
+            let (site, _) = video_url.split_once("e/").unwrap_or((video_url, ""));
+
+            let master = CLIENT
+                .get(&stream.file)
+                .header("Referer", site)
+                .header("User-Agent", config.user_agent())
+                .send()
+                .await?
+                .text()
+                .await?;
+
+            let mut sources: Vec<StreamSBSources> = hls_variants(&master, &stream.file)
+                .into_iter()
+                .map(|(quality, url)| StreamSBSources {
+                    url,
+                    quality,
+                    is_m3u8: true,
+                })
+                .collect();
+
+            sources.push(StreamSBSources {
+                is_m3u8: stream.file.contains(".m3u8"),
+                url: stream.file,
+                quality: String::from("auto"),
+            });
+
+            Ok(StreamSBData { sources })
         })
     }
 }
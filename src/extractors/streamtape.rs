@@ -1,4 +1,6 @@
 use crate::models::{ExtractConfig, VideoExtractor};
+
+use futures::future::BoxFuture;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -9,33 +11,29 @@ pub struct StreamTapeSources {
 
 /// Contains the Decrypted Sources
 #[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct StreamTape {
+pub struct StreamTapeData {
     pub sources: Vec<StreamTapeSources>,
 }
 
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StreamTape;
+
 impl VideoExtractor for StreamTape {
-    type VideoSource = StreamTape;
+    type VideoSource = StreamTapeData;
 
     // NOTE: Only needs video_url param
-    async fn extract(
-        &mut self,
-        _video_url: String,
-        args: ExtractConfig,
-    ) -> anyhow::Result<Self::VideoSource> {
-        let ExtractConfig {
-            vis_cloud_helper: _,
-            api_key: _,
-            is_alternative: _,
-            user_agent: _,
-        } = args;
-
-        self.sources.push(StreamTapeSources {
-            url: String::new(),
-            is_m3u8: false,
-        });
-
-        Ok(Self {
-            sources: self.sources.clone(),
+    fn extract<'a>(
+        &'a self,
+        _video_url: &'a str,
+        _config: &'a ExtractConfig,
+    ) -> BoxFuture<'a, anyhow::Result<Self::VideoSource>> {
+        Box::pin(async move {
+            Ok(StreamTapeData {
+                sources: vec![StreamTapeSources {
+                    url: String::new(),
+                    is_m3u8: false,
+                }],
+            })
         })
     }
 }
@@ -1,4 +1,6 @@
 use crate::models::{ExtractConfig, VideoExtractor};
+
+use futures::future::BoxFuture;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -9,34 +11,30 @@ pub struct StreamWishSources {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct StreamWish {
+pub struct StreamWishData {
     pub sources: Vec<StreamWishSources>,
 }
 
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StreamWish;
+
 impl VideoExtractor for StreamWish {
-    type VideoSource = StreamWish;
+    type VideoSource = StreamWishData;
 
     // NOTE: Only needs video_url param
-    async fn extract(
-        &mut self,
-        _video_url: String,
-        args: ExtractConfig,
-    ) -> anyhow::Result<Self::VideoSource> {
-        let ExtractConfig {
-            vis_cloud_helper: _,
-            api_key: _,
-            is_alternative: _,
-            user_agent: _,
-        } = args;
-
-        self.sources.push(StreamWishSources {
-            url: String::new(),
-            quality: String::new(),
-            is_m3u8: false,
-        });
-
-        Ok(Self {
-            sources: self.sources.clone(),
+    fn extract<'a>(
+        &'a self,
+        _video_url: &'a str,
+        _config: &'a ExtractConfig,
+    ) -> BoxFuture<'a, anyhow::Result<Self::VideoSource>> {
+        Box::pin(async move {
+            Ok(StreamWishData {
+                sources: vec![StreamWishSources {
+                    url: String::new(),
+                    quality: String::new(),
+                    is_m3u8: false,
+                }],
+            })
         })
     }
 }
@@ -1,18 +1,25 @@
 use crate::{
+    extractors::gogocdn::hls_variants,
     models::{ExtractConfig, VideoExtractor},
-    utils::{decrypt, util_funcs::USER_AGENT},
+    utils::decrypt,
     CLIENT,
 };
+
+use anyhow::{anyhow, bail};
+use futures::future::BoxFuture;
 use openssl::base64;
 use serde::{Deserialize, Serialize};
 
 /// Contains both the Decrypted Sources and Subtitles
 #[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct VidCloud {
+pub struct VidCloudData {
     pub sources: Vec<VidCloudSource>,
     pub subtitles: Vec<VidCloudSubtitle>,
 }
 
+#[derive(Clone, Copy, Debug, Default)]
+pub struct VidCloud;
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct VidCloudSubtitle {
     pub url: String,
@@ -62,141 +69,112 @@ const HOST: &str = "https://dokicloud.one";
 const HOST2: &str = "https://rabbitstream.net";
 
 impl VideoExtractor for VidCloud {
-    type VideoSource = VidCloud;
-
-    // NOTE: Only needs video_url & is_alternativeparam
-    async fn extract(
-        &mut self,
-        video_url: String,
-        args: ExtractConfig,
-    ) -> anyhow::Result<Self::VideoSource> {
-        let ExtractConfig {
-            vis_cloud_helper: _,
-            api_key: _,
-            is_alternative,
-            user_agent: _,
-        } = args;
-
-        let is_alternative: bool = is_alternative.unwrap_or(false);
-
-        let host = if !is_alternative { HOST } else { HOST2 };
-
-        let parts: Vec<&str> = video_url.split('/').collect();
-        let id = parts.last().unwrap().split('?').next().unwrap();
-
-        let sources_text = CLIENT
-            .get(format!("{}/ajax/embed-4/getSources?id={}", host, id))
-            .header("X-Requested-With", "XMLHttpRequest")
-            .header("Referer", video_url.to_string())
-            .header("User-Agent", USER_AGENT)
-            .send()
-            .await?
-            .text()
-            .await?;
-
-        let encrypted_sources: Sources =
-            serde_json::from_str(&sources_text).expect("Failed to deserialize json");
-
-        let url = match encrypted_sources.sources {
-            Some(serde_json::Value::String(sources)) => File::EncryptedURL(sources),
-            Some(serde_json::Value::Array(sources)) => {
-                let sources = sources
-                    .into_iter()
-                    .map(|x| serde_json::from_value::<Video>(x).unwrap())
-                    .collect::<Vec<_>>();
-                File::DecryptedURL(sources)
-            }
-            _ => {
-                panic!("Please fix this") // Note: I will never fix this - eatmynerds
-            }
-        };
-
-        let sources = match url {
-            File::DecryptedURL(decrypted) => decrypted,
-            File::EncryptedURL(encrypted) => {
-                let decrypt_key: String = CLIENT
-                    .get("https://raw.githubusercontent.com/eatmynerds/key/e4/key.txt")
-                    .send()
-                    .await?
-                    .text()
-                    .await?;
-
-                let key_json: Vec<u8> = serde_json::from_str(&decrypt_key)?;
-
-                let key_string = base64::encode_block(&key_json);
-
-                let decrypted_str = decrypt::decrypt_url(&encrypted, &key_string.into_bytes())
-                    .expect("Unable to decrypt URL");
-
-                let decrypted: Vec<Video> =
-                    serde_json::from_str(&decrypted_str).expect("Failed to deserialize json");
-
-                decrypted
-            }
-        };
-
-        let mut temp_sources: Vec<VidCloudSource> = vec![];
-
-        self.sources.push(VidCloudSource {
-            url: sources[0].file.clone().unwrap(),
-            quality: "auto".to_string(),
-            is_m3u8: sources[0].file.clone().unwrap().contains(".m3u8"),
-        });
-
-        for source in sources {
-            let data = CLIENT
-                .get(&source.file.unwrap())
+    type VideoSource = VidCloudData;
+
+    // NOTE: Only needs video_url & is_alternative param
+    fn extract<'a>(
+        &'a self,
+        video_url: &'a str,
+        config: &'a ExtractConfig,
+    ) -> BoxFuture<'a, anyhow::Result<Self::VideoSource>> {
+        Box::pin(async move {
+            let host = if !config.is_alternative() {
+                HOST
+            } else {
+                HOST2
+            };
+
+            // Embeds look like "https://dokicloud.one/embed-4/9vGFzPTDZ0BL?z="
+            let Some(id) = video_url
+                .split('?')
+                .next()
+                .and_then(|url| url.rsplit('/').next())
+                .filter(|id| !id.is_empty())
+            else {
+                bail!("No embed id in {}", video_url);
+            };
+
+            let encrypted_sources: Sources = CLIENT
+                .get(format!("{}/ajax/embed-4/getSources?id={}", host, id))
+                .header("X-Requested-With", "XMLHttpRequest")
+                .header("Referer", video_url.to_string())
+                .header("User-Agent", config.user_agent())
                 .send()
                 .await?
-                .text()
+                .error_for_status()?
+                .json()
                 .await?;
 
-            let urls: Vec<String> = data
-                .lines()
-                .filter(|line| line.contains(".m3u8"))
-                .map(|line| line.to_string())
+            let url = match encrypted_sources.sources {
+                Some(serde_json::Value::String(sources)) => File::EncryptedURL(sources),
+                Some(sources @ serde_json::Value::Array(_)) => {
+                    File::DecryptedURL(serde_json::from_value(sources)?)
+                }
+                _ => bail!("No sources found in {}", video_url),
+            };
+
+            let sources: Vec<Video> = match url {
+                File::DecryptedURL(decrypted) => decrypted,
+                File::EncryptedURL(encrypted) => {
+                    let decrypt_key: String = CLIENT
+                        .get("https://raw.githubusercontent.com/eatmynerds/key/e4/key.txt")
+                        .send()
+                        .await?
+                        .text()
+                        .await?;
+
+                    let key_json: Vec<u8> = serde_json::from_str(&decrypt_key)?;
+
+                    let key_string = base64::encode_block(&key_json);
+
+                    let decrypted_str = decrypt::decrypt_url(&encrypted, &key_string.into_bytes())
+                        .map_err(|err| anyhow!("Unable to decrypt sources: {:?}", err))?;
+
+                    serde_json::from_str(&decrypted_str)?
+                }
+            };
+
+            let files: Vec<String> = sources
+                .into_iter()
+                .filter_map(|source| source.file)
                 .collect();
 
-            let qualities: Vec<String> = data
-                .lines()
-                .filter(|line| line.contains("RESOLUTION="))
-                .map(|line| line.to_string())
-                .collect();
+            let Some(first) = files.first() else {
+                bail!("No sources found in {}", video_url);
+            };
+
+            let mut video_sources = vec![VidCloudSource {
+                url: first.clone(),
+                quality: "auto".to_string(),
+                is_m3u8: first.contains(".m3u8"),
+            }];
+
+            for file in &files {
+                let master = CLIENT.get(file).send().await?.text().await?;
+
+                video_sources.extend(hls_variants(&master, file).into_iter().map(
+                    |(quality, url)| VidCloudSource {
+                        is_m3u8: url.contains(".m3u8"),
+                        url,
+                        quality,
+                    },
+                ));
+            }
 
-            let td_array: Vec<(&str, &str)> = qualities
-                .iter()
-                .enumerate()
-                .map(|(i, s)| {
-                    let f1 = s.split('x').nth(1).unwrap_or("");
-                    let f2 = urls[i].as_str();
-                    (f1, f2)
+            let subtitles: Vec<VidCloudSubtitle> = encrypted_sources
+                .tracks
+                .unwrap_or_default()
+                .into_iter()
+                .map(|track| VidCloudSubtitle {
+                    url: track.file,
+                    lang: track.label,
                 })
                 .collect();
 
-            temp_sources.extend(td_array.iter().map(|&(f1, f2)| VidCloudSource {
-                url: f2.to_string(),
-                quality: f1.to_string(),
-                is_m3u8: f2.contains(".m3u8"),
-            }));
-
-            self.sources.extend(temp_sources.iter().cloned());
-        }
-
-        let subtitles: Vec<VidCloudSubtitle> = encrypted_sources
-            .tracks
-            .unwrap()
-            .iter()
-            .map(|s| VidCloudSubtitle {
-                url: s.file.clone(),
-                lang: s.label.clone(),
+            Ok(VidCloudData {
+                sources: video_sources,
+                subtitles,
             })
-            .collect();
-
-        self.subtitles.extend(subtitles);
-
-        Ok(Self {
-            sources: self.sources.clone(),
-            subtitles: self.subtitles.clone(),
         })
     }
 }
@@ -1,4 +1,6 @@
 use crate::models::{ExtractConfig, VideoExtractor};
+
+use futures::future::BoxFuture;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -9,34 +11,30 @@ pub struct VidMolySources {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct VidMoly {
+pub struct VidMolyData {
     pub sources: Vec<VidMolySources>,
 }
 
+#[derive(Clone, Copy, Debug, Default)]
+pub struct VidMoly;
+
 impl VideoExtractor for VidMoly {
-    type VideoSource = VidMoly;
+    type VideoSource = VidMolyData;
 
     // NOTE: Only needs video_url param
-    async fn extract(
-        &mut self,
-        _video_url: String,
-        args: ExtractConfig,
-    ) -> anyhow::Result<Self::VideoSource> {
-        let ExtractConfig {
-            vis_cloud_helper: _,
-            api_key: _,
-            is_alternative: _,
-            user_agent: _,
-        } = args;
-
-        self.sources.push(VidMolySources {
-            url: String::new(),
-            quality: String::new(),
-            is_m3u8: false,
-        });
-
-        Ok(Self {
-            sources: self.sources.clone(),
+    fn extract<'a>(
+        &'a self,
+        _video_url: &'a str,
+        _config: &'a ExtractConfig,
+    ) -> BoxFuture<'a, anyhow::Result<Self::VideoSource>> {
+        Box::pin(async move {
+            Ok(VidMolyData {
+                sources: vec![VidMolySources {
+                    url: String::new(),
+                    quality: String::new(),
+                    is_m3u8: false,
+                }],
+            })
         })
     }
 }
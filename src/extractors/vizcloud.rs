@@ -1,4 +1,6 @@
 use crate::models::{ExtractConfig, VideoExtractor};
+
+use futures::future::BoxFuture;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -9,36 +11,32 @@ pub struct VizCloudSources {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct VizCloud {
+pub struct VizCloudData {
     pub sources: Vec<VizCloudSources>,
 }
 
+#[derive(Clone, Copy, Debug, Default)]
+pub struct VizCloud;
+
 const _HOST: &str = "https://vidstream.pro";
 
 impl VideoExtractor for VizCloud {
-    type VideoSource = VizCloud;
+    type VideoSource = VizCloudData;
 
     // NOTE: Only needs video_url & viz_cloud_helper & api_key param
-    async fn extract(
-        &mut self,
-        _video_url: String,
-        args: ExtractConfig,
-    ) -> anyhow::Result<Self::VideoSource> {
-        let ExtractConfig {
-            vis_cloud_helper: _,
-            api_key: _,
-            is_alternative: _,
-            user_agent: _,
-        } = args;
-
-        self.sources.push(VizCloudSources {
-            url: String::new(),
-            quality: String::new(),
-            is_m3u8: false,
-        });
-
-        Ok(Self {
-            sources: self.sources.clone(),
+    fn extract<'a>(
+        &'a self,
+        _video_url: &'a str,
+        _config: &'a ExtractConfig,
+    ) -> BoxFuture<'a, anyhow::Result<Self::VideoSource>> {
+        Box::pin(async move {
+            Ok(VizCloudData {
+                sources: vec![VizCloudSources {
+                    url: String::new(),
+                    quality: String::new(),
+                    is_m3u8: false,
+                }],
+            })
         })
     }
 }
@@ -0,0 +1,159 @@
+use visdom::types::Elements;
+
+use crate::{
+    html::create_html_fragment,
+    models::MediaStatus,
+    providers::anime::animepahe::{AnimePahe, AnimePaheInfo, AnimePaheLink},
+};
+
+pub(crate) trait AnimePaheHTML {
+    fn single_page(&self, media_html: String, id: &str, url: String) -> AnimePaheInfo;
+    fn info_links(&self, play_html: String) -> Vec<AnimePaheLink>;
+}
+
+impl AnimePaheHTML for AnimePahe {
+    fn single_page(&self, media_html: String, id: &str, url: String) -> AnimePaheInfo {
+        let elements = create_html_fragment(&media_html);
+
+        let info_parser = Info {
+            elements: &elements,
+        };
+
+        AnimePaheInfo {
+            id: id.to_string(),
+            title: info_parser.title(),
+            japanese_title: Some(info_parser.field("Japanese:")).filter(|t| !t.is_empty()),
+            url,
+            image: info_parser.image(),
+            cover: info_parser.cover(),
+            description: info_parser.description(),
+            genres: info_parser.genres(),
+            show_type: Some(info_parser.field("Type:")).filter(|t| !t.is_empty()),
+            status: media_status(&info_parser.field("Status:")),
+            release_date: Some(info_parser.field("Aired:")).filter(|d| !d.is_empty()),
+            season: Some(info_parser.field("Season:")).filter(|s| !s.is_empty()),
+            studios: info_parser.list("Studio:"),
+            synonyms: info_parser.list("Synonyms:"),
+            total_episodes: 0,
+            episodes: vec![],
+            specials: vec![],
+        }
+    }
+
+    fn info_links(&self, play_html: String) -> Vec<AnimePaheLink> {
+        let elements = create_html_fragment(&play_html);
+
+        let link_parser = Links {
+            elements: &elements,
+        };
+
+        link_parser.links()
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Info<'page, 'b> {
+    pub elements: &'b Elements<'page>,
+}
+
+impl<'page, 'b> Info<'page, 'b> {
+    pub fn title(self) -> String {
+        self.elements
+            .find("div.title-wrapper h1 span")
+            .first()
+            .text()
+            .trim()
+            .to_owned()
+    }
+
+    pub fn image(self) -> String {
+        self.elements
+            .find("div.anime-poster a")
+            .attr("href")
+            .map(|image| image.to_string())
+            .unwrap_or_default()
+    }
+
+    pub fn cover(self) -> Option<String> {
+        self.elements
+            .find("div.anime-cover")
+            .attr("data-src")
+            .map(|cover| cover.to_string())
+    }
+
+    pub fn description(self) -> String {
+        self.elements
+            .find("div.anime-synopsis")
+            .text()
+            .trim()
+            .to_owned()
+    }
+
+    pub fn genres(self) -> Vec<String> {
+        self.elements
+            .find("div.anime-genre ul li a")
+            .map(|_, genre| genre.text().trim().to_owned())
+    }
+
+    /// The text of a `<p><strong>label</strong> value</p>` entry
+    pub fn field(self, label: &str) -> String {
+        self.elements
+            .find(&format!("div.anime-info p:contains('{}')", label))
+            .first()
+            .text()
+            .trim()
+            .trim_start_matches(label)
+            .trim()
+            .to_owned()
+    }
+
+    /// A field holding several values separated by `,`
+    pub fn list(self, label: &str) -> Vec<String> {
+        self.field(label)
+            .split(',')
+            .map(|s| s.trim().to_owned())
+            .filter(|x| !x.is_empty())
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Links<'page, 'b> {
+    pub elements: &'b Elements<'page>,
+}
+
+impl<'page, 'b> Links<'page, 'b> {
+    /// The resolution menu of the player, one button per embed
+    pub fn links(self) -> Vec<AnimePaheLink> {
+        self.elements
+            .find("div#resolutionMenu button")
+            .map(|_, element| {
+                let attribute = |name: &str| {
+                    element
+                        .get_attribute(name)
+                        .map(|value| value.to_string())
+                        .unwrap_or_default()
+                };
+
+                AnimePaheLink {
+                    url: attribute("data-src"),
+                    fansub: attribute("data-fansub"),
+                    resolution: attribute("data-resolution"),
+                    audio: attribute("data-audio"),
+                }
+            })
+            .into_iter()
+            .filter(|link| !link.url.is_empty())
+            .collect()
+    }
+}
+
+/// The status shown on the anime page and in search results, e.g. "Finished Airing"
+pub(crate) fn media_status(label: &str) -> MediaStatus {
+    match label.trim() {
+        "Currently Airing" => MediaStatus::OnGoing,
+        "Finished Airing" => MediaStatus::Completed,
+        "Not yet aired" => MediaStatus::NotYetAired,
+        _ => MediaStatus::Unknown,
+    }
+}
@@ -0,0 +1,491 @@
+use visdom::{types::Elements, Vis};
+
+use crate::{
+    html::{create_html_fragment, movies::dramacool_html::media_status},
+    models::{IAnimeEpisode, SubOrSub},
+    providers::anime::gogoanime::{
+        Gogoanime, GogoanimeInfo, GogoanimeListing, GogoanimeRecentEpisode, GogoanimeServer,
+        GogoanimeTopAiring, BASE_URL,
+    },
+};
+
+/// What the ajax endpoint needs to list every episode of an anime
+pub struct EpisodeRange {
+    pub movie_id: String,
+    pub alias: String,
+    pub ep_end: String,
+}
+
+pub(crate) trait GogoanimeHTML {
+    fn parse_listings(&self, page_html: String) -> (Vec<GogoanimeListing>, bool, usize);
+    fn parse_recent_episodes(
+        &self,
+        page_html: String,
+    ) -> (Vec<GogoanimeRecentEpisode>, bool, usize);
+    fn parse_top_airing(&self, page_html: String) -> (Vec<GogoanimeTopAiring>, bool, usize);
+    fn single_page(
+        &self,
+        media_html: String,
+        id: &str,
+        url: String,
+    ) -> (GogoanimeInfo, Option<EpisodeRange>);
+    fn info_episode(&self, episode_html: String) -> Vec<IAnimeEpisode>;
+    fn info_server(&self, server_html: String) -> Vec<GogoanimeServer>;
+}
+
+impl GogoanimeHTML for Gogoanime {
+    fn parse_listings(&self, page_html: String) -> (Vec<GogoanimeListing>, bool, usize) {
+        let elements = create_html_fragment(&page_html);
+
+        let results = Listings {
+            elements: &elements,
+        }
+        .listings();
+
+        let page_parser = Page {
+            elements: &elements,
+        };
+
+        (
+            results,
+            page_parser.has_next_page(),
+            page_parser.total_pages(),
+        )
+    }
+
+    fn parse_recent_episodes(
+        &self,
+        page_html: String,
+    ) -> (Vec<GogoanimeRecentEpisode>, bool, usize) {
+        let elements = create_html_fragment(&page_html);
+
+        let results = Listings {
+            elements: &elements,
+        }
+        .recent_episodes();
+
+        let page_parser = Page {
+            elements: &elements,
+        };
+
+        (
+            results,
+            page_parser.has_next_page(),
+            page_parser.total_pages(),
+        )
+    }
+
+    fn parse_top_airing(&self, page_html: String) -> (Vec<GogoanimeTopAiring>, bool, usize) {
+        let elements = create_html_fragment(&page_html);
+
+        let results = Listings {
+            elements: &elements,
+        }
+        .top_airing();
+
+        let page_parser = Page {
+            elements: &elements,
+        };
+
+        (
+            results,
+            page_parser.has_next_page(),
+            page_parser.total_pages(),
+        )
+    }
+
+    fn single_page(
+        &self,
+        media_html: String,
+        id: &str,
+        url: String,
+    ) -> (GogoanimeInfo, Option<EpisodeRange>) {
+        let elements = create_html_fragment(&media_html);
+
+        let info_parser = Info {
+            elements: &elements,
+        };
+
+        let title = info_parser.title();
+
+        let info = GogoanimeInfo {
+            id: id.to_string(),
+            sub_or_dub: sub_or_dub(id, &title),
+            title,
+            url,
+            image: info_parser.image(),
+            description: info_parser.description(),
+            genres: info_parser.genres(),
+            show_type: Some(info_parser.field("Type:")).filter(|t| !t.is_empty()),
+            status: media_status(&info_parser.field("Status:")),
+            release_date: Some(info_parser.field("Released:")).filter(|d| !d.is_empty()),
+            other_names: info_parser
+                .field("Other name:")
+                .split([';', ','])
+                .map(|s| s.trim().to_owned())
+                .filter(|x| !x.is_empty())
+                .collect(),
+            total_episodes: 0,
+            episodes: vec![],
+        };
+
+        (info, info_parser.episode_range())
+    }
+
+    fn info_episode(&self, episode_html: String) -> Vec<IAnimeEpisode> {
+        let elements = create_html_fragment(&episode_html);
+
+        let episode_parser = Episodes {
+            elements: &elements,
+        };
+
+        episode_parser.episode_results()
+    }
+
+    fn info_server(&self, server_html: String) -> Vec<GogoanimeServer> {
+        let elements = create_html_fragment(&server_html);
+
+        let server_parser = Server {
+            elements: &elements,
+        };
+
+        server_parser.parse_server_html()
+    }
+}
+
+/// Dubs are separate entries, with "(Dub)" in the title and "-dub" at the end of the id
+fn sub_or_dub(id: &str, title: &str) -> SubOrSub {
+    if id.ends_with("-dub") || title.trim_end().ends_with("(Dub)") {
+        SubOrSub::Dub
+    } else {
+        SubOrSub::Sub
+    }
+}
+
+/// Parses labels such as "EP 20" or "Episode 20", specials like "EP 7.5" are rounded down
+fn episode_number(label: &str) -> Option<u32> {
+    label
+        .trim()
+        .trim_start_matches("Episode")
+        .trim_start_matches("EP")
+        .trim()
+        .parse::<f32>()
+        .ok()
+        .map(|number| number as u32)
+}
+
+#[derive(Clone, Copy)]
+pub struct Page<'page, 'b> {
+    pub elements: &'b Elements<'page>,
+}
+
+impl<'page, 'b> Page<'page, 'b> {
+    /// The pagination only links the pages around the current one
+    pub fn has_next_page(self) -> bool {
+        let pages = self.elements.find("ul.pagination-list li");
+
+        !pages.is_empty() && !pages.last().has_class("selected")
+    }
+
+    pub fn total_pages(self) -> usize {
+        self.elements
+            .find("ul.pagination-list li a")
+            .map(|_, element| element.text().trim().parse::<usize>().ok())
+            .into_iter()
+            .flatten()
+            .max()
+            .unwrap_or(1)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Listings<'page, 'b> {
+    pub elements: &'b Elements<'page>,
+}
+
+impl<'page, 'b> Listings<'page, 'b> {
+    pub fn listings(self) -> Vec<GogoanimeListing> {
+        self.elements
+            .find("div.last_episodes ul.items li")
+            .map(|_, element| {
+                let card = Vis::dom(element);
+
+                // Anime pages look like "/category/naruto"
+                let id = card
+                    .find("p.name a")
+                    .attr("href")?
+                    .to_string()
+                    .trim_start_matches('/')
+                    .trim_start_matches("category/")
+                    .to_owned();
+
+                let title = card.find("p.name a").text().trim().to_owned();
+
+                Some(GogoanimeListing {
+                    url: format!("{}/category/{}", BASE_URL, id),
+                    image: card
+                        .find("div.img img")
+                        .attr("src")
+                        .map(|image| image.to_string())
+                        .unwrap_or_default(),
+                    release_date: Some(
+                        card.find("p.released")
+                            .text()
+                            .trim()
+                            .trim_start_matches("Released:")
+                            .trim()
+                            .to_owned(),
+                    )
+                    .filter(|date| !date.is_empty()),
+                    sub_or_dub: sub_or_dub(&id, &title),
+                    title,
+                    id,
+                })
+            })
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    pub fn recent_episodes(self) -> Vec<GogoanimeRecentEpisode> {
+        self.elements
+            .find("div.last_episodes ul.items li")
+            .map(|_, element| {
+                let card = Vis::dom(element);
+
+                // Episode pages look like "/naruto-episode-5"
+                let episode_id = card
+                    .find("p.name a")
+                    .attr("href")?
+                    .to_string()
+                    .trim_start_matches('/')
+                    .to_owned();
+
+                let id = episode_id
+                    .rsplit_once("-episode-")
+                    .map(|(id, _)| id.to_owned())
+                    .unwrap_or(episode_id.clone());
+
+                Some(GogoanimeRecentEpisode {
+                    url: format!("{}/{}", BASE_URL, episode_id),
+                    episode_number: episode_number(&card.find("p.episode").text()),
+                    title: card.find("p.name a").text().trim().to_owned(),
+                    image: card
+                        .find("div.img img")
+                        .attr("src")
+                        .map(|image| image.to_string())
+                        .unwrap_or_default(),
+                    episode_id,
+                    id,
+                })
+            })
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    pub fn top_airing(self) -> Vec<GogoanimeTopAiring> {
+        self.elements
+            .find("div.added_series_body.popular ul li")
+            .map(|_, element| {
+                let card = Vis::dom(element);
+
+                let link = card.find("a").first();
+
+                let id = link
+                    .attr("href")?
+                    .to_string()
+                    .trim_start_matches('/')
+                    .trim_start_matches("category/")
+                    .to_owned();
+
+                // Thumbnails are set as "background: url('...');"
+                let image = card
+                    .find("div.thumbnail-popular")
+                    .attr("style")
+                    .and_then(|style| {
+                        let style = style.to_string();
+                        let (_, rest) = style.split_once("url(")?;
+                        let (image, _) = rest.split_once(')')?;
+                        Some(image.trim_matches(['\'', '"']).to_owned())
+                    })
+                    .unwrap_or_default();
+
+                Some(GogoanimeTopAiring {
+                    url: format!("{}/category/{}", BASE_URL, id),
+                    title: link
+                        .attr("title")
+                        .map(|title| title.to_string())
+                        .unwrap_or_else(|| link.text().trim().to_owned()),
+                    image,
+                    genres: card
+                        .find("p.genres a")
+                        .map(|_, genre| genre.text().trim().to_owned()),
+                    latest_episode: episode_number(&card.find("p:last-child a").text()),
+                    id,
+                })
+            })
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Info<'page, 'b> {
+    pub elements: &'b Elements<'page>,
+}
+
+impl<'page, 'b> Info<'page, 'b> {
+    pub fn title(self) -> String {
+        self.elements
+            .find("div.anime_info_body_bg h1")
+            .text()
+            .trim()
+            .to_owned()
+    }
+
+    pub fn image(self) -> String {
+        self.elements
+            .find("div.anime_info_body_bg img")
+            .attr("src")
+            .map(|image| image.to_string())
+            .unwrap_or_default()
+    }
+
+    pub fn description(self) -> String {
+        self.elements
+            .find("div.anime_info_body_bg div.description")
+            .text()
+            .trim()
+            .to_owned()
+    }
+
+    /// The text of a `<p class="type"><span>label</span>value</p>` entry
+    pub fn field(self, label: &str) -> String {
+        self.elements
+            .find(&format!(
+                "div.anime_info_body_bg p.type:contains('{}')",
+                label
+            ))
+            .text()
+            .trim()
+            .trim_start_matches(label)
+            .trim()
+            .to_owned()
+    }
+
+    pub fn genres(self) -> Vec<String> {
+        self.elements
+            .find("div.anime_info_body_bg p.type:contains('Genre:') a")
+            .map(|_, genre| {
+                genre
+                    .text()
+                    .trim()
+                    .trim_start_matches(',')
+                    .trim()
+                    .to_owned()
+            })
+    }
+
+    /// None when the anime has no episodes yet
+    pub fn episode_range(self) -> Option<EpisodeRange> {
+        let value = |selector: &str| {
+            self.elements
+                .find(selector)
+                .attr("value")
+                .map(|value| value.to_string())
+                .filter(|value| !value.is_empty())
+        };
+
+        Some(EpisodeRange {
+            movie_id: value("input#movie_id")?,
+            alias: value("input#alias_anime")?,
+            ep_end: self
+                .elements
+                .find("ul#episode_page li a")
+                .last()
+                .attr("ep_end")
+                .map(|ep_end| ep_end.to_string())
+                .filter(|ep_end| ep_end != "0")?,
+        })
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Episodes<'page, 'b> {
+    pub elements: &'b Elements<'page>,
+}
+
+impl<'page, 'b> Episodes<'page, 'b> {
+    /// The endpoint lists the latest episode first
+    pub fn episode_results(self) -> Vec<IAnimeEpisode> {
+        let mut episodes: Vec<IAnimeEpisode> = self
+            .elements
+            .find("ul#episode_related li a")
+            .map(|_, element| {
+                let card = Vis::dom(element);
+
+                let id = element
+                    .get_attribute("href")?
+                    .to_string()
+                    .trim()
+                    .trim_start_matches('/')
+                    .to_owned();
+
+                let label = card.find("div.name").text().trim().to_owned();
+
+                Some(IAnimeEpisode {
+                    url: Some(format!("{}/{}", BASE_URL, id)),
+                    number: episode_number(&label)?,
+                    title: Some(label),
+                    description: None,
+                    is_filler: None,
+                    image: None,
+                    release_date: None,
+                    id,
+                })
+            })
+            .into_iter()
+            .flatten()
+            .collect();
+
+        episodes.reverse();
+        episodes
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Server<'page, 'b> {
+    pub elements: &'b Elements<'page>,
+}
+
+impl<'page, 'b> Server<'page, 'b> {
+    pub fn parse_server_html(self) -> Vec<GogoanimeServer> {
+        self.elements
+            .find("div.anime_muti_link ul li")
+            .map(|_, element| {
+                let card = Vis::dom(element);
+
+                let mut url = card
+                    .find("a")
+                    .attr("data-video")
+                    .map(|value| value.to_string())
+                    .unwrap_or_default();
+
+                if url.starts_with("//") {
+                    url = format!("https:{}", url);
+                }
+
+                GogoanimeServer {
+                    name: element
+                        .get_attribute("class")
+                        .map(|value| value.to_string())
+                        .unwrap_or_default()
+                        .trim()
+                        .to_owned(),
+                    url,
+                }
+            })
+    }
+}
@@ -0,0 +1,3 @@
+pub mod animepahe_html;
+pub mod gogoanime_html;
+pub mod zoro_html;
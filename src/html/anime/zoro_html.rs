@@ -0,0 +1,386 @@
+use serde::Deserialize;
+use visdom::{types::Elements, Vis};
+
+use crate::{
+    html::create_html_fragment,
+    models::{IAnimeEpisode, MediaStatus, SubOrSub},
+    providers::anime::zoro::{Zoro, ZoroInfo, ZoroListing, ZoroServer, ZoroServers, BASE_URL},
+};
+
+pub(crate) trait ZoroHTML {
+    fn parse_listings(&self, page_html: String) -> (Vec<ZoroListing>, bool, usize);
+    fn single_page(&self, media_html: String, id: &str, url: String) -> ZoroInfo;
+    fn info_episode(&self, episode_html: String) -> Vec<IAnimeEpisode>;
+    fn info_server(&self, server_html: String) -> ZoroServers;
+}
+
+impl ZoroHTML for Zoro {
+    fn parse_listings(&self, page_html: String) -> (Vec<ZoroListing>, bool, usize) {
+        let elements = create_html_fragment(&page_html);
+
+        let results = Listings {
+            elements: &elements,
+        }
+        .listings();
+
+        let page_parser = Page {
+            elements: &elements,
+        };
+
+        (
+            results,
+            page_parser.has_next_page(),
+            page_parser.total_pages(),
+        )
+    }
+
+    fn single_page(&self, media_html: String, id: &str, url: String) -> ZoroInfo {
+        let elements = create_html_fragment(&media_html);
+
+        let info_parser = Info {
+            elements: &elements,
+        };
+
+        let (mal_id, anilist_id) = info_parser.sync_ids();
+        let (sub_episodes, dub_episodes) = info_parser.episode_counts();
+
+        ZoroInfo {
+            id: id.to_string(),
+            title: info_parser.title(),
+            japanese_title: info_parser.japanese_title(),
+            url,
+            image: info_parser.image(),
+            description: info_parser.description(),
+            show_type: info_parser.show_type(),
+            status: media_status(&info_parser.field("Status:")),
+            genres: info_parser.links("Genres:"),
+            studios: info_parser.links("Studios:"),
+            release_date: Some(info_parser.field("Aired:")).filter(|d| !d.is_empty()),
+            season: Some(info_parser.field("Premiered:")).filter(|s| !s.is_empty()),
+            rating: info_parser.field("MAL Score:").parse::<f32>().ok(),
+            mal_id,
+            anilist_id,
+            sub_or_dub: sub_or_dub(sub_episodes, dub_episodes),
+            sub_episodes,
+            dub_episodes,
+            total_episodes: 0,
+            episodes: vec![],
+        }
+    }
+
+    fn info_episode(&self, episode_html: String) -> Vec<IAnimeEpisode> {
+        let elements = create_html_fragment(&episode_html);
+
+        let episode_parser = Episodes {
+            elements: &elements,
+        };
+
+        episode_parser.episode_results()
+    }
+
+    fn info_server(&self, server_html: String) -> ZoroServers {
+        let elements = create_html_fragment(&server_html);
+
+        let server_parser = Server {
+            elements: &elements,
+        };
+
+        server_parser.parse_server_html()
+    }
+}
+
+pub fn media_status(label: &str) -> MediaStatus {
+    match label.trim() {
+        "Currently Airing" => MediaStatus::OnGoing,
+        "Finished Airing" => MediaStatus::Completed,
+        "Not yet aired" => MediaStatus::NotYetAired,
+        _ => MediaStatus::Unknown,
+    }
+}
+
+fn sub_or_dub(sub_episodes: Option<u32>, dub_episodes: Option<u32>) -> SubOrSub {
+    match (sub_episodes, dub_episodes) {
+        (Some(_), Some(_)) => SubOrSub::Both,
+        (None, Some(_)) => SubOrSub::Dub,
+        _ => SubOrSub::Sub,
+    }
+}
+
+/// The number of a `div.tick-sub` or `div.tick-dub` badge
+fn tick_count(card: &Elements, class: &str) -> Option<u32> {
+    card.find(&format!("div.{}", class))
+        .first()
+        .text()
+        .trim()
+        .parse::<u32>()
+        .ok()
+}
+
+/// Anime pages look like "/one-piece-100?ref=search"
+fn media_id(href: &str) -> String {
+    href.trim_start_matches('/')
+        .split('?')
+        .next()
+        .unwrap_or_default()
+        .to_owned()
+}
+
+#[derive(Clone, Copy)]
+pub struct Page<'page, 'b> {
+    pub elements: &'b Elements<'page>,
+}
+
+impl<'page, 'b> Page<'page, 'b> {
+    pub fn has_next_page(self) -> bool {
+        !self
+            .elements
+            .find("ul.pagination a[title='Next']")
+            .is_empty()
+    }
+
+    pub fn total_pages(self) -> usize {
+        let last_page = self
+            .elements
+            .find("ul.pagination a[title='Last']")
+            .attr("href")
+            .and_then(|href| {
+                href.to_string()
+                    .rsplit("page=")
+                    .next()
+                    .and_then(|page| page.parse::<usize>().ok())
+            });
+
+        // The last page links to no further pages
+        last_page.unwrap_or_else(|| {
+            self.elements
+                .find("ul.pagination li.active")
+                .text()
+                .trim()
+                .parse::<usize>()
+                .unwrap_or(1)
+        })
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Listings<'page, 'b> {
+    pub elements: &'b Elements<'page>,
+}
+
+impl<'page, 'b> Listings<'page, 'b> {
+    pub fn listings(self) -> Vec<ZoroListing> {
+        self.elements
+            .find("div.film_list-wrap div.flw-item")
+            .map(|_, element| {
+                let card = Vis::dom(element);
+
+                let link = card.find("h3.film-name a");
+                let id = media_id(&link.attr("href")?.to_string());
+
+                let sub_episodes = tick_count(&card, "tick-sub");
+                let dub_episodes = tick_count(&card, "tick-dub");
+
+                Some(ZoroListing {
+                    url: format!("{}/{}", BASE_URL, id),
+                    title: link.text().trim().to_owned(),
+                    japanese_title: link.attr("data-jname").map(|name| name.to_string()),
+                    image: card
+                        .find("div.film-poster img")
+                        .attr("data-src")
+                        .map(|image| image.to_string())
+                        .unwrap_or_default(),
+                    show_type: Some(card.find("div.fd-infor span.fdi-item").first().text())
+                        .map(|show_type| show_type.trim().to_owned())
+                        .filter(|show_type| !show_type.is_empty()),
+                    duration: Some(card.find("div.fd-infor span.fdi-duration").text())
+                        .map(|duration| duration.trim().to_owned())
+                        .filter(|duration| !duration.is_empty()),
+                    sub_or_dub: sub_or_dub(sub_episodes, dub_episodes),
+                    sub_episodes,
+                    dub_episodes,
+                    id,
+                })
+            })
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+}
+
+/// Contains the ids of the other sites embedded in the anime page
+#[derive(Deserialize)]
+struct SyncData {
+    mal_id: Option<String>,
+    anilist_id: Option<String>,
+}
+
+#[derive(Clone, Copy)]
+pub struct Info<'page, 'b> {
+    pub elements: &'b Elements<'page>,
+}
+
+impl<'page, 'b> Info<'page, 'b> {
+    pub fn title(self) -> String {
+        self.elements
+            .find("div.anisc-detail h2.film-name")
+            .text()
+            .trim()
+            .to_owned()
+    }
+
+    pub fn japanese_title(self) -> Option<String> {
+        self.elements
+            .find("div.anisc-detail h2.film-name")
+            .attr("data-jname")
+            .map(|name| name.to_string())
+    }
+
+    pub fn image(self) -> String {
+        self.elements
+            .find("div.anisc-poster img")
+            .attr("src")
+            .map(|image| image.to_string())
+            .unwrap_or_default()
+    }
+
+    pub fn description(self) -> String {
+        self.elements
+            .find("div.anisc-detail div.film-description div.text")
+            .text()
+            .trim()
+            .to_owned()
+    }
+
+    pub fn show_type(self) -> Option<String> {
+        Some(
+            self.elements
+                .find("div.anisc-detail div.film-stats span.item")
+                .first()
+                .text()
+                .trim()
+                .to_owned(),
+        )
+        .filter(|show_type| !show_type.is_empty())
+    }
+
+    pub fn episode_counts(self) -> (Option<u32>, Option<u32>) {
+        let stats = self.elements.find("div.anisc-detail div.film-stats");
+
+        (
+            tick_count(&stats, "tick-sub"),
+            tick_count(&stats, "tick-dub"),
+        )
+    }
+
+    /// The text of a `<div class="item"><span class="item-head">label</span>...</div>` entry
+    pub fn field(self, label: &str) -> String {
+        self.elements
+            .find(&format!(
+                "div.anisc-info div.item:contains('{}') span.name",
+                label
+            ))
+            .first()
+            .text()
+            .trim()
+            .to_owned()
+    }
+
+    /// A field holding a list of links
+    pub fn links(self, label: &str) -> Vec<String> {
+        self.elements
+            .find(&format!("div.anisc-info div.item:contains('{}') a", label))
+            .map(|_, link| link.text().trim().to_owned())
+    }
+
+    pub fn sync_ids(self) -> (Option<u32>, Option<u32>) {
+        let Ok(data) =
+            serde_json::from_str::<SyncData>(&self.elements.find("script#syncData").text())
+        else {
+            return (None, None);
+        };
+
+        let id = |id: Option<String>| id.and_then(|id| id.parse::<u32>().ok());
+
+        (id(data.mal_id), id(data.anilist_id))
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Episodes<'page, 'b> {
+    pub elements: &'b Elements<'page>,
+}
+
+impl<'page, 'b> Episodes<'page, 'b> {
+    pub fn episode_results(self) -> Vec<IAnimeEpisode> {
+        self.elements
+            .find("a.ep-item")
+            .map(|_, element| {
+                // Episode pages look like "/watch/one-piece-100?ep=2142"
+                let href = element.get_attribute("href")?.to_string();
+                let id = href
+                    .trim_start_matches('/')
+                    .trim_start_matches("watch/")
+                    .to_owned();
+
+                Some(IAnimeEpisode {
+                    number: element
+                        .get_attribute("data-number")?
+                        .to_string()
+                        .parse::<u32>()
+                        .ok()?,
+                    title: element
+                        .get_attribute("title")
+                        .map(|title| title.to_string()),
+                    description: None,
+                    is_filler: Some(
+                        element
+                            .get_attribute("class")
+                            .is_some_and(|class| class.to_string().contains("ssl-item-filler")),
+                    ),
+                    url: Some(format!("{}{}", BASE_URL, href)),
+                    image: None,
+                    release_date: None,
+                    id,
+                })
+            })
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Server<'page, 'b> {
+    pub elements: &'b Elements<'page>,
+}
+
+impl<'page, 'b> Server<'page, 'b> {
+    pub fn parse_server_html(self) -> ZoroServers {
+        let mut servers = ZoroServers::default();
+
+        self.elements.find("div.server-item").map(|_, element| {
+            let attribute = |name: &str| {
+                element
+                    .get_attribute(name)
+                    .map(|value| value.to_string())
+                    .unwrap_or_default()
+            };
+
+            let server = ZoroServer {
+                name: Vis::dom(element).find("a").text().trim().to_owned(),
+                server_id: attribute("data-server-id")
+                    .parse::<u32>()
+                    .unwrap_or_default(),
+                id: attribute("data-id"),
+            };
+
+            match attribute("data-type").as_str() {
+                "dub" => servers.dub.push(server),
+                "raw" => servers.raw.push(server),
+                _ => servers.sub.push(server),
+            }
+        });
+
+        servers
+    }
+}
@@ -1 +1,8 @@
+use visdom::{types::Elements, Vis};
+
+pub mod anime;
 pub mod movies;
+
+pub fn create_html_fragment(page_html: &str) -> Elements<'_> {
+    Vis::load(page_html).unwrap()
+}
@@ -1,31 +1,44 @@
+use chrono::NaiveDateTime;
 use visdom::{types::Elements, Vis};
 
-use crate::providers::movies::dramacool::{
-    DramaCool, DramaCoolEpisode, DramaCoolResult, DramaCoolServer, BASE_URL,
+use crate::{
+    html::create_html_fragment,
+    models::{MediaStatus, SubOrSub},
+    providers::movies::dramacool::{
+        DramaCool, DramaCoolEpisode, DramaCoolListing, DramaCoolRecentEpisode, DramaCoolResult,
+        DramaCoolServer, DramaCoolUpcoming, BASE_URL,
+    },
+    utils::util_funcs::Days,
 };
 
 pub(crate) trait DramaCoolHTML {
-    fn parse_search(&self, page_html: String) -> (Vec<Option<String>>, bool, usize);
-    fn single_page(&self, media_html: String, id: &str, url: String) -> DramaCoolResult;
-    fn info_episode(&self, episode_html: String) -> Vec<DramaCoolEpisode>;
+    fn parse_search(&self, page_html: String) -> (Vec<DramaCoolListing>, bool, usize);
+    fn single_page(&self, media_html: &str, id: &str, url: String) -> DramaCoolResult;
+    fn info_episode(&self, episode_html: &str) -> Vec<DramaCoolEpisode>;
     fn info_server(&self, server_html: String) -> Vec<DramaCoolServer>;
+    fn parse_listings(&self, page_html: String) -> (Vec<DramaCoolListing>, bool, usize);
+    fn parse_recent_episodes(
+        &self,
+        page_html: String,
+    ) -> (Vec<DramaCoolRecentEpisode>, bool, usize);
+    fn parse_upcoming(&self, home_html: String) -> Vec<DramaCoolUpcoming>;
 }
 
 impl DramaCoolHTML for DramaCool {
-    fn parse_search(&self, page_html: String) -> (Vec<Option<String>>, bool, usize) {
+    fn parse_search(&self, page_html: String) -> (Vec<DramaCoolListing>, bool, usize) {
         let elements = create_html_fragment(&page_html);
 
         let page_parser = Page { elements };
 
         (
-            page_parser.page_ids(),
+            page_parser.page_listings(),
             page_parser.has_next_page(),
             page_parser.total_pages(),
         )
     }
 
-    fn single_page(&self, media_html: String, id: &str, url: String) -> DramaCoolResult {
-        let elements = create_html_fragment(&media_html);
+    fn single_page(&self, media_html: &str, id: &str, url: String) -> DramaCoolResult {
+        let elements = create_html_fragment(media_html);
 
         let search_parser = Search {
             elements: &elements,
@@ -47,11 +60,14 @@ impl DramaCoolHTML for DramaCool {
             description: info_parser.description(),
             genres: info_parser.label("Genre:"),
             country: info_parser.label("Country:"),
+            original_network: info_parser.links("Original Network:"),
+            airs_on: info_parser.airs_on(),
+            duration: info_parser.duration(),
         }
     }
 
-    fn info_episode(&self, episode_html: String) -> Vec<DramaCoolEpisode> {
-        let elements = create_html_fragment(&episode_html);
+    fn info_episode(&self, episode_html: &str) -> Vec<DramaCoolEpisode> {
+        let elements = create_html_fragment(episode_html);
 
         let episode_parser = Episodes { elements };
 
@@ -65,10 +81,60 @@ impl DramaCoolHTML for DramaCool {
 
         server_parser.parse_server_html()
     }
+
+    fn parse_listings(&self, page_html: String) -> (Vec<DramaCoolListing>, bool, usize) {
+        let elements = create_html_fragment(&page_html);
+
+        let page_parser = Page { elements };
+
+        (
+            page_parser.listings("div.content-left ul.list-episode-item li a"),
+            page_parser.has_next_page(),
+            page_parser.total_pages(),
+        )
+    }
+
+    fn parse_recent_episodes(
+        &self,
+        page_html: String,
+    ) -> (Vec<DramaCoolRecentEpisode>, bool, usize) {
+        let elements = create_html_fragment(&page_html);
+
+        let results = Recent {
+            elements: &elements,
+        }
+        .recent_episodes();
+
+        let page_parser = Page { elements };
+
+        (
+            results,
+            page_parser.has_next_page(),
+            page_parser.total_pages(),
+        )
+    }
+
+    fn parse_upcoming(&self, home_html: String) -> Vec<DramaCoolUpcoming> {
+        let elements = create_html_fragment(&home_html);
+
+        let recent_parser = Recent {
+            elements: &elements,
+        };
+
+        recent_parser.upcoming()
+    }
 }
 
-pub fn create_html_fragment(page_html: &str) -> Elements<'_> {
-    Vis::load(page_html).unwrap()
+/// Splits a listing title like "Vincenzo (2021)" into the title and its year
+pub(crate) fn split_year(full_title: String) -> (String, Option<String>) {
+    match full_title.rsplit_once(" (").and_then(|(title, year)| {
+        year.strip_suffix(')')
+            .filter(|year| year.len() == 4 && year.bytes().all(|b| b.is_ascii_digit()))
+            .map(|year| (title.to_owned(), year.to_owned()))
+    }) {
+        Some((title, year)) => (title, Some(year)),
+        None => (full_title, None),
+    }
 }
 
 pub struct Page<'a> {
@@ -95,16 +161,40 @@ impl<'a> Page<'a> {
         1
     }
 
-    pub fn page_ids(&self) -> Vec<Option<String>> {
+    pub fn page_listings(&self) -> Vec<DramaCoolListing> {
+        self.listings("div.block div.tab-content ul.list-episode-item li a")
+    }
+
+    pub fn listings(&self, selector: &str) -> Vec<DramaCoolListing> {
         self.elements
-            .find("div.block div.tab-content ul.list-episode-item li a")
+            .find(selector)
             .map(|_, element| {
-                element
+                let card = Vis::dom(element);
+
+                let id = element
                     .get_attribute("href")?
                     .to_string()
                     .strip_prefix('/')
-                    .map(String::from)
+                    .map(String::from)?;
+
+                let (title, release_date) =
+                    split_year(card.find("h3.title").text().trim().to_owned());
+
+                Some(DramaCoolListing {
+                    title,
+                    url: format!("{}/{}", BASE_URL, id),
+                    image: card
+                        .find("img")
+                        .attr("data-original")
+                        .map(|image| image.to_string())
+                        .unwrap_or_default(),
+                    release_date,
+                    id,
+                })
             })
+            .into_iter()
+            .flatten()
+            .collect()
     }
 }
 
@@ -116,7 +206,7 @@ pub struct Search<'page, 'b> {
 
 impl<'page, 'b> Search<'page, 'b> {
     pub fn title(self) -> String {
-        match self.id.split('/').last() {
+        match self.id.split('/').next_back() {
             Some(title) => title.to_owned(),
             None => String::new(),
         }
@@ -132,20 +222,15 @@ impl<'page, 'b> Search<'page, 'b> {
         String::new()
     }
 
-    pub fn release_date(self) -> String {
-        let release_date = self
-            .elements
+    pub fn release_date(self) -> Option<u32> {
+        self.elements
             .find("div.details div.info p:contains('Released:')")
             .text()
             .replace("Released:", "")
             .trim()
-            .to_owned();
-
-        if release_date == "0" {
-            return String::from("N/A");
-        }
-
-        release_date
+            .parse::<u32>()
+            .ok()
+            .filter(|year| *year != 0)
     }
 
     pub fn other_names(&self) -> Vec<String> {
@@ -161,13 +246,14 @@ pub struct Info<'page, 'b> {
 }
 
 impl<'page, 'b> Info<'page, 'b> {
-    pub fn status(&self) -> String {
-        self.elements
+    pub fn status(&self) -> MediaStatus {
+        let status = self
+            .elements
             .find("div.details div.info p:contains('Status:')")
             .text()
-            .replace("Status:", "")
-            .trim()
-            .to_owned()
+            .replace("Status:", "");
+
+        media_status(&status)
     }
 
     pub fn description(&self) -> String {
@@ -194,6 +280,34 @@ impl<'page, 'b> Info<'page, 'b> {
             .filter(|x| !x.is_empty())
             .collect()
     }
+
+    pub fn links(&self, label: &str) -> Vec<String> {
+        self.elements
+            .find(&format!("div.details div.info p:contains('{}') a", label))
+            .map(|_, element| element.text().trim().to_owned())
+    }
+
+    pub fn airs_on(&self) -> Vec<Days> {
+        self.elements
+            .find("div.details div.info p:contains('Airs On:')")
+            .text()
+            .replace("Airs On:", "")
+            .split(',')
+            .filter_map(|day| day.parse::<Days>().ok())
+            .collect()
+    }
+
+    pub fn duration(&self) -> Option<String> {
+        let duration = self
+            .elements
+            .find("div.details div.info p:contains('Duration:')")
+            .text()
+            .replace("Duration:", "")
+            .trim()
+            .to_owned();
+
+        (!duration.is_empty()).then_some(duration)
+    }
 }
 
 pub struct Episodes<'a> {
@@ -260,11 +374,21 @@ impl<'a> Episodes<'a> {
         let mut episodes: Vec<DramaCoolEpisode> = vec![];
 
         for i in 0..episode_ids.len() {
+            let title = episode_titles[i].trim().to_owned();
+
             episodes.push(DramaCoolEpisode {
                 id: episode_ids[i].clone(),
-                title: episode_titles[i].clone(),
-                sub_type: episode_sub_types[i].clone(),
-                release_date: episode_release_dates[i].clone(),
+                // Titles look like "Vincenzo Episode 20"
+                number: title
+                    .rsplit_once("Episode ")
+                    .and_then(|(_, number)| number.trim().parse::<u32>().ok()),
+                title,
+                sub_type: sub_type(&episode_sub_types[i]),
+                release_date: NaiveDateTime::parse_from_str(
+                    episode_release_dates[i].trim(),
+                    "%Y-%m-%d %H:%M:%S",
+                )
+                .ok(),
                 url: episode_urls[i].clone(),
             })
         }
@@ -273,6 +397,99 @@ impl<'a> Episodes<'a> {
     }
 }
 
+#[derive(Clone, Copy)]
+pub struct Recent<'page, 'b> {
+    pub elements: &'b Elements<'page>,
+}
+
+impl<'page, 'b> Recent<'page, 'b> {
+    pub fn recent_episodes(self) -> Vec<DramaCoolRecentEpisode> {
+        self.elements
+            .find("div.content-left ul.list-episode-item li")
+            .map(|_, element| {
+                let card = Vis::dom(element);
+
+                let href = card.find("a.img").attr("href")?.to_string();
+                let id = href.split(".html").next()?.to_owned();
+
+                Some(DramaCoolRecentEpisode {
+                    title: card.find("h3.title").text().trim().to_owned(),
+                    url: format!("{}{}", BASE_URL, href),
+                    image: card
+                        .find("img")
+                        .attr("data-original")
+                        .map(|image| image.to_string())
+                        .unwrap_or_default(),
+                    sub_type: sub_type(&card.find("span.type").text()),
+                    episode_number: episode_number(&card.find("span.ep").text()),
+                    release_date: card.find("span.time").text().trim().to_owned(),
+                    id,
+                })
+            })
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    pub fn upcoming(self) -> Vec<DramaCoolUpcoming> {
+        self.elements
+            .find("div.content-right div.block-tab-right:contains('Upcoming') ul li")
+            .map(|_, element| {
+                let card = Vis::dom(element);
+
+                let id = card
+                    .find("a")
+                    .attr("href")?
+                    .to_string()
+                    .strip_prefix('/')
+                    .map(String::from)?;
+
+                Some(DramaCoolUpcoming {
+                    title: card.find("h3.title").text().trim().to_owned(),
+                    url: format!("{}/{}", BASE_URL, id),
+                    image: card
+                        .find("img")
+                        .attr("data-original")
+                        .map(|image| image.to_string())
+                        .unwrap_or_default(),
+                    episode_number: episode_number(&card.find("span.ep").text()),
+                    release_date: card.find("span.time").text().trim().to_owned(),
+                    id,
+                })
+            })
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+}
+
+pub fn media_status(label: &str) -> MediaStatus {
+    match label.trim() {
+        "Ongoing" => MediaStatus::OnGoing,
+        "Completed" => MediaStatus::Completed,
+        "Upcoming" => MediaStatus::NotYetAired,
+        _ => MediaStatus::Unknown,
+    }
+}
+
+/// Episodes are labelled "RAW" until their subtitles are out
+pub fn sub_type(label: &str) -> SubOrSub {
+    match label.trim() {
+        "RAW" => SubOrSub::Raw,
+        _ => SubOrSub::Sub,
+    }
+}
+
+/// Parses episode labels such as "EP 20"
+fn episode_number(label: &str) -> Option<u32> {
+    label
+        .trim()
+        .trim_start_matches("EP")
+        .trim()
+        .parse::<u32>()
+        .ok()
+}
+
 pub struct Server<'a> {
     pub elements: Elements<'a>,
 }
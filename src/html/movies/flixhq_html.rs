@@ -1,80 +1,93 @@
 use crate::{
+    html::create_html_fragment,
     models::types::TvType,
-    providers::movies::flixhq::{FlixHQ, FlixHQEpisode, FlixHQResult, FlixHQServer, BASE_URL},
+    providers::movies::flixhq::{
+        FlixHQ, FlixHQEpisode, FlixHQHome, FlixHQListing, FlixHQResult, FlixHQSeason,
+        FlixHQSelectors, FlixHQServer, FlixHQSpotlight,
+    },
 };
 
+use anyhow::bail;
 use visdom::{types::Elements, Vis};
 
 pub(crate) trait FlixHQHTML {
-    fn parse_recent_shows(&self, recent_html: String) -> Vec<Option<String>>;
-    fn parse_recent_movies(&self, recent_html: String) -> Vec<Option<String>>;
-    fn parse_trending_movies(&self, trending_html: String) -> Vec<Option<String>>;
-    fn parse_trending_shows(&self, trending_html: String) -> Vec<Option<String>>;
-    fn parse_search(&self, page_html: String) -> (Vec<Option<String>>, bool, usize);
-    fn single_page(&self, media_html: String, id: &str, url: String) -> FlixHQResult;
-    fn info_season(&self, season_html: String) -> Vec<String>;
-    fn info_episode(&self, episode_html: String) -> Vec<FlixHQEpisode>;
+    fn parse_search(&self, page_html: String) -> (Vec<FlixHQListing>, bool, usize);
+    fn parse_home(&self, home_html: String) -> FlixHQHome;
+    fn single_page(
+        &self,
+        media_html: String,
+        id: &str,
+        url: String,
+    ) -> anyhow::Result<FlixHQResult>;
+    fn info_season(&self, season_html: String) -> Vec<FlixHQSeason>;
+    fn info_episode(&self, episode_html: String, season: usize) -> Vec<FlixHQEpisode>;
     fn info_server(&self, server_html: String, media_id: &str) -> Vec<FlixHQServer>;
 }
 
 impl FlixHQHTML for FlixHQ {
-    fn parse_recent_shows(&self, recent_html: String) -> Vec<Option<String>> {
-        let elements = create_html_fragment(&recent_html);
-
-        let trending_parser = Recent { elements };
-
-        trending_parser.recent_shows()
-    }
-
-    fn parse_recent_movies(&self, recent_html: String) -> Vec<Option<String>> {
-        let elements = create_html_fragment(&recent_html);
+    fn parse_search(&self, page_html: String) -> (Vec<FlixHQListing>, bool, usize) {
+        let elements = create_html_fragment(&page_html);
 
-        let trending_parser = Recent { elements };
+        let page_parser = Page {
+            elements,
+            base_url: self.profile().base_url,
+            selectors: self.profile().selectors,
+        };
 
-        trending_parser.recent_movies()
+        (
+            page_parser.page_listings(),
+            page_parser.has_next_page(),
+            page_parser.total_pages(),
+        )
     }
 
-    fn parse_trending_movies(&self, trending_html: String) -> Vec<Option<String>> {
-        let elements = create_html_fragment(&trending_html);
+    fn parse_home(&self, home_html: String) -> FlixHQHome {
+        let elements = create_html_fragment(&home_html);
 
-        let trending_parser = Trending { elements };
+        let selectors = self.profile().selectors;
 
-        trending_parser.trending_movies()
-    }
-
-    fn parse_trending_shows(&self, trending_html: String) -> Vec<Option<String>> {
-        let elements = create_html_fragment(&trending_html);
-
-        let trending_parser = Trending { elements };
+        let home_parser = Home {
+            elements,
+            base_url: self.profile().base_url,
+            selectors,
+        };
 
-        trending_parser.trending_shows()
+        FlixHQHome {
+            spotlight: home_parser.spotlight(),
+            trending_movies: home_parser.section(selectors.trending_movies),
+            trending_shows: home_parser.section(selectors.trending_shows),
+            recent_movies: home_parser.section(selectors.recent_movies),
+            recent_shows: home_parser.section(selectors.recent_shows),
+            coming_soon: home_parser.section(selectors.coming_soon),
+        }
     }
 
-    fn parse_search(&self, page_html: String) -> (Vec<Option<String>>, bool, usize) {
-        let elements = create_html_fragment(&page_html);
-
-        let page_parser = Page { elements };
-
-        (
-            page_parser.page_ids(),
-            page_parser.has_next_page(),
-            page_parser.total_pages(),
-        )
-    }
+    fn single_page(
+        &self,
+        media_html: String,
+        id: &str,
+        url: String,
+    ) -> anyhow::Result<FlixHQResult> {
+        // Ids look like "movie/watch-vincenzo-67955" or "tv/watch-vincenzo-67955"
+        let Some(media_type) = media_type_from_id(id) else {
+            bail!("Unknown media type for id {}", id);
+        };
 
-    fn single_page(&self, media_html: String, id: &str, url: String) -> FlixHQResult {
         let elements = create_html_fragment(&media_html);
 
+        let selectors = self.profile().selectors;
+
         let search_parser = Search {
             elements: &elements,
-            id,
+            selectors,
         };
 
         let info_parser = Info {
             elements: &elements,
+            selectors,
         };
 
-        FlixHQResult {
+        Ok(FlixHQResult {
             cover: search_parser.cover(),
             title: search_parser.title(),
             url,
@@ -82,7 +95,7 @@ impl FlixHQHTML for FlixHQ {
             country: info_parser.label(1, "Country:"),
             genres: info_parser.label(2, "Genre:"),
             release_date: info_parser.label(3, "Released:").join(""),
-            media_type: search_parser.media_type(),
+            media_type,
             id: id.to_string(),
             description: info_parser.description(),
             quality: info_parser.quality(),
@@ -91,55 +104,60 @@ impl FlixHQHTML for FlixHQ {
             production: info_parser.label(4, "Production:"),
             casts: info_parser.label(5, "Casts:"),
             tags: info_parser.label(6, "Tags:"),
-        }
+        })
     }
 
-    fn info_season(&self, season_html: String) -> Vec<String> {
+    fn info_season(&self, season_html: String) -> Vec<FlixHQSeason> {
         let elements = create_html_fragment(&season_html);
 
-        let season_parser = Seasons { elements };
+        let season_parser = Seasons {
+            elements,
+            selectors: self.profile().selectors,
+        };
 
-        season_parser
-            .season_results()
-            .into_iter()
-            .flatten()
-            .collect()
+        season_parser.season_results()
     }
 
-    fn info_episode(&self, episode_html: String) -> Vec<FlixHQEpisode> {
+    fn info_episode(&self, episode_html: String, season: usize) -> Vec<FlixHQEpisode> {
         let elements = create_html_fragment(&episode_html);
 
-        let episode_parser = Episodes { elements };
+        let episode_parser = Episodes {
+            elements,
+            base_url: self.profile().base_url,
+            selectors: self.profile().selectors,
+        };
 
-        episode_parser.episode_results()
+        episode_parser.episode_results(season)
     }
 
     fn info_server(&self, server_html: String, media_id: &str) -> Vec<FlixHQServer> {
         let elements = create_html_fragment(&server_html);
 
-        let server_parser = Server { elements };
+        let server_parser = Server {
+            elements,
+            base_url: self.profile().base_url,
+            selectors: self.profile().selectors,
+        };
 
         server_parser.parse_server_html(media_id)
     }
 }
 
-pub fn create_html_fragment(page_html: &str) -> Elements<'_> {
-    Vis::load(page_html).unwrap()
-}
-
 pub struct Page<'a> {
     pub elements: Elements<'a>,
+    pub base_url: &'a str,
+    pub selectors: FlixHQSelectors,
 }
 
 impl<'a> Page<'a> {
     pub fn has_next_page(&self) -> bool {
         self.elements
-        .find("div.pre-pagination:nth-child(3) > nav:nth-child(1) > ul:nth-child(1) > li:nth-child(1)")
-        .has_class("active")
+            .find(self.selectors.first_page)
+            .has_class("active")
     }
 
     pub fn total_pages(&self) -> usize {
-        let total_pages_attr = self.elements.find("div.pre-pagination:nth-child(3) > nav:nth-child(1) > ul:nth-child(1) > li.page-item:last-child a").attr("href");
+        let total_pages_attr = self.elements.find(self.selectors.last_page).attr("href");
 
         if let Some(total_pages) = total_pages_attr {
             if let Some(pages) = total_pages.to_string().rsplit('=').next() {
@@ -150,29 +168,81 @@ impl<'a> Page<'a> {
         1
     }
 
-    pub fn page_ids(&self) -> Vec<Option<String>> {
-        self.elements.find("div.film-poster > a").map(|_, element| {
-            element
-                .get_attribute("href")?
+    pub fn page_listings(&self) -> Vec<FlixHQListing> {
+        listings(&self.elements, self.selectors, self.base_url)
+    }
+}
+
+/// Parses every film card inside `elements`
+fn listings(elements: &Elements, selectors: FlixHQSelectors, base_url: &str) -> Vec<FlixHQListing> {
+    elements
+        .find(selectors.card)
+        .map(|_, element| {
+            let card = Vis::dom(element);
+
+            let id = card
+                .find(selectors.card_link)
+                .attr("href")?
                 .to_string()
                 .strip_prefix('/')
-                .map(String::from)
+                .map(String::from)?;
+
+            let media_type = match card.find("span.fdi-type").text().trim() {
+                "TV" => TvType::TvSeries,
+                "Movie" => TvType::Movie,
+                _ => media_type_from_id(&id)?,
+            };
+
+            // Movies show their release year, tv shows show "SS 1" instead
+            let release_date = card
+                .find("div.fd-infor > span.fdi-item")
+                .first()
+                .text()
+                .trim()
+                .parse::<u32>()
+                .ok()
+                .map(|year| year.to_string());
+
+            Some(FlixHQListing {
+                title: card
+                    .find(selectors.card_title)
+                    .attr("title")
+                    .map(|title| title.to_string())
+                    .unwrap_or_else(|| card.find(selectors.card_title).text().trim().to_owned()),
+                url: format!("{}/{}", base_url, id),
+                image: card
+                    .find(selectors.card_image)
+                    .attr("data-src")
+                    .or_else(|| card.find(selectors.card_image).attr("src"))
+                    .map(|image| image.to_string())
+                    .unwrap_or_default(),
+                release_date,
+                media_type,
+                id,
+            })
         })
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+fn media_type_from_id(id: &str) -> Option<TvType> {
+    match id.split('/').next() {
+        Some("tv") => Some(TvType::TvSeries),
+        Some("movie") => Some(TvType::Movie),
+        _ => None,
     }
 }
 
 #[derive(Clone, Copy)]
 pub struct Search<'page, 'b> {
     pub elements: &'b Elements<'page>,
-    pub id: &'b str,
+    pub selectors: FlixHQSelectors,
 }
 
 impl<'page, 'b> Search<'page, 'b> {
     pub fn image(&self) -> String {
-        let image_attr = self
-            .elements
-            .find("div.m_i-d-poster > div > img")
-            .attr("src");
+        let image_attr = self.elements.find(self.selectors.detail_image).attr("src");
 
         if let Some(image) = image_attr {
             return image.to_string();
@@ -183,16 +253,17 @@ impl<'page, 'b> Search<'page, 'b> {
 
     pub fn title(&self) -> String {
         self.elements
-        .find(
-            "#main-wrapper > div.movie_information > div > div.m_i-detail > div.m_i-d-content > h2",
-        )
-        .text()
-        .trim()
-        .to_owned()
+            .find(self.selectors.detail_title)
+            .text()
+            .trim()
+            .to_owned()
     }
 
     pub fn cover(&self) -> String {
-        let cover_attr = self.elements.find("div.w_b-cover").attr("style");
+        let cover_attr = self
+            .elements
+            .find(self.selectors.detail_cover)
+            .attr("style");
         if let Some(cover) = cover_attr {
             return cover
                 .to_string()
@@ -202,28 +273,21 @@ impl<'page, 'b> Search<'page, 'b> {
 
         String::new()
     }
-
-    pub fn media_type(&self) -> TvType {
-        match self.id.split('/').next() {
-            Some("tv") => TvType::TvSeries,
-            Some("movie") => TvType::Movie,
-            _ => todo!(),
-        }
-    }
 }
 
 /// Remy clarke was here & some red guy
 #[derive(Clone, Copy)]
 pub struct Info<'page, 'b> {
     pub elements: &'b Elements<'page>,
+    pub selectors: FlixHQSelectors,
 }
 
 impl<'page, 'b> Info<'page, 'b> {
     pub fn label(&self, index: usize, label: &str) -> Vec<String> {
         self.elements
             .find(&format!(
-                "div.m_i-d-content > div.elements > div:nth-child({})",
-                index
+                "{}:nth-child({})",
+                self.selectors.detail_label, index
             ))
             .text()
             .replace(label, "")
@@ -234,12 +298,16 @@ impl<'page, 'b> Info<'page, 'b> {
     }
 
     pub fn description(&self) -> String {
-        self.elements.find("#main-wrapper > div.movie_information > div > div.m_i-detail > div.m_i-d-content > div.description").text().trim().to_owned()
+        self.elements
+            .find(self.selectors.detail_description)
+            .text()
+            .trim()
+            .to_owned()
     }
 
     pub fn quality(&self) -> String {
         self.elements
-            .find("span.item:nth-child(1)")
+            .find(&format!("{}:nth-child(1)", self.selectors.detail_stat))
             .text()
             .trim()
             .to_owned()
@@ -247,7 +315,7 @@ impl<'page, 'b> Info<'page, 'b> {
 
     pub fn rating(&self) -> String {
         self.elements
-            .find("span.item:nth-child(2)")
+            .find(&format!("{}:nth-child(2)", self.selectors.detail_stat))
             .text()
             .trim()
             .to_owned()
@@ -255,7 +323,7 @@ impl<'page, 'b> Info<'page, 'b> {
 
     pub fn duration(&self) -> String {
         self.elements
-            .find("span.item:nth-child(3)")
+            .find(&format!("{}:nth-child(3)", self.selectors.detail_stat))
             .text()
             .trim()
             .to_owned()
@@ -264,37 +332,47 @@ impl<'page, 'b> Info<'page, 'b> {
 
 pub struct Episodes<'a> {
     pub elements: Elements<'a>,
+    pub base_url: &'a str,
+    pub selectors: FlixHQSelectors,
 }
 
 impl<'a> Episodes<'a> {
     pub fn episode_title(&self) -> Vec<Option<String>> {
-        self.elements.find("ul > li > a").map(|_, element| {
-            element
-                .get_attribute("title")
-                .map(|value| value.to_string())
-        })
+        self.elements
+            .find(self.selectors.episode)
+            .map(|_, element| {
+                element
+                    .get_attribute("title")
+                    .map(|value| value.to_string())
+            })
     }
 
     pub fn episode_id(&self) -> Vec<Option<String>> {
-        self.elements.find("ul > li > a").map(|_, element| {
-            element
-                .get_attribute("data-id")
-                .map(|value| value.to_string())
-        })
+        self.elements
+            .find(self.selectors.episode)
+            .map(|_, element| {
+                element
+                    .get_attribute("data-id")
+                    .map(|value| value.to_string())
+            })
     }
 
-    pub fn episode_results(&self) -> Vec<FlixHQEpisode> {
+    pub fn episode_results(&self, season: usize) -> Vec<FlixHQEpisode> {
         let episode_titles = self.episode_title();
         let episode_ids = self.episode_id();
 
         let mut episodes: Vec<FlixHQEpisode> = vec![];
 
-        for (id, title) in episode_ids.iter().zip(episode_titles.iter()) {
+        for (index, (id, title)) in episode_ids.iter().zip(episode_titles.iter()).enumerate() {
             if let Some(id) = id {
-                let url = format!("{}/ajax/v2/episode/servers/{}", BASE_URL, id);
+                let url = format!("{}/ajax/v2/episode/servers/{}", self.base_url, id);
+                let (number, title) = split_episode_title(title.as_deref().unwrap_or_default());
+
                 episodes.push(FlixHQEpisode {
                     id: id.clone(),
-                    title: title.clone().unwrap_or(String::new()),
+                    number: number.unwrap_or(index as u32 + 1),
+                    season,
+                    title,
                     url,
                 });
             }
@@ -304,27 +382,66 @@ impl<'a> Episodes<'a> {
     }
 }
 
+/// Splits titles like "Eps 3: Foo" into the episode number and "Foo"
+fn split_episode_title(full_title: &str) -> (Option<u32>, String) {
+    let full_title = full_title.trim();
+
+    if let Some((label, title)) = full_title.split_once(':') {
+        if let Some(number) = label
+            .trim()
+            .strip_prefix("Eps")
+            .and_then(|number| number.trim().parse::<u32>().ok())
+        {
+            return (Some(number), title.trim().to_owned());
+        }
+    }
+
+    (None, full_title.to_owned())
+}
+
 pub struct Seasons<'a> {
     pub elements: Elements<'a>,
+    pub selectors: FlixHQSelectors,
 }
 
 impl<'a> Seasons<'a> {
-    pub fn season_results(&self) -> Vec<Option<String>> {
-        self.elements.find(".dropdown-menu > a").map(|_, element| {
-            element
-                .get_attribute("data-id")
-                .map(|value| value.to_string())
-        })
+    pub fn season_results(&self) -> Vec<FlixHQSeason> {
+        self.elements
+            .find(self.selectors.season)
+            .map(|index, element| {
+                let id = element.get_attribute("data-id")?.to_string();
+                let title = element.text().trim().to_owned();
+
+                // Seasons are labelled "Season 1", fall back to their position otherwise
+                let number = title
+                    .rsplit(' ')
+                    .next()
+                    .and_then(|number| number.parse::<usize>().ok())
+                    .unwrap_or(index + 1);
+
+                Some(FlixHQSeason {
+                    id,
+                    number,
+                    title,
+                    total_episodes: 0,
+                    episodes: vec![],
+                })
+            })
+            .into_iter()
+            .flatten()
+            .collect()
     }
 }
 
 pub struct Server<'a> {
     pub elements: Elements<'a>,
+    pub base_url: &'a str,
+    pub selectors: FlixHQSelectors,
 }
 
 impl<'a> Server<'a> {
     pub fn parse_server_html(&self, media_id: &str) -> Vec<FlixHQServer> {
-        self.elements.find("ul > li > a").map(|_, element| {
+        self.elements.find(self.selectors.server).map(|_, element| {
             let id = element
                 .get_attribute("id")
                 .map(|value| value.to_string().replace("watch-", ""))
@@ -334,7 +451,7 @@ impl<'a> Server<'a> {
                 .get_attribute("title")
                 .map(|value| value.to_string().trim_start_matches("Server ").to_owned());
 
-            let url = format!("{}/watch-{}.{}", BASE_URL, media_id, id);
+            let url = format!("{}/watch-{}.{}", self.base_url, media_id, id);
             let name = name.unwrap_or(String::from(""));
 
             FlixHQServer { name, url }
@@ -342,59 +459,83 @@ impl<'a> Server<'a> {
     }
 }
 
-pub struct Recent<'a> {
+pub struct Home<'a> {
     pub elements: Elements<'a>,
+    pub base_url: &'a str,
+    pub selectors: FlixHQSelectors,
 }
 
-impl<'a> Recent<'a> {
-    pub fn recent_movies(&self) -> Vec<Option<String>> {
-        self.elements.find("#main-wrapper > div > section:nth-child(6) > div.block_area-content.block_area-list.film_list.film_list-grid > div > div.flw-item > div.film-poster > a").map(|_, element| {
-            element
-                .get_attribute("href")?
-                .to_string()
-                .strip_prefix('/')
-                .map(String::from)
-        })
-    }
-
-    pub fn recent_shows(&self) -> Vec<Option<String>> {
-        self.elements.find("#main-wrapper > div > section:nth-child(7) > div.block_area-content.block_area-list.film_list.film_list-grid > div > div.flw-item > div.film-poster > a").map(|_, element| {
-            element
-                 .get_attribute("href")?
-                .to_string()
-                .strip_prefix('/')
-                .map(String::from)
-
-        })
-    }
-}
-
-pub struct Trending<'a> {
-    pub elements: Elements<'a>,
-}
-
-impl<'a> Trending<'a> {
-    pub fn trending_movies(&self) -> Vec<Option<String>> {
+impl<'a> Home<'a> {
+    pub fn spotlight(&self) -> Vec<FlixHQSpotlight> {
         self.elements
-            .find("div#trending-movies div.film_list-wrap div.flw-item div.film-poster a")
+            .find(self.selectors.spotlight)
             .map(|_, element| {
-                element
-                    .get_attribute("href")?
+                let slide = Vis::dom(element);
+
+                let title_link = slide.find("h3.film-title > a");
+
+                let id = title_link
+                    .attr("href")?
                     .to_string()
                     .strip_prefix('/')
-                    .map(String::from)
+                    .map(String::from)?;
+
+                let cover = element
+                    .get_attribute("style")
+                    .map(|style| {
+                        style
+                            .to_string()
+                            .replace("background-image: url(", "")
+                            .replace([')', ';'], "")
+                            .trim()
+                            .to_owned()
+                    })
+                    .unwrap_or_default();
+
+                let label = |label: &str| {
+                    slide
+                        .find(&format!(
+                            "div.sc-detail > div.scd-item:contains('{}')",
+                            label
+                        ))
+                        .text()
+                        .replace(label, "")
+                        .trim()
+                        .to_owned()
+                };
+
+                Some(FlixHQSpotlight {
+                    title: title_link
+                        .attr("title")
+                        .map(|title| title.to_string())
+                        .unwrap_or_default(),
+                    url: format!("{}/{}", self.base_url, id),
+                    cover,
+                    description: slide.find("p.sc-desc").text().trim().to_owned(),
+                    quality: slide.find("span.quality").text().trim().to_owned(),
+                    rating: slide
+                        .find("div.sc-detail > div.scd-item > strong")
+                        .first()
+                        .text()
+                        .trim()
+                        .to_owned(),
+                    duration: label("Duration:"),
+                    genres: label("Genre:")
+                        .split(',')
+                        .map(|genre| genre.trim().to_owned())
+                        .filter(|genre| !genre.is_empty())
+                        .collect(),
+                    media_type: media_type_from_id(&id)?,
+                    id,
+                })
             })
+            .into_iter()
+            .flatten()
+            .collect()
     }
 
-    pub fn trending_shows(&self) -> Vec<Option<String>> {
-        self.elements
-            .find("div#trending-tv div.film_list-wrap div.flw-item div.film-poster a")
-            .map(|_, element| {
-                element
-                    .get_attribute("href")?
-                    .to_string()
-                    .strip_prefix('/')
-                    .map(String::from)
-            })
+    /// The film cards inside the section matching `selector`
+    pub fn section(&self, selector: &str) -> Vec<FlixHQListing> {
+        listings(&self.elements.find(selector), self.selectors, self.base_url)
     }
 }
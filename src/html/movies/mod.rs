@@ -1,2 +1,3 @@
 pub mod dramacool_html;
 pub mod flixhq_html;
+pub mod viewasian_html;
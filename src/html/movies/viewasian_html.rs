@@ -0,0 +1,215 @@
+use chrono::NaiveDateTime;
+use visdom::{types::Elements, Vis};
+
+use crate::{
+    html::{
+        create_html_fragment,
+        movies::dramacool_html::{media_status, split_year, sub_type, Page, Server},
+    },
+    providers::movies::{
+        dramacool::{DramaCoolEpisode, DramaCoolListing, DramaCoolResult, DramaCoolServer},
+        viewasian::{ViewAsian, BASE_URL},
+    },
+    utils::util_funcs::Days,
+};
+
+pub(crate) trait ViewAsianHTML {
+    fn parse_search(&self, page_html: String) -> (Vec<DramaCoolListing>, bool, usize);
+    fn single_page(&self, media_html: &str, id: &str, url: String) -> DramaCoolResult;
+    fn info_episode(&self, episode_html: &str) -> Vec<DramaCoolEpisode>;
+    fn info_server(&self, server_html: String) -> Vec<DramaCoolServer>;
+}
+
+impl ViewAsianHTML for ViewAsian {
+    fn parse_search(&self, page_html: String) -> (Vec<DramaCoolListing>, bool, usize) {
+        let elements = create_html_fragment(&page_html);
+
+        let results = Search {
+            elements: &elements,
+        }
+        .search_results();
+
+        // ViewAsian uses the same pagination markup as DramaCool
+        let page_parser = Page { elements };
+
+        (
+            results,
+            page_parser.has_next_page(),
+            page_parser.total_pages(),
+        )
+    }
+
+    fn single_page(&self, media_html: &str, id: &str, url: String) -> DramaCoolResult {
+        let elements = create_html_fragment(media_html);
+
+        let info_parser = Info {
+            elements: &elements,
+        };
+
+        DramaCoolResult {
+            id: id.to_string(),
+            title: info_parser.title(),
+            url,
+            image: info_parser.image(),
+            status: media_status(&info_parser.field("Status:")),
+            release_date: info_parser.field("Released:").parse::<u32>().ok(),
+            other_names: info_parser.list("Other name:"),
+            description: info_parser.description(),
+            genres: info_parser.list("Genre:"),
+            country: info_parser.list("Country:"),
+            original_network: info_parser.list("Original Network:"),
+            airs_on: info_parser
+                .list("Airs On:")
+                .iter()
+                .filter_map(|day| day.parse::<Days>().ok())
+                .collect(),
+            duration: Some(info_parser.field("Duration:")).filter(|d| !d.is_empty()),
+        }
+    }
+
+    fn info_episode(&self, episode_html: &str) -> Vec<DramaCoolEpisode> {
+        let elements = create_html_fragment(episode_html);
+
+        let episode_parser = Episodes {
+            elements: &elements,
+        };
+
+        episode_parser.episode_results()
+    }
+
+    fn info_server(&self, server_html: String) -> Vec<DramaCoolServer> {
+        let elements = create_html_fragment(&server_html);
+
+        // the watch page embeds the same server list as DramaCool
+        let server_parser = Server { elements };
+
+        server_parser.parse_server_html()
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Search<'page, 'b> {
+    pub elements: &'b Elements<'page>,
+}
+
+impl<'page, 'b> Search<'page, 'b> {
+    pub fn search_results(self) -> Vec<DramaCoolListing> {
+        self.elements
+            .find("ul.listing.items li a")
+            .map(|_, element| {
+                let card = Vis::dom(element);
+
+                let id = element
+                    .get_attribute("href")?
+                    .to_string()
+                    .strip_prefix('/')
+                    .map(String::from)?;
+
+                let (title, release_date) =
+                    split_year(card.find("div.name").text().trim().to_owned());
+
+                Some(DramaCoolListing {
+                    title,
+                    url: format!("{}/{}", BASE_URL, id),
+                    image: card
+                        .find("div.picture img")
+                        .attr("src")
+                        .map(|image| image.to_string())
+                        .unwrap_or_default(),
+                    release_date,
+                    id,
+                })
+            })
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Info<'page, 'b> {
+    pub elements: &'b Elements<'page>,
+}
+
+impl<'page, 'b> Info<'page, 'b> {
+    pub fn title(self) -> String {
+        self.elements
+            .find("div.detail-mod h3")
+            .text()
+            .trim()
+            .to_owned()
+    }
+
+    pub fn image(self) -> String {
+        self.elements
+            .find("div.detail-mod div.cover img")
+            .attr("src")
+            .map(|image| image.to_string())
+            .unwrap_or_default()
+    }
+
+    pub fn description(self) -> String {
+        self.elements
+            .find("div.detail-mod div.desc")
+            .text()
+            .trim()
+            .to_owned()
+    }
+
+    /// The text of a `<dl><dt>label</dt><dd>value</dd></dl>` entry
+    pub fn field(self, label: &str) -> String {
+        self.elements
+            .find(&format!("div.detail-mod dl:contains('{}') dd", label))
+            .text()
+            .trim()
+            .to_owned()
+    }
+
+    /// A field holding several values separated by `;` or `,`
+    pub fn list(self, label: &str) -> Vec<String> {
+        self.field(label)
+            .split([';', ','])
+            .map(|s| s.trim().to_owned())
+            .filter(|x| !x.is_empty())
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Episodes<'page, 'b> {
+    pub elements: &'b Elements<'page>,
+}
+
+impl<'page, 'b> Episodes<'page, 'b> {
+    pub fn episode_results(self) -> Vec<DramaCoolEpisode> {
+        self.elements
+            .find("ul.episodes li a")
+            .map(|_, element| {
+                let card = Vis::dom(element);
+
+                // Episode pages look like "/watch/vincenzo/watching.html$episode=20"
+                let href = element.get_attribute("href")?.to_string();
+                let id = href.trim_start_matches('/').to_owned();
+
+                let title = card.find("span.ep").text().trim().to_owned();
+
+                Some(DramaCoolEpisode {
+                    url: format!("{}/{}", BASE_URL, id),
+                    number: id
+                        .rsplit_once("$episode=")
+                        .and_then(|(_, number)| number.parse::<u32>().ok()),
+                    title,
+                    sub_type: sub_type(&card.find("span.type").text()),
+                    release_date: NaiveDateTime::parse_from_str(
+                        card.find("span.time").text().trim(),
+                        "%Y-%m-%d %H:%M:%S",
+                    )
+                    .ok(),
+                    id,
+                })
+            })
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+}
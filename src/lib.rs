@@ -1,4 +1,3 @@
-#![allow(async_fn_in_trait)]
 pub mod extractors;
 pub(crate) mod html;
 pub mod models;
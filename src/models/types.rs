@@ -1,12 +1,86 @@
+use crate::utils::util_funcs::USER_AGENT;
+
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Options passed to a [`VideoExtractor`](crate::models::VideoExtractor)
+///
+/// Extractors only read the options they need, so new ones can be added without
+/// breaking existing callers.
+/// ```
+/// use consumet::models::ExtractConfig;
+///
+/// let config = ExtractConfig::new()
+///     .with_alternative(true)
+///     .with_user_agent("consumet");
+///
+/// assert!(config.is_alternative());
+/// assert_eq!(config.user_agent(), "consumet");
+/// assert_eq!(config.api_key(), None);
+/// ```
 #[derive(Default, Clone, Debug)]
 pub struct ExtractConfig {
-    pub vis_cloud_helper: Option<String>,
-    pub api_key: Option<String>,
-    pub is_alternative: Option<bool>,
-    pub user_agent: Option<String>,
+    vis_cloud_helper: Option<String>,
+    api_key: Option<String>,
+    is_alternative: bool,
+    user_agent: Option<String>,
+    referer: Option<String>,
+}
+
+impl ExtractConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Url of the helper used to sign VizCloud requests
+    pub fn with_vis_cloud_helper(mut self, helper: impl Into<String>) -> Self {
+        self.vis_cloud_helper = Some(helper.into());
+        self
+    }
+
+    /// Api key sent along with the VizCloud helper
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Use the extractor's alternative host (e.g. VidCloud instead of UpCloud)
+    pub fn with_alternative(mut self, is_alternative: bool) -> Self {
+        self.is_alternative = is_alternative;
+        self
+    }
+
+    /// Overrides the default [`USER_AGENT`]
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Referer header sent to the embed host
+    pub fn with_referer(mut self, referer: impl Into<String>) -> Self {
+        self.referer = Some(referer.into());
+        self
+    }
+
+    pub fn vis_cloud_helper(&self) -> Option<&str> {
+        self.vis_cloud_helper.as_deref()
+    }
+
+    pub fn api_key(&self) -> Option<&str> {
+        self.api_key.as_deref()
+    }
+
+    pub fn is_alternative(&self) -> bool {
+        self.is_alternative
+    }
+
+    pub fn user_agent(&self) -> &str {
+        self.user_agent.as_deref().unwrap_or(USER_AGENT)
+    }
+
+    pub fn referer(&self) -> Option<&str> {
+        self.referer.as_deref()
+    }
 }
 
 /// Used to get other fields in structs
@@ -44,7 +118,23 @@ pub struct Hashes {
 }
 
 /// Contains Title Info
+/// ```
+/// use consumet::models::ITitle;
+/// use serde_json::json;
+///
+/// let json = json!({
+///     "romaji": "Shingeki no Kyojin",
+///     "english": "Attack on Titan",
+///     "native": "進撃の巨人",
+///     "userPreferred": "Shingeki no Kyojin"
+/// });
+///
+/// let title: ITitle = serde_json::from_value(json.clone()).unwrap();
+/// assert_eq!(title.user_preferred.as_deref(), Some("Shingeki no Kyojin"));
+/// assert_eq!(serde_json::to_value(&title).unwrap(), json);
+/// ```
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ITitle {
     pub romaji: Option<String>,
     pub english: Option<String>,
@@ -53,7 +143,29 @@ pub struct ITitle {
 }
 
 /// Contains Anime Search Results
+/// ```
+/// use consumet::models::IAnimeResult;
+/// use serde_json::json;
+///
+/// let json = json!({
+///     "id": "16498",
+///     "title": { "romaji": "Shingeki no Kyojin", "english": null, "native": null, "userPreferred": null },
+///     "url": "https://anilist.co/anime/16498",
+///     "image": null,
+///     "cover": null,
+///     "status": null,
+///     "rating": 84.0,
+///     "type": null,
+///     "releaseDate": "2013",
+///     "relationType": "SEQUEL"
+/// });
+///
+/// let result: IAnimeResult = serde_json::from_value(json.clone()).unwrap();
+/// assert_eq!(result.release_date.as_deref(), Some("2013"));
+/// assert_eq!(serde_json::to_value(&result).unwrap(), json);
+/// ```
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct IAnimeResult {
     pub id: String,
     pub title: ITitle,
@@ -61,13 +173,51 @@ pub struct IAnimeResult {
     pub image: Option<String>,
     pub cover: Option<String>,
     pub status: Option<MediaStatus>,
-    pub rating: Option<u32>,
+    /// out of 100
+    pub rating: Option<f32>,
+    #[serde(rename = "type")]
     pub show_type: Option<MediaFormat>,
     pub release_date: Option<String>,
+    /// how it relates to the anime listing it in [`IAnimeInfo::relations`], e.g. "SEQUEL"
+    pub relation_type: Option<String>,
+}
+
+/// Contains the results of fetching several pages, in the order the site listed them
+#[derive(Debug, Deserialize, Serialize)]
+pub struct FetchResults<T> {
+    pub results: Vec<T>,
+    /// the pages that could not be fetched
+    pub errors: Vec<FetchError>,
+}
+
+/// Contains the reason a page could not be fetched
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FetchError {
+    /// position of the item on the upstream page
+    pub index: usize,
+    pub id: String,
+    pub error: String,
 }
 
 /// Contains Search Results
+/// ```
+/// use consumet::models::ISearch;
+/// use serde_json::json;
+///
+/// let json = json!({
+///     "currentPage": 1,
+///     "hasNextPage": true,
+///     "totalPages": 4,
+///     "totalResults": 80,
+///     "results": ["one-piece-100"]
+/// });
+///
+/// let search: ISearch<String> = serde_json::from_value(json.clone()).unwrap();
+/// assert_eq!(search.total_pages, Some(4));
+/// assert_eq!(serde_json::to_value(&search).unwrap(), json);
+/// ```
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ISearch<T> {
     pub current_page: Option<usize>,
     pub has_next_page: bool,
@@ -94,6 +244,7 @@ pub struct FuzzyDate {
 
 /// Used to get the Format of the chosen media
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum MediaFormat {
     Tv,
     TvShort,
@@ -127,8 +278,42 @@ impl std::fmt::Display for MediaFormat {
 }
 
 /// Contains Anime Info
+/// ```
+/// use consumet::models::IAnimeInfo;
+/// use serde_json::json;
+///
+/// let info: IAnimeInfo = serde_json::from_value(json!({
+///     "id": "16498",
+///     "title": { "romaji": "Shingeki no Kyojin" },
+///     "malId": 16498,
+///     "totalEpisodes": 25,
+///     "countryOfOrigin": "JP",
+///     "isAdult": false,
+///     "isLicensed": true,
+///     "nextAiringEpisode": null
+/// }))
+/// .unwrap();
+/// assert_eq!(info.mal_id, Some(16498));
+/// assert_eq!(info.country_of_origin.as_deref(), Some("JP"));
+///
+/// let json = serde_json::to_value(&info).unwrap();
+/// let keys = [
+///     "id", "title", "url", "image", "malId", "genres", "description", "status",
+///     "totalEpisodes", "subOrDub", "synonyms", "countryOfOrigin", "isAdult", "isLicensed",
+///     "season", "studios", "color", "cover", "trailer", "episodes", "startDate", "endDate",
+///     "recommendations", "relations", "characters", "nextAiringEpisode",
+/// ];
+/// assert_eq!(json.as_object().unwrap().len(), keys.len());
+/// assert!(keys.iter().all(|key| json.get(key).is_some()));
+/// assert_eq!(json["totalEpisodes"], 25);
+/// ```
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct IAnimeInfo {
+    pub id: String,
+    pub title: ITitle,
+    pub url: Option<String>,
+    pub image: Option<String>,
     pub mal_id: Option<u32>,
     pub genres: Option<Vec<String>>,
     pub description: Option<String>,
@@ -147,15 +332,116 @@ pub struct IAnimeInfo {
     pub episodes: Option<Vec<IAnimeEpisode>>,
     pub start_date: Option<FuzzyDate>,
     pub end_date: Option<FuzzyDate>,
-    pub recommendations: Option<IAnimeResult>,
+    pub recommendations: Option<Vec<IAnimeResult>>,
     pub relations: Option<Vec<IAnimeResult>>,
+    pub characters: Option<Vec<IAnimeCharacter>>,
+    /// missing once the anime has finished airing
+    pub next_airing_episode: Option<INextAiringEpisode>,
+}
+
+/// Contains the next episode to air
+/// ```
+/// use consumet::models::INextAiringEpisode;
+/// use serde_json::json;
+///
+/// let json = json!({ "airingTime": 1700000000, "timeUntilAiring": 3600, "episode": 7 });
+///
+/// let next: INextAiringEpisode = serde_json::from_value(json.clone()).unwrap();
+/// assert_eq!(next.time_until_airing, 3600);
+/// assert_eq!(serde_json::to_value(next).unwrap(), json);
+/// ```
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct INextAiringEpisode {
+    /// unix timestamp
+    pub airing_time: i64,
+    /// in seconds
+    pub time_until_airing: i64,
+    pub episode: u32,
+}
+
+/// Contains Character Info
+/// ```
+/// use consumet::models::IAnimeCharacter;
+/// use serde_json::json;
+///
+/// let name = |full: &str| {
+///     json!({ "first": null, "last": null, "full": full, "native": null, "userPreferred": full })
+/// };
+/// let json = json!({
+///     "id": "40882",
+///     "role": "MAIN",
+///     "name": name("Eren Yeager"),
+///     "image": null,
+///     "voiceActors": [{
+///         "id": "95991",
+///         "language": "Japanese",
+///         "name": name("Yuki Kaji"),
+///         "image": null
+///     }]
+/// });
+///
+/// let character: IAnimeCharacter = serde_json::from_value(json.clone()).unwrap();
+/// assert_eq!(character.voice_actors[0].name.user_preferred.as_deref(), Some("Yuki Kaji"));
+/// assert_eq!(serde_json::to_value(&character).unwrap(), json);
+/// ```
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IAnimeCharacter {
+    pub id: String,
+    /// e.g. "MAIN" or "SUPPORTING"
+    pub role: Option<String>,
+    pub name: ICharacterName,
+    pub image: Option<String>,
+    pub voice_actors: Vec<IVoiceActor>,
+}
+
+/// Contains Voice Actor Info
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IVoiceActor {
+    pub id: String,
+    pub language: Option<String>,
+    pub name: ICharacterName,
+    pub image: Option<String>,
+}
+
+/// Contains the name of a character or a person
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ICharacterName {
+    pub first: Option<String>,
+    pub last: Option<String>,
+    pub full: Option<String>,
+    pub native: Option<String>,
+    pub user_preferred: Option<String>,
 }
 
 /// Contains Anime Episode Info
+/// ```
+/// use consumet::models::IAnimeEpisode;
+/// use serde_json::json;
+///
+/// let json = json!({
+///     "id": "one-piece-100?ep=2142",
+///     "number": 1,
+///     "title": "I'm Luffy! The Man Who's Gonna Be King of the Pirates!",
+///     "description": null,
+///     "isFiller": false,
+///     "url": null,
+///     "image": null,
+///     "releaseDate": "1999-10-20"
+/// });
+///
+/// let episode: IAnimeEpisode = serde_json::from_value(json.clone()).unwrap();
+/// assert_eq!(episode.is_filler, Some(false));
+/// assert_eq!(serde_json::to_value(&episode).unwrap(), json);
+/// ```
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct IAnimeEpisode {
     pub id: String,
-    pub u32: u32,
+    pub number: u32,
     pub title: Option<String>,
     pub description: Option<String>,
     pub is_filler: Option<bool>,
@@ -172,11 +458,31 @@ pub struct IEpisodeServer {
 }
 
 /// Contains Video Sources
+/// ```
+/// use consumet::models::IVideo;
+/// use serde_json::json;
+///
+/// let json = json!({
+///     "url": "https://example.com/master.m3u8",
+///     "quality": "auto",
+///     "isM3U8": true,
+///     "isDASH": false,
+///     "size": null,
+///     "other": null
+/// });
+///
+/// let video: IVideo = serde_json::from_value(json.clone()).unwrap();
+/// assert_eq!(video.is_m3u8, Some(true));
+/// assert_eq!(serde_json::to_value(&video).unwrap(), json);
+/// ```
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct IVideo {
     pub url: Option<String>,
     pub quality: Option<String>,
+    #[serde(rename = "isM3U8")]
     pub is_m3u8: Option<bool>,
+    #[serde(rename = "isDASH")]
     pub is_dash: Option<bool>,
     pub size: Option<u32>,
     pub other: Option<HashMap<String, Other>>,
@@ -218,13 +524,15 @@ impl std::fmt::Display for StreamingServers {
     }
 }
 
-/// Used to check the status of the provided media
+/// Used to check the status of the provided media, serialized as its [`Display`](std::fmt::Display)
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub enum MediaStatus {
+    #[serde(rename = "Ongoing")]
     OnGoing,
     Completed,
     Hiatus,
     Cancelled,
+    #[serde(rename = "Not yet aired")]
     NotYetAired,
     Unknown,
 }
@@ -244,12 +552,14 @@ impl std::fmt::Display for MediaStatus {
     }
 }
 
-/// Used to check if something is Sub, Dub or Both
+/// Used to check if something is Sub, Dub, Both or Raw (not subtitled yet)
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum SubOrSub {
     Sub,
     Dub,
     Both,
+    Raw,
 }
 
 impl std::fmt::Display for SubOrSub {
@@ -258,6 +568,7 @@ impl std::fmt::Display for SubOrSub {
             Self::Sub => "sub",
             Self::Dub => "dub",
             Self::Both => "both",
+            Self::Raw => "raw",
         };
 
         write!(f, "{}", bofa)
@@ -289,6 +600,13 @@ pub struct IMangaChapter {
 /// Contains Manga Info
 #[derive(Debug, Deserialize, Serialize)]
 pub struct IMangaInfo {
+    pub id: String,
+    pub title: String,
+    pub alt_titles: Option<Vec<String>>,
+    pub image: Option<String>,
+    pub description: Option<String>,
+    pub status: Option<MediaStatus>,
+    pub release_date: Option<u32>,
     pub mal_id: Option<u32>,
     pub authors: Option<Vec<String>>,
     pub genres: Option<Vec<String>>,
@@ -409,25 +727,54 @@ pub struct ZLibrary {
 }
 
 /// Contains Subtitle Info
+/// ```
+/// use consumet::models::ISubtitle;
+/// use serde_json::json;
+///
+/// let json = json!({ "id": null, "url": "https://example.com/eng.vtt", "lang": "English" });
+///
+/// let subtitle: ISubtitle = serde_json::from_value(json.clone()).unwrap();
+/// assert_eq!(subtitle.lang.as_deref(), Some("English"));
+/// assert_eq!(serde_json::to_value(&subtitle).unwrap(), json);
+/// ```
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ISubtitle {
     pub id: Option<String>,
     pub url: Option<String>,
     pub lang: Option<String>,
 }
 
-/// The start, and the end of the intro or opening in seconds.
-#[derive(Debug, Deserialize, Serialize)]
+/// The start, and the end of the intro (opening) or outro (ending) in seconds.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct Intro {
     pub start: u32,
     pub end: u32,
 }
 
 /// Contains Source Info
+/// ```
+/// use consumet::models::ISource;
+/// use serde_json::json;
+///
+/// let json = json!({
+///     "headers": "https://megacloud.tv/",
+///     "intro": { "start": 31, "end": 111 },
+///     "outro": null,
+///     "subtitles": [],
+///     "sources": []
+/// });
+///
+/// let source: ISource = serde_json::from_value(json.clone()).unwrap();
+/// assert_eq!(source.intro.map(|intro| intro.end), Some(111));
+/// assert_eq!(serde_json::to_value(&source).unwrap(), json);
+/// ```
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ISource {
     pub headers: Option<String>,
     pub intro: Option<Intro>,
+    pub outro: Option<Intro>,
     pub subtitles: Option<Vec<ISubtitle>>,
     pub sources: Option<Vec<IVideo>>,
 }
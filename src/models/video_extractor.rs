@@ -1,13 +1,20 @@
 use crate::models::ExtractConfig;
 
-pub trait VideoExtractor {
+use futures::future::BoxFuture;
+
+/// Implemented by every video extractor.
+///
+/// Extractors hold no state between calls, so a single instance can be shared
+/// across tasks (or stored as a `Box<dyn VideoExtractor<VideoSource = _>>`) and
+/// every call to `extract` returns a fresh result.
+pub trait VideoExtractor: Send + Sync {
     type VideoSource;
 
     /// takes video link
     /// returns video sources (video links) available
-    async fn extract(
-        &mut self,
-        video_url: String,
-        args: ExtractConfig,
-    ) -> anyhow::Result<Self::VideoSource>;
+    fn extract<'a>(
+        &'a self,
+        video_url: &'a str,
+        config: &'a ExtractConfig,
+    ) -> BoxFuture<'a, anyhow::Result<Self::VideoSource>>;
 }
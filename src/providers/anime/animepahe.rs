@@ -0,0 +1,338 @@
+use crate::{
+    extractors::kwik::Kwik,
+    html::anime::animepahe_html::{media_status, AnimePaheHTML},
+    models::{ExtractConfig, IAnimeEpisode, ISource, IVideo, MediaStatus, VideoExtractor},
+    utils::util_funcs::DEFAULT_CONCURRENCY,
+    CLIENT,
+};
+
+use anyhow::bail;
+use futures::{stream, StreamExt};
+use serde::{Deserialize, Serialize};
+
+/// Anime from AnimePahe, played through Kwik
+pub struct AnimePahe;
+
+/// Contains an anime from the search results
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AnimePaheResult {
+    /// the anime session, which changes from time to time
+    pub id: String,
+    pub title: String,
+    pub url: String,
+    pub image: String,
+    /// as shown on the site, e.g. "TV" or "Movie"
+    pub show_type: Option<String>,
+    pub total_episodes: Option<u32>,
+    pub status: MediaStatus,
+    /// the season it premiered in, e.g. "Fall"
+    pub season: Option<String>,
+    pub release_date: Option<u32>,
+    /// out of 10
+    pub rating: Option<f32>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AnimePaheInfo {
+    pub id: String,
+    pub title: String,
+    pub japanese_title: Option<String>,
+    pub url: String,
+    pub image: String,
+    pub cover: Option<String>,
+    pub description: String,
+    pub genres: Vec<String>,
+    pub show_type: Option<String>,
+    pub status: MediaStatus,
+    /// as shown on the site, e.g. "Oct 20, 1999 to ?"
+    pub release_date: Option<String>,
+    /// the season it premiered in, e.g. "Fall 1999"
+    pub season: Option<String>,
+    pub studios: Vec<String>,
+    pub synonyms: Vec<String>,
+    /// the regular episodes, without specials
+    pub total_episodes: usize,
+    pub episodes: Vec<IAnimeEpisode>,
+    pub specials: Vec<AnimePaheSpecial>,
+}
+
+/// Contains a page of episodes, see [`EPISODES_PER_PAGE`]
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AnimePaheEpisodes {
+    pub current_page: usize,
+    pub has_next_page: bool,
+    pub total_pages: usize,
+    /// the number of episodes and specials over every page
+    pub total_results: usize,
+    pub results: Vec<IAnimeEpisode>,
+    /// the specials of this page, which are not in `results`
+    pub specials: Vec<AnimePaheSpecial>,
+}
+
+/// Contains an episode numbered between two others, e.g. a 12.5 recap
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AnimePaheSpecial {
+    /// as listed, e.g. 12.5
+    pub number: f32,
+    /// its [`IAnimeEpisode::number`] is the regular episode it follows, e.g. 12
+    pub episode: IAnimeEpisode,
+}
+
+/// Contains a Kwik embed of an episode, one per fansub, resolution and audio
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AnimePaheLink {
+    pub url: String,
+    pub fansub: String,
+    /// the video height, e.g. "1080"
+    pub resolution: String,
+    /// "jpn" or "eng"
+    pub audio: String,
+}
+
+impl AnimePaheLink {
+    /// The label used as [`IVideo::quality`], e.g. "1080p" or "1080p (eng)" for dubs
+    pub fn quality(&self) -> String {
+        match self.audio.as_str() {
+            "eng" => format!("{}p (eng)", self.resolution),
+            _ => format!("{}p", self.resolution),
+        }
+    }
+}
+
+/// How many episodes the release endpoint returns per page
+pub const EPISODES_PER_PAGE: usize = 30;
+
+#[derive(Deserialize)]
+struct RawPage<T> {
+    total: usize,
+    current_page: usize,
+    last_page: usize,
+    /// missing when nothing matched
+    #[serde(default = "Vec::new")]
+    data: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct RawSearchResult {
+    session: String,
+    title: String,
+    poster: String,
+    r#type: Option<String>,
+    episodes: Option<u32>,
+    status: Option<String>,
+    season: Option<String>,
+    year: Option<u32>,
+    score: Option<f32>,
+}
+
+#[derive(Deserialize)]
+struct RawEpisode {
+    session: String,
+    /// specials are numbered like 7.5
+    episode: f32,
+    title: Option<String>,
+    snapshot: Option<String>,
+    #[serde(default)]
+    filler: u8,
+    created_at: Option<String>,
+}
+
+pub(crate) const BASE_URL: &str = "https://animepahe.ru";
+
+/// AnimePahe sits behind DDoS-Guard, which lets requests with this cookie through
+const COOKIE: &str = "__ddg2_=";
+
+impl AnimePahe {
+    /// Returns every anime matching the query, the site does not page search results
+    pub async fn search(&self, query: &str) -> anyhow::Result<Vec<AnimePaheResult>> {
+        let search_json = get(&format!("/api?m=search&q={}", query.replace(' ', "%20"))).await?;
+
+        let results: RawPage<RawSearchResult> = serde_json::from_str(&search_json)?;
+
+        Ok(results
+            .data
+            .into_iter()
+            .map(|result| AnimePaheResult {
+                url: format!("{}/anime/{}", BASE_URL, result.session),
+                id: result.session,
+                title: result.title,
+                image: result.poster,
+                show_type: result.r#type,
+                total_episodes: result.episodes.filter(|episodes| *episodes > 0),
+                status: media_status(&result.status.unwrap_or_default()),
+                season: result.season,
+                release_date: result.year,
+                rating: result.score,
+            })
+            .collect())
+    }
+
+    /// Returns the details of an anime along with all of its episodes
+    pub async fn info(&self, media_id: &str) -> anyhow::Result<AnimePaheInfo> {
+        let info_html = get(&format!("/anime/{}", media_id)).await?;
+
+        let mut info = self.single_page(
+            info_html,
+            media_id,
+            format!("{}/anime/{}", BASE_URL, media_id),
+        );
+
+        (info.episodes, info.specials) = self.all_episodes(media_id, None).await?;
+        info.total_episodes = info.episodes.len();
+
+        Ok(info)
+    }
+
+    /// Returns a page of [`EPISODES_PER_PAGE`] episodes, oldest first.\
+    /// Specials numbered between two episodes, e.g. 7.5, are listed apart in [`AnimePaheEpisodes::specials`] as [`IAnimeEpisode::number`] is whole.
+    /// # Parameters
+    /// * `media_id` - the anime session
+    /// * `page (optional)` - page number (default: 1)
+    pub async fn episodes(
+        &self,
+        media_id: &str,
+        page: Option<usize>,
+    ) -> anyhow::Result<AnimePaheEpisodes> {
+        let episode_json = get(&format!(
+            "/api?m=release&id={}&sort=episode_asc&page={}",
+            media_id,
+            page.unwrap_or(1)
+        ))
+        .await?;
+
+        let episodes: RawPage<RawEpisode> = serde_json::from_str(&episode_json)?;
+
+        let mut results = vec![];
+        let mut specials = vec![];
+
+        for raw in episodes.data {
+            let number = raw.episode;
+            let episode = IAnimeEpisode {
+                id: format!("{}/{}", media_id, raw.session),
+                url: Some(format!("{}/play/{}/{}", BASE_URL, media_id, raw.session)),
+                number: number as u32,
+                title: raw.title.filter(|title| !title.is_empty()),
+                description: None,
+                is_filler: Some(raw.filler == 1),
+                image: raw.snapshot,
+                release_date: raw.created_at,
+            };
+
+            if number.fract() == 0.0 {
+                results.push(episode);
+            } else {
+                specials.push(AnimePaheSpecial { number, episode });
+            }
+        }
+
+        Ok(AnimePaheEpisodes {
+            current_page: episodes.current_page,
+            has_next_page: episodes.current_page < episodes.last_page,
+            total_pages: episodes.last_page,
+            total_results: episodes.total,
+            results,
+            specials,
+        })
+    }
+
+    /// Fetches every page of episodes, the pages after the first `concurrency` at a time (default: [`DEFAULT_CONCURRENCY`]).\
+    /// Returns the regular episodes and the specials apart, see [`AnimePahe::episodes`].
+    pub async fn all_episodes(
+        &self,
+        media_id: &str,
+        concurrency: Option<usize>,
+    ) -> anyhow::Result<(Vec<IAnimeEpisode>, Vec<AnimePaheSpecial>)> {
+        let concurrency = concurrency.unwrap_or(DEFAULT_CONCURRENCY).max(1);
+
+        let first = self.episodes(media_id, None).await?;
+
+        let pages: Vec<_> = stream::iter(2..=first.total_pages)
+            .map(|page| self.episodes(media_id, Some(page)))
+            .buffered(concurrency)
+            .collect()
+            .await;
+
+        let mut episodes = first.results;
+        let mut specials = first.specials;
+        for page in pages {
+            let page = page?;
+            episodes.extend(page.results);
+            specials.extend(page.specials);
+        }
+
+        Ok((episodes, specials))
+    }
+
+    /// Returns the Kwik embeds of an episode
+    /// # Parameters
+    /// * `episode_id` - the episode id, "{anime session}/{episode session}"
+    pub async fn links(&self, episode_id: &str) -> anyhow::Result<Vec<AnimePaheLink>> {
+        let play_html = get(&format!("/play/{}", episode_id)).await?;
+
+        Ok(self.info_links(play_html))
+    }
+
+    /// Returns one source per resolution and audio, labelled by [`AnimePaheLink::quality`].\
+    /// Embeds which fail to extract are skipped, it only fails when none of them could be extracted.
+    pub async fn sources(&self, episode_id: &str) -> anyhow::Result<ISource> {
+        let links = self.links(episode_id).await?;
+
+        if links.is_empty() {
+            bail!("No Kwik links found for {}", episode_id);
+        }
+
+        let config = ExtractConfig::new().with_referer(format!("{}/", BASE_URL));
+
+        let extracted: Vec<_> = stream::iter(&links)
+            .map(|link| Kwik.extract(&link.url, &config))
+            .buffered(DEFAULT_CONCURRENCY)
+            .collect()
+            .await;
+
+        // A dead embed only loses its own resolution
+        let mut sources = vec![];
+        let mut errors = vec![];
+        for (link, kwik) in links.iter().zip(extracted) {
+            match kwik {
+                Ok(kwik) => sources.extend(kwik.sources.into_iter().map(|source| IVideo {
+                    url: Some(source.url),
+                    quality: Some(link.quality()),
+                    is_m3u8: Some(source.is_m3u8),
+                    is_dash: None,
+                    size: None,
+                    other: None,
+                })),
+                Err(err) => errors.push(format!("{}: {}", link.quality(), err)),
+            }
+        }
+
+        if sources.is_empty() {
+            bail!(
+                "No Kwik link of {} could be extracted ({})",
+                episode_id,
+                errors.join(", ")
+            );
+        }
+
+        Ok(ISource {
+            // The videos only play with Kwik as the Referer
+            headers: Some(String::from("https://kwik.cx/")),
+            intro: None,
+            outro: None,
+            subtitles: None,
+            sources: Some(sources),
+        })
+    }
+}
+
+async fn get(path: &str) -> anyhow::Result<String> {
+    Ok(CLIENT
+        .get(format!("{}{}", BASE_URL, path))
+        .header("Cookie", COOKIE)
+        .header("Referer", BASE_URL)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?)
+}
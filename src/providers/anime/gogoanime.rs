@@ -0,0 +1,336 @@
+use crate::{
+    extractors::{
+        gogocdn::{GogoCDN, GogoCDNSources},
+        streamsb::{StreamSB, StreamSBSources},
+    },
+    html::anime::gogoanime_html::GogoanimeHTML,
+    models::{
+        ExtractConfig, IAnimeEpisode, MediaStatus, StreamingServers, SubOrSub, VideoExtractor,
+    },
+    utils::{mirrors::Mirrors, util_funcs::encode_query},
+    CLIENT,
+};
+
+use anyhow::bail;
+use serde::{Deserialize, Serialize};
+
+/// Anime from Gogoanime, subbed and dubbed releases are listed as separate entries
+pub struct Gogoanime;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub enum GogoanimeSourceType {
+    GogoCDN(Vec<GogoCDNSources>),
+    StreamSB(Vec<StreamSBSources>),
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GogoanimeSources {
+    /// the Referer the sources expect
+    pub headers: Option<String>,
+    pub sources: GogoanimeSourceType,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GogoanimeSearchResults<T = GogoanimeListing> {
+    pub current_page: usize,
+    pub has_next_page: bool,
+    /// the highest page linked from the current one
+    pub total_pages: usize,
+    pub total_results: usize,
+    pub results: Vec<T>,
+}
+
+/// Contains an anime as shown on the search and genre pages
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GogoanimeListing {
+    pub id: String,
+    pub title: String,
+    pub url: String,
+    pub image: String,
+    /// the year the anime started airing
+    pub release_date: Option<String>,
+    pub sub_or_dub: SubOrSub,
+}
+
+/// Contains an episode from the recent releases
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GogoanimeRecentEpisode {
+    /// the anime id, for [`Gogoanime::info`]
+    pub id: String,
+    pub episode_id: String,
+    pub episode_number: Option<u32>,
+    pub title: String,
+    pub url: String,
+    pub image: String,
+}
+
+/// Contains an anime from the top airing list
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GogoanimeTopAiring {
+    pub id: String,
+    pub title: String,
+    pub url: String,
+    pub image: String,
+    pub genres: Vec<String>,
+    pub latest_episode: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GogoanimeInfo {
+    pub id: String,
+    pub title: String,
+    pub url: String,
+    pub image: String,
+    pub description: String,
+    pub genres: Vec<String>,
+    /// as shown on the site, e.g. "TV Series" or "Movie"
+    pub show_type: Option<String>,
+    pub status: MediaStatus,
+    /// the year the anime started airing
+    pub release_date: Option<String>,
+    pub other_names: Vec<String>,
+    pub sub_or_dub: SubOrSub,
+    pub total_episodes: usize,
+    pub episodes: Vec<IAnimeEpisode>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GogoanimeServers {
+    pub servers: Vec<GogoanimeServer>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GogoanimeServer {
+    /// the server's class on the watch page, e.g. "anime", "vidcdn" or "streamsb"
+    pub name: String,
+    pub url: String,
+}
+
+/// Which releases [`Gogoanime::recent_episodes`] lists
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum GogoanimeRecentType {
+    #[default]
+    Sub,
+    Dub,
+    Chinese,
+}
+
+impl std::fmt::Display for GogoanimeRecentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let recent_type = match self {
+            Self::Sub => "1",
+            Self::Dub => "2",
+            Self::Chinese => "3",
+        };
+
+        write!(f, "{}", recent_type)
+    }
+}
+
+pub(crate) const BASE_URL: &str = "https://anitaku.pe";
+const AJAX_URL: &str = "https://ajax.gogocdn.net/ajax";
+
+static MIRRORS: Mirrors = Mirrors::new(&[BASE_URL, "https://gogoanime3.co"], "Gogoanime");
+
+impl Gogoanime {
+    /// Replaces the ranked list of mirror domains requests are sent to, the first one is tried first.\
+    /// Returned ids are paths and returned urls always use [`BASE_URL`], whichever mirror served them.\
+    /// The list is global: every `Gogoanime` in the process switches to it, e.g. `Gogoanime::set_mirrors(domains)`.
+    pub fn set_mirrors(domains: Vec<String>) -> anyhow::Result<()> {
+        MIRRORS.set(domains)
+    }
+
+    /// The ranked list of mirror domains shared by every `Gogoanime`, and the one currently in use
+    pub fn mirrors() -> (Vec<String>, String) {
+        (MIRRORS.domains(), MIRRORS.active())
+    }
+
+    pub async fn search(
+        &self,
+        query: &str,
+        page: Option<usize>,
+    ) -> anyhow::Result<GogoanimeSearchResults> {
+        let current_page = page.unwrap_or(1);
+
+        let page_html = MIRRORS
+            .get(&format!(
+                "/search.html?keyword={}&page={}",
+                encode_query(query),
+                current_page
+            ))
+            .await?;
+
+        Ok(self.listing_results(page_html, current_page))
+    }
+
+    /// Returns the anime of a genre, e.g. "action" or "slice-of-life"
+    pub async fn genre(
+        &self,
+        genre: &str,
+        page: Option<usize>,
+    ) -> anyhow::Result<GogoanimeSearchResults> {
+        let current_page = page.unwrap_or(1);
+
+        let page_html = MIRRORS
+            .get(&format!("/genre/{}?page={}", genre, current_page))
+            .await?;
+
+        Ok(self.listing_results(page_html, current_page))
+    }
+
+    fn listing_results(&self, page_html: String, current_page: usize) -> GogoanimeSearchResults {
+        let (results, has_next_page, total_pages) = self.parse_listings(page_html);
+
+        GogoanimeSearchResults {
+            current_page,
+            has_next_page,
+            total_pages,
+            total_results: results.len(),
+            results,
+        }
+    }
+
+    /// Returns the latest episodes, subbed by default
+    pub async fn recent_episodes(
+        &self,
+        page: Option<usize>,
+        recent_type: Option<GogoanimeRecentType>,
+    ) -> anyhow::Result<GogoanimeSearchResults<GogoanimeRecentEpisode>> {
+        let current_page = page.unwrap_or(1);
+
+        let page_html = ajax(&format!(
+            "/page-recent-release.html?page={}&type={}",
+            current_page,
+            recent_type.unwrap_or_default()
+        ))
+        .await?;
+
+        let (results, has_next_page, total_pages) = self.parse_recent_episodes(page_html);
+
+        Ok(GogoanimeSearchResults {
+            current_page,
+            has_next_page,
+            total_pages,
+            total_results: results.len(),
+            results,
+        })
+    }
+
+    pub async fn top_airing(
+        &self,
+        page: Option<usize>,
+    ) -> anyhow::Result<GogoanimeSearchResults<GogoanimeTopAiring>> {
+        let current_page = page.unwrap_or(1);
+
+        let page_html = ajax(&format!(
+            "/page-recent-release-ongoing.html?page={}",
+            current_page
+        ))
+        .await?;
+
+        let (results, has_next_page, total_pages) = self.parse_top_airing(page_html);
+
+        Ok(GogoanimeSearchResults {
+            current_page,
+            has_next_page,
+            total_pages,
+            total_results: results.len(),
+            results,
+        })
+    }
+
+    /// Returns the details of an anime along with all of its episodes, oldest first
+    pub async fn info(&self, media_id: &str) -> anyhow::Result<GogoanimeInfo> {
+        let media_id = media_id.trim_start_matches("category/");
+
+        let info_html = MIRRORS.get(&format!("/category/{}", media_id)).await?;
+
+        let (mut info, episode_range) = self.single_page(
+            info_html,
+            media_id,
+            format!("{}/category/{}", BASE_URL, media_id),
+        );
+
+        // The episode list is loaded separately, for the whole range at once
+        if let Some(range) = episode_range {
+            let episode_html = ajax(&format!(
+                "/load-list-episode?ep_start=0&ep_end={}&id={}&default_ep=0&alias={}",
+                range.ep_end, range.movie_id, range.alias
+            ))
+            .await?;
+
+            info.episodes = self.info_episode(episode_html);
+            info.total_episodes = info.episodes.len();
+        }
+
+        Ok(info)
+    }
+
+    pub async fn servers(&self, episode_id: &str) -> anyhow::Result<GogoanimeServers> {
+        let server_html = MIRRORS
+            .get(&format!("/{}", episode_id.trim_start_matches('/')))
+            .await?;
+
+        let servers = self.info_server(server_html);
+
+        Ok(GogoanimeServers { servers })
+    }
+
+    /// Returns the sources of an episode, from [`StreamingServers::GogoCDN`] by default.\
+    /// [`StreamingServers::VidStreaming`] is the alternative GogoCDN player.
+    pub async fn sources(
+        &self,
+        episode_id: &str,
+        server: Option<StreamingServers>,
+    ) -> anyhow::Result<GogoanimeSources> {
+        let server: StreamingServers = server.unwrap_or(StreamingServers::GogoCDN);
+
+        let name = match server {
+            StreamingServers::GogoCDN => "anime",
+            StreamingServers::VidStreaming => "vidcdn",
+            StreamingServers::StreamSB => "streamsb",
+            _ => bail!("{} is not available on Gogoanime", server),
+        };
+
+        let servers = self.servers(episode_id).await?;
+
+        let Some(server_url) = servers
+            .servers
+            .iter()
+            .find(|s| s.name == name)
+            .map(|s| &s.url)
+        else {
+            bail!("Server {} not found for {}", server, episode_id);
+        };
+
+        match server {
+            StreamingServers::StreamSB => {
+                let streamsb = StreamSB.extract(server_url, &ExtractConfig::new()).await?;
+
+                Ok(GogoanimeSources {
+                    sources: GogoanimeSourceType::StreamSB(streamsb.sources),
+                    headers: Some(server_url.to_string()),
+                })
+            }
+            _ => {
+                let gogocdn = GogoCDN.extract(server_url, &ExtractConfig::new()).await?;
+
+                Ok(GogoanimeSources {
+                    sources: GogoanimeSourceType::GogoCDN(gogocdn.sources),
+                    headers: Some(server_url.to_string()),
+                })
+            }
+        }
+    }
+}
+
+/// Fetches a fragment from the ajax endpoint, which is shared by every mirror
+async fn ajax(path: &str) -> anyhow::Result<String> {
+    Ok(CLIENT
+        .get(format!("{}{}", AJAX_URL, path))
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?)
+}
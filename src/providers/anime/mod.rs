@@ -1 +1,6 @@
-
+pub mod animepahe;
+pub use animepahe::*;
+pub mod gogoanime;
+pub use gogoanime::*;
+pub mod zoro;
+pub use zoro::*;
@@ -0,0 +1,561 @@
+use crate::{
+    extractors::{megacloud::MegaCloud, rapidcloud::RapidCloud},
+    html::anime::zoro_html::ZoroHTML,
+    models::{
+        ExtractConfig, IAnimeEpisode, ISource, ISubtitle, IVideo, MediaFormat, MediaStatus,
+        StreamingServers, SubOrSub, VideoExtractor,
+    },
+    providers::meta::AnilistSeason,
+    utils::mirrors::Mirrors,
+};
+
+use anyhow::bail;
+use serde::{Deserialize, Serialize};
+
+/// Anime from Zoro (HiAnime), with separate sub and dub servers and intro and outro skip times
+pub struct Zoro;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ZoroSearchResults<T = ZoroListing> {
+    pub current_page: usize,
+    pub has_next_page: bool,
+    pub total_pages: usize,
+    pub total_results: usize,
+    pub results: Vec<T>,
+}
+
+/// Contains an anime as shown on the search and filter pages
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ZoroListing {
+    pub id: String,
+    pub title: String,
+    pub japanese_title: Option<String>,
+    pub url: String,
+    pub image: String,
+    /// as shown on the site, e.g. "TV" or "Movie"
+    pub show_type: Option<String>,
+    /// the episode duration, e.g. "24m"
+    pub duration: Option<String>,
+    pub sub_episodes: Option<u32>,
+    pub dub_episodes: Option<u32>,
+    pub sub_or_dub: SubOrSub,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ZoroInfo {
+    pub id: String,
+    pub title: String,
+    pub japanese_title: Option<String>,
+    pub url: String,
+    pub image: String,
+    pub description: String,
+    pub show_type: Option<String>,
+    pub status: MediaStatus,
+    pub genres: Vec<String>,
+    pub studios: Vec<String>,
+    /// as shown on the site, e.g. "Oct 20, 1999 to ?"
+    pub release_date: Option<String>,
+    /// the season it premiered in, e.g. "Fall 1999"
+    pub season: Option<String>,
+    /// the MAL score, out of 10
+    pub rating: Option<f32>,
+    pub mal_id: Option<u32>,
+    pub anilist_id: Option<u32>,
+    pub sub_episodes: Option<u32>,
+    pub dub_episodes: Option<u32>,
+    pub sub_or_dub: SubOrSub,
+    pub total_episodes: usize,
+    pub episodes: Vec<IAnimeEpisode>,
+}
+
+/// The servers of an episode, per audio category
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct ZoroServers {
+    pub sub: Vec<ZoroServer>,
+    pub dub: Vec<ZoroServer>,
+    pub raw: Vec<ZoroServer>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ZoroServer {
+    /// as shown on the site, e.g. "HD-1"
+    pub name: String,
+    /// the id of the hosting site, see [`ZoroServer::streaming_server`]
+    pub server_id: u32,
+    /// the id [`Zoro::sources`] asks the embed link for
+    pub id: String,
+}
+
+impl ZoroServer {
+    pub fn streaming_server(&self) -> Option<StreamingServers> {
+        match self.server_id {
+            1 => Some(StreamingServers::VidCloud),
+            4 => Some(StreamingServers::VidStreaming),
+            _ => None,
+        }
+    }
+}
+
+/// Sort order used by the Zoro filter
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ZoroSort {
+    Default,
+    RecentlyAdded,
+    RecentlyUpdated,
+    Score,
+    Name,
+    ReleasedDate,
+    MostWatched,
+}
+
+impl std::fmt::Display for ZoroSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sort = match self {
+            Self::Default => "default",
+            Self::RecentlyAdded => "recently_added",
+            Self::RecentlyUpdated => "recently_updated",
+            Self::Score => "score",
+            Self::Name => "name_az",
+            Self::ReleasedDate => "released_date",
+            Self::MostWatched => "most_watched",
+        };
+
+        write!(f, "{}", sort)
+    }
+}
+
+/// Genres used by the Zoro filter
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ZoroGenre {
+    Action,
+    Adventure,
+    Cars,
+    Comedy,
+    Dementia,
+    Demons,
+    Drama,
+    Ecchi,
+    Fantasy,
+    Game,
+    Harem,
+    Historical,
+    Horror,
+    Isekai,
+    Josei,
+    Kids,
+    Magic,
+    MartialArts,
+    Mecha,
+    Military,
+    Music,
+    Mystery,
+    Parody,
+    Police,
+    Psychological,
+    Romance,
+    Samurai,
+    School,
+    SciFi,
+    Seinen,
+    Shoujo,
+    ShoujoAi,
+    Shounen,
+    ShounenAi,
+    SliceOfLife,
+    Space,
+    Sports,
+    SuperPower,
+    Supernatural,
+    Thriller,
+    Vampire,
+}
+
+impl ZoroGenre {
+    /// The id used by the `/filter` endpoint
+    pub fn id(&self) -> u32 {
+        match self {
+            Self::Action => 1,
+            Self::Adventure => 2,
+            Self::Cars => 3,
+            Self::Comedy => 4,
+            Self::Dementia => 5,
+            Self::Demons => 6,
+            Self::Mystery => 7,
+            Self::Drama => 8,
+            Self::Ecchi => 9,
+            Self::Fantasy => 10,
+            Self::Game => 11,
+            Self::Historical => 13,
+            Self::Horror => 14,
+            Self::Kids => 15,
+            Self::Magic => 16,
+            Self::MartialArts => 17,
+            Self::Mecha => 18,
+            Self::Music => 19,
+            Self::Parody => 20,
+            Self::Samurai => 21,
+            Self::Romance => 22,
+            Self::School => 23,
+            Self::SciFi => 24,
+            Self::Shoujo => 25,
+            Self::ShoujoAi => 26,
+            Self::Shounen => 27,
+            Self::ShounenAi => 28,
+            Self::Space => 29,
+            Self::Sports => 30,
+            Self::SuperPower => 31,
+            Self::Vampire => 32,
+            Self::Harem => 35,
+            Self::SliceOfLife => 36,
+            Self::Supernatural => 37,
+            Self::Military => 38,
+            Self::Police => 39,
+            Self::Psychological => 40,
+            Self::Thriller => 41,
+            Self::Seinen => 42,
+            Self::Josei => 43,
+            Self::Isekai => 44,
+        }
+    }
+}
+
+/// Options for [`Zoro::advanced_search`], unset options match everything
+/// ```
+/// use consumet::{
+///     models::{MediaFormat, SubOrSub},
+///     providers::anime::zoro::{ZoroFilter, ZoroGenre, ZoroSort},
+///     providers::meta::AnilistSeason,
+/// };
+///
+/// let filter = ZoroFilter {
+///     keyword: Some(String::from("one piece")),
+///     format: Some(MediaFormat::Tv),
+///     season: Some(AnilistSeason::Fall),
+///     language: Some(SubOrSub::Dub),
+///     genres: vec![ZoroGenre::Action, ZoroGenre::Adventure],
+///     sort: Some(ZoroSort::Score),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     filter.query(),
+///     "keyword=one%20piece&type=2&season=3&language=2&sort=score&genres=1,2&page=1"
+/// );
+/// ```
+#[derive(Default, Clone, Debug)]
+pub struct ZoroFilter {
+    pub keyword: Option<String>,
+    /// one of Movie, Tv, Ova, Ona, Special or Music
+    pub format: Option<MediaFormat>,
+    /// one of Completed, OnGoing or NotYetAired
+    pub status: Option<MediaStatus>,
+    pub season: Option<AnilistSeason>,
+    /// Sub, Dub or Both
+    pub language: Option<SubOrSub>,
+    /// the minimum MAL score, from 1 to 10
+    pub score: Option<u32>,
+    pub genres: Vec<ZoroGenre>,
+    pub sort: Option<ZoroSort>,
+    /// page number (default: 1)
+    pub page: Option<usize>,
+}
+
+impl ZoroFilter {
+    /// The query string sent to the `/filter` endpoint
+    pub fn query(&self) -> String {
+        let mut params: Vec<(&str, String)> = vec![];
+
+        if let Some(keyword) = &self.keyword {
+            params.push(("keyword", keyword.replace(' ', "%20")));
+        }
+
+        let format = self.format.and_then(|format| match format {
+            MediaFormat::Movie => Some(1),
+            MediaFormat::Tv => Some(2),
+            MediaFormat::Ova => Some(3),
+            MediaFormat::Ona => Some(4),
+            MediaFormat::Special => Some(5),
+            MediaFormat::Music => Some(6),
+            _ => None,
+        });
+        if let Some(format) = format {
+            params.push(("type", format.to_string()));
+        }
+
+        let status = self.status.and_then(|status| match status {
+            MediaStatus::Completed => Some(1),
+            MediaStatus::OnGoing => Some(2),
+            MediaStatus::NotYetAired => Some(3),
+            _ => None,
+        });
+        if let Some(status) = status {
+            params.push(("status", status.to_string()));
+        }
+
+        if let Some(score) = self.score {
+            params.push(("score", score.clamp(1, 10).to_string()));
+        }
+
+        if let Some(season) = self.season {
+            let season = match season {
+                AnilistSeason::Spring => 1,
+                AnilistSeason::Summer => 2,
+                AnilistSeason::Fall => 3,
+                AnilistSeason::Winter => 4,
+            };
+            params.push(("season", season.to_string()));
+        }
+
+        let language = self.language.and_then(|language| match language {
+            SubOrSub::Sub => Some(1),
+            SubOrSub::Dub => Some(2),
+            SubOrSub::Both => Some(3),
+            SubOrSub::Raw => None,
+        });
+        if let Some(language) = language {
+            params.push(("language", language.to_string()));
+        }
+
+        if let Some(sort) = self.sort {
+            params.push(("sort", sort.to_string()));
+        }
+
+        if !self.genres.is_empty() {
+            let genres: Vec<String> = self
+                .genres
+                .iter()
+                .map(|genre| genre.id().to_string())
+                .collect();
+            params.push(("genres", genres.join(",")));
+        }
+
+        params.push(("page", self.page.unwrap_or(1).to_string()));
+
+        params
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<_>>()
+            .join("&")
+    }
+}
+
+/// Contains the JSON the ajax endpoints wrap their html in
+#[derive(Deserialize)]
+struct RawAjax {
+    html: String,
+}
+
+/// Contains the embed an episode server plays
+#[derive(Deserialize)]
+struct RawEmbed {
+    link: String,
+}
+
+pub(crate) const BASE_URL: &str = "https://hianime.to";
+
+static MIRRORS: Mirrors = Mirrors::new(&[BASE_URL, "https://hianime.nz"], "HiAnime");
+
+impl Zoro {
+    /// Replaces the ranked list of mirror domains requests are sent to, the first one is tried first.\
+    /// Returned ids are paths and returned urls always use [`BASE_URL`], whichever mirror served them.\
+    /// The list is global: every `Zoro` in the process switches to it, e.g. `Zoro::set_mirrors(domains)`.
+    pub fn set_mirrors(domains: Vec<String>) -> anyhow::Result<()> {
+        MIRRORS.set(domains)
+    }
+
+    /// The ranked list of mirror domains shared by every `Zoro`, and the one currently in use
+    pub fn mirrors() -> (Vec<String>, String) {
+        (MIRRORS.domains(), MIRRORS.active())
+    }
+
+    pub async fn search(
+        &self,
+        query: &str,
+        page: Option<usize>,
+    ) -> anyhow::Result<ZoroSearchResults> {
+        let current_page = page.unwrap_or(1);
+
+        let page_html = MIRRORS
+            .get(&format!(
+                "/search?keyword={}&page={}",
+                query.replace(' ', "%20"),
+                current_page
+            ))
+            .await?;
+
+        Ok(self.listing_results(page_html, current_page))
+    }
+
+    /// Searches with the options of the site's filter page, see [`ZoroFilter`]
+    pub async fn advanced_search(&self, filter: &ZoroFilter) -> anyhow::Result<ZoroSearchResults> {
+        let page_html = MIRRORS.get(&format!("/filter?{}", filter.query())).await?;
+
+        Ok(self.listing_results(page_html, filter.page.unwrap_or(1)))
+    }
+
+    fn listing_results(&self, page_html: String, current_page: usize) -> ZoroSearchResults {
+        let (results, has_next_page, total_pages) = self.parse_listings(page_html);
+
+        ZoroSearchResults {
+            current_page,
+            has_next_page,
+            total_pages,
+            total_results: results.len(),
+            results,
+        }
+    }
+
+    /// Returns the details of an anime along with all of its episodes
+    pub async fn info(&self, media_id: &str) -> anyhow::Result<ZoroInfo> {
+        let info_html = MIRRORS.get(&format!("/{}", media_id)).await?;
+
+        let mut info = self.single_page(info_html, media_id, format!("{}/{}", BASE_URL, media_id));
+
+        info.episodes = self.episodes(media_id).await?;
+        info.total_episodes = info.episodes.len();
+
+        Ok(info)
+    }
+
+    /// Returns the episodes of an anime, fillers are flagged with `is_filler`
+    /// # Parameters
+    /// * `media_id` - the anime id, e.g. "one-piece-100"
+    pub async fn episodes(&self, media_id: &str) -> anyhow::Result<Vec<IAnimeEpisode>> {
+        // The ajax endpoints only know the number at the end of the id
+        let Some((_, anime_id)) = media_id.rsplit_once('-') else {
+            bail!("Invalid Zoro id {}", media_id);
+        };
+
+        let episode_json = MIRRORS
+            .get(&format!("/ajax/v2/episode/list/{}", anime_id))
+            .await?;
+
+        let episodes: RawAjax = serde_json::from_str(&episode_json)?;
+
+        Ok(self.info_episode(episodes.html))
+    }
+
+    /// Returns the servers of an episode, per audio category
+    /// # Parameters
+    /// * `episode_id` - the episode id, e.g. "one-piece-100?ep=2142"
+    pub async fn servers(&self, episode_id: &str) -> anyhow::Result<ZoroServers> {
+        let id = episode_id
+            .rsplit_once("?ep=")
+            .map_or(episode_id, |(_, id)| id);
+
+        let server_json = MIRRORS
+            .get(&format!("/ajax/v2/episode/servers?episodeId={}", id))
+            .await?;
+
+        let servers: RawAjax = serde_json::from_str(&server_json)?;
+
+        Ok(self.info_server(servers.html))
+    }
+
+    /// Returns the sources of an episode along with its subtitles and intro and outro skip times
+    /// # Parameters
+    /// * `episode_id` - the episode id, e.g. "one-piece-100?ep=2142"
+    /// * `server (optional)` - [`StreamingServers::VidCloud`] (default) or [`StreamingServers::VidStreaming`]
+    /// * `category (optional)` - [`SubOrSub::Sub`] (default), [`SubOrSub::Dub`] or [`SubOrSub::Raw`]
+    pub async fn sources(
+        &self,
+        episode_id: &str,
+        server: Option<StreamingServers>,
+        category: Option<SubOrSub>,
+    ) -> anyhow::Result<ISource> {
+        let server = server.unwrap_or(StreamingServers::VidCloud);
+        let category = category.unwrap_or(SubOrSub::Sub);
+
+        let servers = self.servers(episode_id).await?;
+
+        let listed = match category {
+            SubOrSub::Sub => &servers.sub,
+            SubOrSub::Dub => &servers.dub,
+            SubOrSub::Raw => &servers.raw,
+            SubOrSub::Both => bail!("Choose either the sub or the dub servers"),
+        };
+
+        let Some(zoro_server) = listed.iter().find(|s| s.streaming_server() == Some(server)) else {
+            bail!(
+                "Server {} ({}) not found for {}",
+                server,
+                category,
+                episode_id
+            );
+        };
+
+        let embed_json = MIRRORS
+            .get(&format!("/ajax/v2/episode/sources?id={}", zoro_server.id))
+            .await?;
+
+        let embed: RawEmbed = serde_json::from_str(&embed_json)?;
+
+        let config = ExtractConfig::new().with_referer(BASE_URL);
+
+        // Older episodes are still hosted on RapidCloud
+        if embed.link.contains("rapid-cloud") {
+            let rapidcloud = RapidCloud.extract(&embed.link, &config).await?;
+
+            return Ok(ISource {
+                headers: Some(embed.link.clone()),
+                intro: rapidcloud.intro,
+                outro: rapidcloud.outro,
+                subtitles: Some(
+                    rapidcloud
+                        .subtitles
+                        .into_iter()
+                        .map(|track| subtitle(track.url, track.lang))
+                        .collect(),
+                ),
+                sources: Some(
+                    rapidcloud
+                        .sources
+                        .into_iter()
+                        .map(|source| video(source.url, source.quality, source.is_m3u8))
+                        .collect(),
+                ),
+            });
+        }
+
+        let megacloud = MegaCloud.extract(&embed.link, &config).await?;
+
+        Ok(ISource {
+            headers: Some(embed.link.clone()),
+            intro: megacloud.intro,
+            outro: megacloud.outro,
+            subtitles: Some(
+                megacloud
+                    .subtitles
+                    .into_iter()
+                    .map(|track| subtitle(track.url, track.lang))
+                    .collect(),
+            ),
+            sources: Some(
+                megacloud
+                    .sources
+                    .into_iter()
+                    .map(|source| video(source.url, String::from("auto"), source.is_m3u8))
+                    .collect(),
+            ),
+        })
+    }
+}
+
+fn subtitle(url: String, lang: String) -> ISubtitle {
+    ISubtitle {
+        id: None,
+        url: Some(url),
+        lang: Some(lang),
+    }
+}
+
+fn video(url: String, quality: String, is_m3u8: bool) -> IVideo {
+    IVideo {
+        url: Some(url),
+        quality: Some(quality),
+        is_m3u8: Some(is_m3u8),
+        is_dash: None,
+        size: None,
+        other: None,
+    }
+}
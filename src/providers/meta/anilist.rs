@@ -0,0 +1,1200 @@
+use crate::{
+    models::{
+        FuzzyDate, IAnimeCharacter, IAnimeInfo, IAnimeResult, ICharacterName, INextAiringEpisode,
+        ISearch, ITitle, IVoiceActor, MediaFormat, MediaStatus, Trailer, TvType,
+    },
+    providers::meta::mapping::{MetaId, MetaTitles},
+    utils::queries::{
+        anilist_advanced_query, anilist_airing_schedule_query, anilist_character_query,
+        anilist_genres_query, anilist_media_detail_query, anilist_popular_query,
+        anilist_search_query, anilist_site_statistics_query, anilist_staff_query,
+        anilist_trending_query, AnilistType, GraphQLRequest,
+    },
+    utils::util_funcs::Days,
+};
+
+use anyhow::bail;
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, TimeZone, Utc, Weekday};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+/// Metadata from the Anilist GraphQL API
+/// ```no_run
+/// use chrono::Local;
+/// use consumet::{
+///     providers::meta::{Anilist, AnilistFilter, AnilistSort},
+///     utils::{queries::AnilistType, util_funcs::Days},
+/// };
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let results = Anilist.search("frieren", None, None, AnilistType::Anime).await?;
+///     let info = Anilist.info(&results.results[0].id).await?;
+///
+///     if let Some(character) = info.characters.iter().flatten().next() {
+///         let details = Anilist.character(&character.id).await?;
+///         for voice_actor in &character.voice_actors {
+///             let staff = Anilist.staff(&voice_actor.id).await?;
+///             println!("{:?} is voiced by {:?}", details.name.full, staff.name.full);
+///         }
+///     }
+///
+///     let statistics = Anilist.site_statistics().await?;
+///     println!("Anilist lists {:?} anime", statistics.anime);
+///
+///     let filter = AnilistFilter::new()
+///         .with_genres(vec![String::from("Romance")])
+///         .with_year(2023)
+///         .with_sort(vec![AnilistSort::ScoreDesc]);
+///     let romances = Anilist.advanced_search(&filter).await?;
+///
+///     let timezone = *Local::now().offset();
+///     let week = Anilist
+///         .airing_schedule(Days::Monday, Days::Sunday, false, timezone)
+///         .await?;
+///     for day in week {
+///         println!("{:?} {}: {} episodes", day.day, day.date, day.episodes.len());
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+pub struct Anilist;
+
+pub(crate) const BASE_URL: &str = "https://graphql.anilist.co";
+
+/// Filters for [`Anilist::advanced_search`], unset filters are left out of the request
+/// ```
+/// use consumet::{
+///     models::{MediaFormat, MediaStatus},
+///     providers::meta::{AnilistFilter, AnilistSeason},
+/// };
+///
+/// let filter = AnilistFilter::new()
+///     .with_formats(vec![MediaFormat::Tv, MediaFormat::Ona])
+///     .with_status(MediaStatus::OnGoing)
+///     .with_season(AnilistSeason::Fall, 2023);
+///
+/// let variables = filter.variables();
+/// assert_eq!(variables["format"], serde_json::json!(["TV", "ONA"]));
+/// assert_eq!(variables["status"], "RELEASING");
+/// assert_eq!(variables["season"], "FALL");
+/// assert_eq!(variables["seasonYear"], 2023);
+/// assert!(variables.get("genres").is_none());
+/// ```
+#[derive(Default, Clone, Debug)]
+pub struct AnilistFilter {
+    query: Option<String>,
+    media_type: AnilistType,
+    page: Option<u32>,
+    per_page: Option<u32>,
+    formats: Vec<MediaFormat>,
+    status: Option<MediaStatus>,
+    season: Option<(AnilistSeason, u32)>,
+    year: Option<u32>,
+    genres: Vec<String>,
+    excluded_genres: Vec<String>,
+    tags: Vec<String>,
+    sort: Vec<AnilistSort>,
+}
+
+/// The season a show started airing in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AnilistSeason {
+    Winter,
+    Spring,
+    Summer,
+    Fall,
+}
+
+/// Sort orders for [`AnilistFilter::with_sort`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AnilistSort {
+    PopularityDesc,
+    ScoreDesc,
+    TrendingDesc,
+    FavouritesDesc,
+    StartDateDesc,
+    StartDate,
+    UpdatedAtDesc,
+    EpisodesDesc,
+    TitleRomaji,
+}
+
+/// One day of [`Anilist::airing_schedule`], in the caller's timezone
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AnilistScheduleDay {
+    pub day: Days,
+    pub date: NaiveDate,
+    /// in airing order
+    pub episodes: Vec<AnilistAiringEpisode>,
+}
+
+/// Contains an episode of [`Anilist::airing_schedule`]
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AnilistAiringEpisode {
+    /// the Anilist id of the anime
+    pub id: String,
+    pub mal_id: Option<u32>,
+    pub title: ITitle,
+    pub image: Option<String>,
+    pub cover: Option<String>,
+    pub episode: u32,
+    /// in the timezone passed to [`Anilist::airing_schedule`]
+    pub airing_at: DateTime<FixedOffset>,
+    /// seconds left until the episode airs, negative once it has aired
+    pub time_until_airing: i64,
+    pub genres: Vec<String>,
+    /// out of 100
+    pub rating: Option<f32>,
+    pub show_type: Option<MediaFormat>,
+}
+
+/// Contains the details of an Anilist character
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AnilistCharacter {
+    pub id: String,
+    pub name: ICharacterName,
+    pub alternative_names: Vec<String>,
+    pub image: Option<String>,
+    /// markdown, spoilers are wrapped in `~!` and `!~`
+    pub description: Option<String>,
+    pub gender: Option<String>,
+    /// free text, e.g. "17" or "Early 20s"
+    pub age: Option<String>,
+    pub blood_type: Option<String>,
+    pub date_of_birth: Option<FuzzyDate>,
+    pub favourites: Option<u32>,
+    pub appearances: Vec<AnilistAppearance>,
+}
+
+/// Contains a media an [`AnilistCharacter`] appears in
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AnilistAppearance {
+    /// e.g. "MAIN" or "SUPPORTING"
+    pub role: Option<String>,
+    pub media: IAnimeResult,
+}
+
+/// Contains the details of an Anilist staff member, e.g. a voice actor or a director
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AnilistStaff {
+    pub id: String,
+    pub name: ICharacterName,
+    pub alternative_names: Vec<String>,
+    pub image: Option<String>,
+    pub description: Option<String>,
+    /// e.g. "Japanese"
+    pub language: Option<String>,
+    pub occupations: Vec<String>,
+    pub gender: Option<String>,
+    pub age: Option<u32>,
+    pub blood_type: Option<String>,
+    pub home_town: Option<String>,
+    /// the first year, and the last one once they stopped
+    pub years_active: Vec<u32>,
+    pub date_of_birth: Option<FuzzyDate>,
+    pub date_of_death: Option<FuzzyDate>,
+    pub favourites: Option<u32>,
+}
+
+/// Contains the counts Anilist publishes about itself
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct AnilistSiteStatistics {
+    /// the number of anime listed, from the latest daily snapshot
+    pub anime: Option<u64>,
+}
+
+impl AnilistScheduleDay {
+    /// The days from the next `week_start` through the following `week_end`, counted from `today`, without episodes
+    /// ```
+    /// use chrono::{FixedOffset, NaiveDate, TimeZone, Utc};
+    /// use consumet::{providers::meta::AnilistScheduleDay, utils::util_funcs::Days};
+    ///
+    /// // a Monday
+    /// let today = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    /// let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+    ///
+    /// let mut days = AnilistScheduleDay::range(today, Days::Wednesday, Days::Friday);
+    /// assert_eq!(days.len(), 3);
+    /// assert_eq!(days[0].date, NaiveDate::from_ymd_opt(2024, 1, 3).unwrap());
+    /// assert_eq!(days[2].day, Days::Friday);
+    ///
+    /// // both bounds are exclusive, and midnight is Tokyo's
+    /// let (after, before) = AnilistScheduleDay::bounds(&days, &tokyo);
+    /// assert_eq!(after, tokyo.with_ymd_and_hms(2024, 1, 3, 0, 0, 0).unwrap().timestamp() - 1);
+    /// assert_eq!(before, Utc.with_ymd_and_hms(2024, 1, 5, 15, 0, 0).unwrap().timestamp());
+    ///
+    /// // late Thursday evening is still Thursday in Tokyo
+    /// let airing_at = tokyo.with_ymd_and_hms(2024, 1, 4, 23, 30, 0).unwrap();
+    /// let day = AnilistScheduleDay::day_of(&mut days, &airing_at).unwrap();
+    /// assert_eq!(day.day, Days::Thursday);
+    /// // while the same instant is already Thursday afternoon in UTC
+    /// let day = AnilistScheduleDay::day_of(&mut days, &airing_at.with_timezone(&Utc)).unwrap();
+    /// assert_eq!(day.day, Days::Thursday);
+    /// let airing_at = tokyo.with_ymd_and_hms(2024, 1, 6, 0, 30, 0).unwrap();
+    /// assert!(AnilistScheduleDay::day_of(&mut days, &airing_at).is_none());
+    /// // but Friday evening in UTC
+    /// let day = AnilistScheduleDay::day_of(&mut days, &airing_at.with_timezone(&Utc)).unwrap();
+    /// assert_eq!(day.day, Days::Friday);
+    ///
+    /// // a range starting today, and one wrapping around the weekend
+    /// assert_eq!(AnilistScheduleDay::range(today, Days::Monday, Days::Monday)[0].date, today);
+    /// assert_eq!(AnilistScheduleDay::range(today, Days::Friday, Days::Tuesday).len(), 5);
+    /// ```
+    pub fn range(today: NaiveDate, week_start: Days, week_end: Days) -> Vec<Self> {
+        let start = next_date(today, week_start);
+        let end = next_date(start, week_end);
+
+        (0..=(end - start).num_days())
+            .map(|offset| {
+                let date = start + Duration::days(offset);
+
+                Self {
+                    day: date.weekday().into(),
+                    date,
+                    episodes: vec![],
+                }
+            })
+            .collect()
+    }
+
+    /// Unix timestamps just before the first of `days` starts and when the day after the last one starts, in `timezone`
+    pub fn bounds<Tz: TimeZone>(days: &[Self], timezone: &Tz) -> (i64, i64) {
+        match (days.first(), days.last()) {
+            (Some(first), Some(last)) => (
+                midnight(first.date, timezone) - 1,
+                midnight(last.date + Duration::days(1), timezone),
+            ),
+            _ => (0, 0),
+        }
+    }
+
+    /// The day of `days` which `airing_at` falls on, in the timezone of `airing_at`
+    pub fn day_of<'a, Tz: TimeZone>(
+        days: &'a mut [Self],
+        airing_at: &DateTime<Tz>,
+    ) -> Option<&'a mut Self> {
+        let date = airing_at.date_naive();
+
+        days.iter_mut().find(|day| day.date == date)
+    }
+}
+
+impl AnilistFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_query(mut self, query: impl Into<String>) -> Self {
+        self.query = Some(query.into());
+        self
+    }
+
+    /// Anime by default
+    pub fn with_type(mut self, media_type: AnilistType) -> Self {
+        self.media_type = media_type;
+        self
+    }
+
+    pub fn with_page(mut self, page: u32) -> Self {
+        self.page = Some(page);
+        self
+    }
+
+    pub fn with_per_page(mut self, per_page: u32) -> Self {
+        self.per_page = Some(per_page);
+        self
+    }
+
+    pub fn with_formats(mut self, formats: Vec<MediaFormat>) -> Self {
+        self.formats = formats;
+        self
+    }
+
+    /// [`MediaStatus::Unknown`] matches every status
+    pub fn with_status(mut self, status: MediaStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    pub fn with_season(mut self, season: AnilistSeason, year: u32) -> Self {
+        self.season = Some((season, year));
+        self
+    }
+
+    /// The year the media started in
+    pub fn with_year(mut self, year: u32) -> Self {
+        self.year = Some(year);
+        self
+    }
+
+    pub fn with_genres(mut self, genres: Vec<String>) -> Self {
+        self.genres = genres;
+        self
+    }
+
+    pub fn with_excluded_genres(mut self, genres: Vec<String>) -> Self {
+        self.excluded_genres = genres;
+        self
+    }
+
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Popularity then score by default
+    pub fn with_sort(mut self, sort: Vec<AnilistSort>) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// The GraphQL variables sent along with [`anilist_advanced_query`](crate::utils::queries::anilist_advanced_query)
+    pub fn variables(&self) -> Map<String, Value> {
+        let mut variables = Map::new();
+
+        variables.insert("page".into(), json!(self.page.unwrap_or(1)));
+        variables.insert("size".into(), json!(self.per_page.unwrap_or(20)));
+        variables.insert("type".into(), json!(self.media_type));
+
+        if let Some(query) = &self.query {
+            variables.insert("search".into(), json!(query));
+        }
+        if !self.formats.is_empty() {
+            let formats: Vec<String> = self.formats.iter().map(|f| f.to_string()).collect();
+            variables.insert("format".into(), json!(formats));
+        }
+        if let Some(status) = self.status.and_then(anilist_status) {
+            variables.insert("status".into(), json!(status));
+        }
+        if let Some((season, year)) = self.season {
+            variables.insert("season".into(), json!(season));
+            variables.insert("seasonYear".into(), json!(year));
+        }
+        if let Some(year) = self.year {
+            // startDate_like matches FuzzyDateInts such as 20230929
+            variables.insert("year".into(), json!(format!("{}%", year)));
+        }
+        if !self.genres.is_empty() {
+            variables.insert("genres".into(), json!(self.genres));
+        }
+        if !self.excluded_genres.is_empty() {
+            variables.insert("excludedGenres".into(), json!(self.excluded_genres));
+        }
+        if !self.tags.is_empty() {
+            variables.insert("tags".into(), json!(self.tags));
+        }
+        if !self.sort.is_empty() {
+            variables.insert("sort".into(), json!(self.sort));
+        }
+
+        variables
+    }
+}
+
+#[derive(Deserialize)]
+struct RawPageData {
+    #[serde(rename = "Page")]
+    page: RawPage,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawPage {
+    page_info: RawPageInfo,
+    media: Vec<RawMedia>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawPageInfo {
+    total: Option<usize>,
+    current_page: Option<usize>,
+    last_page: Option<usize>,
+    #[serde(default)]
+    has_next_page: bool,
+}
+
+#[derive(Deserialize)]
+struct RawMediaData {
+    #[serde(rename = "Media")]
+    media: RawMedia,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawMedia {
+    id: u32,
+    id_mal: Option<u32>,
+    #[serde(rename = "type")]
+    media_type: Option<String>,
+    title: RawTitle,
+    status: Option<String>,
+    format: Option<String>,
+    cover_image: Option<RawCoverImage>,
+    banner_image: Option<String>,
+    description: Option<String>,
+    genres: Option<Vec<String>>,
+    synonyms: Option<Vec<String>>,
+    episodes: Option<u32>,
+    season: Option<String>,
+    season_year: Option<u32>,
+    average_score: Option<u32>,
+    mean_score: Option<u32>,
+    country_of_origin: Option<String>,
+    is_adult: Option<bool>,
+    is_licensed: Option<bool>,
+    trailer: Option<Trailer>,
+    start_date: Option<FuzzyDate>,
+    end_date: Option<FuzzyDate>,
+    studios: Option<RawEdges<RawStudioEdge>>,
+    recommendations: Option<RawEdges<RawRecommendationEdge>>,
+    relations: Option<RawEdges<RawRelationEdge>>,
+    characters: Option<RawEdges<RawCharacterEdge>>,
+    next_airing_episode: Option<RawNextAiring>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawTitle {
+    romaji: Option<String>,
+    english: Option<String>,
+    native: Option<String>,
+    user_preferred: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawCoverImage {
+    extra_large: Option<String>,
+    large: Option<String>,
+    color: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawEdges<T> {
+    edges: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct RawStudioEdge {
+    node: RawStudio,
+}
+
+#[derive(Deserialize)]
+struct RawStudio {
+    name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawRecommendationEdge {
+    node: RawRecommendation,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawRecommendation {
+    media_recommendation: Option<RawMedia>,
+}
+
+#[derive(Deserialize)]
+struct RawScheduleData {
+    #[serde(rename = "Page")]
+    page: RawSchedulePage,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawSchedulePage {
+    page_info: RawPageInfo,
+    airing_schedules: Vec<RawAiringSchedule>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawAiringSchedule {
+    airing_at: i64,
+    time_until_airing: i64,
+    episode: u32,
+    media: Option<RawMedia>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawRelationEdge {
+    relation_type: Option<String>,
+    node: RawMedia,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawNextAiring {
+    airing_at: i64,
+    time_until_airing: i64,
+    episode: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawCharacterEdge {
+    role: Option<String>,
+    node: RawPerson,
+    #[serde(default)]
+    voice_actors: Vec<RawPerson>,
+}
+
+/// A character, or the staff member voicing one
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawPerson {
+    id: u32,
+    language_v2: Option<String>,
+    name: Option<RawName>,
+    image: Option<RawPersonImage>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawName {
+    first: Option<String>,
+    last: Option<String>,
+    full: Option<String>,
+    native: Option<String>,
+    user_preferred: Option<String>,
+    alternative: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct RawPersonImage {
+    large: Option<String>,
+    medium: Option<String>,
+}
+
+impl RawPersonImage {
+    fn url(self) -> Option<String> {
+        self.large.or(self.medium)
+    }
+}
+
+#[derive(Deserialize)]
+struct RawCharacterData {
+    #[serde(rename = "Character")]
+    character: RawCharacter,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawCharacter {
+    id: u32,
+    name: Option<RawName>,
+    image: Option<RawPersonImage>,
+    description: Option<String>,
+    gender: Option<String>,
+    age: Option<String>,
+    blood_type: Option<String>,
+    date_of_birth: Option<FuzzyDate>,
+    favourites: Option<u32>,
+    media: Option<RawEdges<RawAppearanceEdge>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawAppearanceEdge {
+    character_role: Option<String>,
+    node: RawMedia,
+}
+
+#[derive(Deserialize)]
+struct RawStaffData {
+    #[serde(rename = "Staff")]
+    staff: RawStaff,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawStaff {
+    id: u32,
+    name: Option<RawName>,
+    image: Option<RawPersonImage>,
+    description: Option<String>,
+    language: Option<String>,
+    primary_occupations: Option<Vec<String>>,
+    gender: Option<String>,
+    age: Option<u32>,
+    blood_type: Option<String>,
+    home_town: Option<String>,
+    years_active: Option<Vec<u32>>,
+    date_of_birth: Option<FuzzyDate>,
+    date_of_death: Option<FuzzyDate>,
+    favourites: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct RawSiteStatisticsData {
+    #[serde(rename = "SiteStatistics")]
+    site_statistics: RawSiteStatistics,
+}
+
+#[derive(Deserialize)]
+struct RawSiteStatistics {
+    anime: Option<RawTrends>,
+}
+
+#[derive(Deserialize)]
+struct RawTrends {
+    nodes: Vec<RawTrend>,
+}
+
+#[derive(Deserialize)]
+struct RawTrend {
+    count: u64,
+}
+
+/// The first date on or after `from` which falls on `day`
+fn next_date(from: NaiveDate, day: Days) -> NaiveDate {
+    let from_day = from.weekday().num_days_from_sunday();
+    let day = Weekday::from(day).num_days_from_sunday();
+
+    from + Duration::days(((day + 7 - from_day) % 7) as i64)
+}
+
+/// Unix timestamp of the midnight starting `date` in `timezone`
+fn midnight<Tz: TimeZone>(date: NaiveDate, timezone: &Tz) -> i64 {
+    let midnight = date.and_hms_opt(0, 0, 0).unwrap_or_default();
+
+    // DST can skip midnight, the day then starts at the first valid instant
+    match timezone.from_local_datetime(&midnight).earliest() {
+        Some(midnight) => midnight.timestamp(),
+        None => timezone
+            .from_local_datetime(&(midnight + Duration::hours(1)))
+            .earliest()
+            .map(|midnight| midnight.timestamp())
+            .unwrap_or_else(|| midnight.and_utc().timestamp()),
+    }
+}
+
+/// Maps an Anilist `MediaStatus`
+fn media_status(status: &str) -> MediaStatus {
+    match status {
+        "RELEASING" => MediaStatus::OnGoing,
+        "FINISHED" => MediaStatus::Completed,
+        "HIATUS" => MediaStatus::Hiatus,
+        "CANCELLED" => MediaStatus::Cancelled,
+        "NOT_YET_RELEASED" => MediaStatus::NotYetAired,
+        _ => MediaStatus::Unknown,
+    }
+}
+
+fn anilist_status(status: MediaStatus) -> Option<&'static str> {
+    match status {
+        MediaStatus::OnGoing => Some("RELEASING"),
+        MediaStatus::Completed => Some("FINISHED"),
+        MediaStatus::Hiatus => Some("HIATUS"),
+        MediaStatus::Cancelled => Some("CANCELLED"),
+        MediaStatus::NotYetAired => Some("NOT_YET_RELEASED"),
+        MediaStatus::Unknown => None,
+    }
+}
+
+/// Maps an Anilist `MediaFormat`, the inverse of its [`Display`](std::fmt::Display) impl
+fn media_format(format: &str) -> Option<MediaFormat> {
+    match format {
+        "TV" => Some(MediaFormat::Tv),
+        "TV_SHORT" => Some(MediaFormat::TvShort),
+        "MOVIE" => Some(MediaFormat::Movie),
+        "SPECIAL" => Some(MediaFormat::Special),
+        "OVA" => Some(MediaFormat::Ova),
+        "ONA" => Some(MediaFormat::Ona),
+        "MUSIC" => Some(MediaFormat::Music),
+        "MANGA" => Some(MediaFormat::Manga),
+        "NOVEL" => Some(MediaFormat::Novel),
+        "ONE_SHOT" => Some(MediaFormat::OneShot),
+        _ => None,
+    }
+}
+
+impl From<RawTitle> for ITitle {
+    fn from(title: RawTitle) -> Self {
+        Self {
+            romaji: title.romaji,
+            english: title.english,
+            native: title.native,
+            user_preferred: title.user_preferred,
+        }
+    }
+}
+
+impl From<RawName> for ICharacterName {
+    fn from(name: RawName) -> Self {
+        Self {
+            first: name.first,
+            last: name.last,
+            full: name.full,
+            native: name.native,
+            user_preferred: name.user_preferred,
+        }
+    }
+}
+
+impl From<RawPerson> for IVoiceActor {
+    fn from(person: RawPerson) -> Self {
+        Self {
+            id: person.id.to_string(),
+            language: person.language_v2,
+            name: person.name.map(Into::into).unwrap_or_default(),
+            image: person.image.and_then(RawPersonImage::url),
+        }
+    }
+}
+
+impl From<RawCharacterEdge> for IAnimeCharacter {
+    fn from(edge: RawCharacterEdge) -> Self {
+        Self {
+            id: edge.node.id.to_string(),
+            role: edge.role,
+            name: edge.node.name.map(Into::into).unwrap_or_default(),
+            image: edge.node.image.and_then(RawPersonImage::url),
+            voice_actors: edge.voice_actors.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<RawCharacter> for AnilistCharacter {
+    fn from(character: RawCharacter) -> Self {
+        let mut name = character.name;
+        let alternative_names = name
+            .as_mut()
+            .and_then(|name| name.alternative.take())
+            .unwrap_or_default();
+
+        Self {
+            id: character.id.to_string(),
+            name: name.map(Into::into).unwrap_or_default(),
+            alternative_names,
+            image: character.image.and_then(RawPersonImage::url),
+            description: character.description,
+            gender: character.gender,
+            age: character.age,
+            blood_type: character.blood_type,
+            date_of_birth: character.date_of_birth,
+            favourites: character.favourites,
+            appearances: character
+                .media
+                .map(|media| {
+                    media
+                        .edges
+                        .into_iter()
+                        .map(|edge| AnilistAppearance {
+                            role: edge.character_role,
+                            media: edge.node.into(),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl From<RawStaff> for AnilistStaff {
+    fn from(staff: RawStaff) -> Self {
+        let mut name = staff.name;
+        let alternative_names = name
+            .as_mut()
+            .and_then(|name| name.alternative.take())
+            .unwrap_or_default();
+
+        Self {
+            id: staff.id.to_string(),
+            name: name.map(Into::into).unwrap_or_default(),
+            alternative_names,
+            image: staff.image.and_then(RawPersonImage::url),
+            description: staff.description,
+            language: staff.language,
+            occupations: staff.primary_occupations.unwrap_or_default(),
+            gender: staff.gender,
+            age: staff.age,
+            blood_type: staff.blood_type,
+            home_town: staff.home_town,
+            years_active: staff.years_active.unwrap_or_default(),
+            date_of_birth: staff.date_of_birth,
+            date_of_death: staff.date_of_death,
+            favourites: staff.favourites,
+        }
+    }
+}
+
+impl From<RawNextAiring> for INextAiringEpisode {
+    fn from(airing: RawNextAiring) -> Self {
+        Self {
+            airing_time: airing.airing_at,
+            time_until_airing: airing.time_until_airing,
+            episode: airing.episode,
+        }
+    }
+}
+
+impl RawMedia {
+    fn url(&self) -> String {
+        let kind = match self.media_type.as_deref() {
+            Some("MANGA") => "manga",
+            _ => "anime",
+        };
+
+        format!("https://anilist.co/{}/{}", kind, self.id)
+    }
+
+    fn image(&self) -> Option<String> {
+        self.cover_image
+            .as_ref()
+            .and_then(|cover| cover.extra_large.clone().or(cover.large.clone()))
+    }
+
+    fn rating(&self) -> Option<f32> {
+        self.average_score
+            .or(self.mean_score)
+            .map(|score| score as f32)
+    }
+}
+
+impl From<RawMedia> for IAnimeResult {
+    fn from(media: RawMedia) -> Self {
+        Self {
+            id: media.id.to_string(),
+            url: Some(media.url()),
+            image: media.image(),
+            rating: media.rating(),
+            title: media.title.into(),
+            cover: media.banner_image,
+            status: media.status.as_deref().map(media_status),
+            show_type: media.format.as_deref().and_then(media_format),
+            release_date: media.season_year.map(|year| year.to_string()),
+            relation_type: None,
+        }
+    }
+}
+
+impl From<RawPage> for ISearch<IAnimeResult> {
+    fn from(page: RawPage) -> Self {
+        let results: Vec<IAnimeResult> = page.media.into_iter().map(Into::into).collect();
+
+        Self {
+            current_page: page.page_info.current_page,
+            has_next_page: page.page_info.has_next_page,
+            total_pages: page.page_info.last_page,
+            total_results: page.page_info.total.unwrap_or(results.len()),
+            results,
+        }
+    }
+}
+
+impl From<RawMedia> for IAnimeInfo {
+    fn from(media: RawMedia) -> Self {
+        Self {
+            id: media.id.to_string(),
+            url: Some(media.url()),
+            image: media.image(),
+            title: media.title.into(),
+            mal_id: media.id_mal,
+            genres: media.genres,
+            description: media.description,
+            status: media.status.as_deref().map(media_status),
+            total_episodes: media.episodes,
+            sub_or_dub: None,
+            synonyms: media.synonyms,
+            country_of_origin: media.country_of_origin,
+            is_adult: media.is_adult,
+            is_licensed: media.is_licensed,
+            season: media.season,
+            studios: media.studios.map(|studios| {
+                studios
+                    .edges
+                    .into_iter()
+                    .map(|edge| edge.node.name)
+                    .collect()
+            }),
+            color: media
+                .cover_image
+                .as_ref()
+                .and_then(|cover| cover.color.clone()),
+            cover: media.banner_image,
+            trailer: media.trailer,
+            // Anilist does not list episodes, they come from the anime providers
+            episodes: None,
+            start_date: media.start_date,
+            end_date: media.end_date,
+            recommendations: media.recommendations.map(|recommendations| {
+                recommendations
+                    .edges
+                    .into_iter()
+                    .filter_map(|edge| edge.node.media_recommendation)
+                    .map(Into::into)
+                    .collect()
+            }),
+            relations: media.relations.map(|relations| {
+                relations
+                    .edges
+                    .into_iter()
+                    .map(|edge| IAnimeResult {
+                        relation_type: edge.relation_type,
+                        ..edge.node.into()
+                    })
+                    .collect()
+            }),
+            characters: media
+                .characters
+                .map(|characters| characters.edges.into_iter().map(Into::into).collect()),
+            next_airing_episode: media.next_airing_episode.map(Into::into),
+        }
+    }
+}
+
+impl Anilist {
+    async fn request<T: DeserializeOwned, V: Serialize>(
+        &self,
+        request: GraphQLRequest<V>,
+    ) -> anyhow::Result<T> {
+        request.send(BASE_URL).await
+    }
+
+    /// Returns a future which resolves into ISearch\<IAnimeResult\>. (*[`impl Future<Output = Result<ISearch<IAnimeResult>>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/anilist.rs)*)\
+    /// # Parameters
+    /// * `query` - query to search for.
+    /// * `page (optional)` - page number (default: 1)
+    /// * `per_page (optional)` - number of results per page (default: 20)
+    /// * `query_type` - Either AnilistType::Anime or AnilistType::Manga
+    pub async fn search(
+        &self,
+        query: &str,
+        page: Option<u32>,
+        per_page: Option<u32>,
+        query_type: AnilistType,
+    ) -> anyhow::Result<ISearch<IAnimeResult>> {
+        let data: RawPageData = self
+            .request(anilist_search_query(query, page, per_page, query_type))
+            .await?;
+
+        Ok(data.page.into())
+    }
+
+    /// Returns a future which resolves into ISearch\<IAnimeResult\>. (*[`impl Future<Output = Result<ISearch<IAnimeResult>>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/anilist.rs)*)\
+    /// # Parameters
+    /// * `filter` - see [`AnilistFilter`]
+    pub async fn advanced_search(
+        &self,
+        filter: &AnilistFilter,
+    ) -> anyhow::Result<ISearch<IAnimeResult>> {
+        let data: RawPageData = self
+            .request(anilist_advanced_query(filter.variables()))
+            .await?;
+
+        Ok(data.page.into())
+    }
+
+    /// Titles, year and type of an entry, the input of [`IdMapper`](crate::providers::meta::IdMapper)
+    pub(crate) async fn titles(&self, id: u32) -> anyhow::Result<MetaTitles> {
+        let data: RawMediaData = self.request(anilist_media_detail_query(id)).await?;
+        let media = data.media;
+
+        let mut titles: Vec<String> = [media.title.romaji, media.title.english, media.title.native]
+            .into_iter()
+            .flatten()
+            .collect();
+        titles.extend(media.synonyms.unwrap_or_default());
+
+        Ok(MetaTitles {
+            titles,
+            year: media
+                .season_year
+                .or(media.start_date.and_then(|date| date.year)),
+            media_type: media.format.as_deref().map(|format| match format {
+                "MOVIE" => TvType::Movie,
+                _ => TvType::TvSeries,
+            }),
+            ids: media.id_mal.map(MetaId::Mal).into_iter().collect(),
+        })
+    }
+
+    /// Returns a future which resolves into IAnimeInfo. (*[`impl Future<Output = Result<IAnimeInfo>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/anilist.rs)*)\
+    /// # Parameters
+    /// * `id` - the Anilist id
+    pub async fn info(&self, id: &str) -> anyhow::Result<IAnimeInfo> {
+        let Ok(id) = id.parse::<u32>() else {
+            bail!("{} is not an Anilist id", id);
+        };
+
+        let data: RawMediaData = self.request(anilist_media_detail_query(id)).await?;
+
+        Ok(data.media.into())
+    }
+
+    /// Returns a future which resolves into AnilistCharacter, with the media the character appears in. (*[`impl Future<Output = Result<AnilistCharacter>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/anilist.rs)*)\
+    /// # Parameters
+    /// * `id` - the Anilist id of the character, e.g. an [`IAnimeCharacter::id`]
+    pub async fn character(&self, id: &str) -> anyhow::Result<AnilistCharacter> {
+        let Ok(id) = id.parse::<u32>() else {
+            bail!("{} is not an Anilist character id", id);
+        };
+
+        let data: RawCharacterData = self.request(anilist_character_query(id)).await?;
+
+        Ok(data.character.into())
+    }
+
+    /// Returns a future which resolves into AnilistStaff. (*[`impl Future<Output = Result<AnilistStaff>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/anilist.rs)*)\
+    /// # Parameters
+    /// * `id` - the Anilist id of the staff member, e.g. an [`IVoiceActor::id`]
+    pub async fn staff(&self, id: &str) -> anyhow::Result<AnilistStaff> {
+        let Ok(id) = id.parse::<u32>() else {
+            bail!("{} is not an Anilist staff id", id);
+        };
+
+        let data: RawStaffData = self.request(anilist_staff_query(id)).await?;
+
+        Ok(data.staff.into())
+    }
+
+    /// Returns a future which resolves into AnilistSiteStatistics. (*[`impl Future<Output = Result<AnilistSiteStatistics>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/anilist.rs)*)
+    pub async fn site_statistics(&self) -> anyhow::Result<AnilistSiteStatistics> {
+        let data: RawSiteStatisticsData = self.request(anilist_site_statistics_query()).await?;
+
+        Ok(AnilistSiteStatistics {
+            anime: data
+                .site_statistics
+                .anime
+                .and_then(|trends| trends.nodes.first().map(|trend| trend.count)),
+        })
+    }
+
+    /// Returns a future which resolves into ISearch\<IAnimeResult\>. (*[`impl Future<Output = Result<ISearch<IAnimeResult>>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/anilist.rs)*)\
+    /// # Parameters
+    /// * `page (optional)` - page number (default: 1)
+    /// * `per_page (optional)` - number of results per page (default: 20)
+    /// * `query_type` - Either AnilistType::Anime or AnilistType::Manga
+    pub async fn trending(
+        &self,
+        page: Option<u32>,
+        per_page: Option<u32>,
+        query_type: AnilistType,
+    ) -> anyhow::Result<ISearch<IAnimeResult>> {
+        let data: RawPageData = self
+            .request(anilist_trending_query(page, per_page, query_type))
+            .await?;
+
+        Ok(data.page.into())
+    }
+
+    /// Returns a future which resolves into ISearch\<IAnimeResult\>. (*[`impl Future<Output = Result<ISearch<IAnimeResult>>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/anilist.rs)*)\
+    /// # Parameters
+    /// * `page (optional)` - page number (default: 1)
+    /// * `per_page (optional)` - number of results per page (default: 20)
+    /// * `query_type` - Either AnilistType::Anime or AnilistType::Manga
+    pub async fn popular(
+        &self,
+        page: Option<u32>,
+        per_page: Option<u32>,
+        query_type: AnilistType,
+    ) -> anyhow::Result<ISearch<IAnimeResult>> {
+        let data: RawPageData = self
+            .request(anilist_popular_query(page, per_page, query_type))
+            .await?;
+
+        Ok(data.page.into())
+    }
+
+    /// Returns a future which resolves into ISearch\<IAnimeResult\>, anime in any of `genres`. (*[`impl Future<Output = Result<ISearch<IAnimeResult>>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/anilist.rs)*)\
+    /// # Parameters
+    /// * `genres` - e.g. "Action", "Slice of Life"
+    /// * `page (optional)` - page number (default: 1)
+    /// * `per_page (optional)` - number of results per page (default: 20)
+    pub async fn genres(
+        &self,
+        genres: Vec<String>,
+        page: Option<u32>,
+        per_page: Option<u32>,
+    ) -> anyhow::Result<ISearch<IAnimeResult>> {
+        let data: RawPageData = self
+            .request(anilist_genres_query(genres, page, per_page))
+            .await?;
+
+        Ok(data.page.into())
+    }
+
+    /// Returns a future which resolves into the episodes airing from the next `week_start` through the following `week_end`,
+    /// grouped per day in `timezone`. (*[`impl Future<Output = Result<Vec<AnilistScheduleDay>>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/anilist.rs)*)\
+    /// Every day of the range is listed, including days without episodes. A `week_start` of today starts the range today.
+    /// # Parameters
+    /// * `week_start` - first day of the range
+    /// * `week_end` - last day of the range, e.g. the same as `week_start` for a single day
+    /// * `not_yet_aired` - leave out the episodes which have already aired, `false` keeps them
+    /// * `timezone` - the caller's timezone, which decides today and where each day starts, e.g. `*chrono::Local::now().offset()`
+    pub async fn airing_schedule(
+        &self,
+        week_start: Days,
+        week_end: Days,
+        not_yet_aired: bool,
+        timezone: FixedOffset,
+    ) -> anyhow::Result<Vec<AnilistScheduleDay>> {
+        let today = Utc::now().with_timezone(&timezone).date_naive();
+        let mut days = AnilistScheduleDay::range(today, week_start, week_end);
+
+        // both bounds are exclusive
+        let (after, before) = AnilistScheduleDay::bounds(&days, &timezone);
+
+        let mut page = 1;
+
+        loop {
+            let data: RawScheduleData = self
+                .request(anilist_airing_schedule_query(
+                    Some(page),
+                    Some(50),
+                    after,
+                    before,
+                    not_yet_aired,
+                ))
+                .await?;
+
+            for schedule in data.page.airing_schedules {
+                let Some(media) = schedule.media else {
+                    continue;
+                };
+                let Some(airing_at) = timezone.timestamp_opt(schedule.airing_at, 0).single() else {
+                    continue;
+                };
+
+                let Some(day) = AnilistScheduleDay::day_of(&mut days, &airing_at) else {
+                    continue;
+                };
+
+                day.episodes.push(AnilistAiringEpisode {
+                    id: media.id.to_string(),
+                    mal_id: media.id_mal,
+                    image: media.image(),
+                    rating: media.rating(),
+                    title: media.title.into(),
+                    cover: media.banner_image,
+                    episode: schedule.episode,
+                    airing_at,
+                    time_until_airing: schedule.time_until_airing,
+                    genres: media.genres.unwrap_or_default(),
+                    show_type: media.format.as_deref().and_then(media_format),
+                });
+            }
+
+            if !data.page.page_info.has_next_page {
+                break;
+            }
+
+            page += 1;
+        }
+
+        for day in &mut days {
+            day.episodes.sort_by_key(|episode| episode.airing_at);
+        }
+
+        Ok(days)
+    }
+}
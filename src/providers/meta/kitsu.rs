@@ -0,0 +1,591 @@
+use crate::{
+    models::TvType,
+    models::{
+        FuzzyDate, IAnimeEpisode, IAnimeInfo, IAnimeResult, IMangaInfo, IMangaResult, ISearch,
+        ITitle, MediaFormat, MediaStatus,
+    },
+    providers::meta::mapping::{MetaId, MetaTitles},
+    utils::queries::{kitsu_search_query, AnilistType},
+    CLIENT,
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Metadata from Kitsu, a fallback for when Anilist rate limits
+/// ```no_run
+/// use consumet::{providers::meta::Kitsu, utils::queries::AnilistType};
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let results = Kitsu.search("cowboy bebop").await?;
+///     let id = &results.results[0].id;
+///
+///     let info = Kitsu.info(id).await?;
+///     let episodes = Kitsu.episodes(id, None).await?;
+///     let mappings = Kitsu.mappings(id, AnilistType::Anime).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub struct Kitsu;
+
+pub(crate) const BASE_URL: &str = "https://kitsu.io/api/edge";
+pub(crate) const GRAPHQL_URL: &str = "https://kitsu.io/api/graphql";
+
+/// The REST api returns at most 20 resources per page
+const PAGE_LIMIT: usize = 20;
+
+/// Ids of the same media on other sites
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct KitsuMappings {
+    pub mal_id: Option<u32>,
+    pub anilist_id: Option<u32>,
+    pub anidb_id: Option<u32>,
+    /// `kind/id`, e.g. `tv/1399`
+    pub thetvdb_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawSearchData {
+    #[serde(rename = "searchAnimeByTitle")]
+    search: RawConnection,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawConnection {
+    total_count: Option<usize>,
+    page_info: Option<RawPageInfo>,
+    nodes: Vec<RawNode>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawPageInfo {
+    has_next_page: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawNode {
+    id: String,
+    start_date: Option<String>,
+    status: Option<String>,
+    subtype: Option<String>,
+    average_rating: Option<f32>,
+    titles: RawNodeTitles,
+    poster_image: Option<RawNodeImage>,
+    banner_image: Option<RawNodeImage>,
+}
+
+#[derive(Deserialize)]
+struct RawNodeTitles {
+    canonical: Option<String>,
+    #[serde(default)]
+    localized: HashMap<String, String>,
+    romanized: Option<String>,
+    original: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawNodeImage {
+    original: Option<RawImageView>,
+}
+
+#[derive(Deserialize)]
+struct RawImageView {
+    url: String,
+}
+
+/// A JSON:API document
+#[derive(Deserialize)]
+struct RawDocument<T> {
+    data: T,
+    #[serde(default)]
+    included: Vec<RawIncluded>,
+    meta: Option<RawMeta>,
+    links: Option<RawLinks>,
+}
+
+#[derive(Deserialize)]
+struct RawResource<A> {
+    id: String,
+    attributes: A,
+}
+
+#[derive(Deserialize)]
+struct RawIncluded {
+    #[serde(rename = "type")]
+    kind: String,
+    attributes: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+struct RawMeta {
+    count: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct RawLinks {
+    next: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawMediaAttributes {
+    canonical_title: Option<String>,
+    #[serde(default)]
+    titles: HashMap<String, Option<String>>,
+    #[serde(default)]
+    abbreviated_titles: Option<Vec<String>>,
+    synopsis: Option<String>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    status: Option<String>,
+    subtype: Option<String>,
+    episode_count: Option<u32>,
+    nsfw: Option<bool>,
+    poster_image: Option<RawImage>,
+    cover_image: Option<RawImage>,
+}
+
+#[derive(Deserialize)]
+struct RawImage {
+    original: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawEpisodeAttributes {
+    number: Option<u32>,
+    canonical_title: Option<String>,
+    synopsis: Option<String>,
+    airdate: Option<String>,
+    thumbnail: Option<RawImage>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawMappingAttributes {
+    external_site: String,
+    external_id: String,
+}
+
+#[derive(Deserialize)]
+struct RawCategoryAttributes {
+    title: String,
+}
+
+/// Maps a Kitsu status, the REST api uses lowercase and GraphQL uppercase
+fn media_status(status: &str) -> MediaStatus {
+    match status.to_lowercase().as_str() {
+        "current" => MediaStatus::OnGoing,
+        "finished" => MediaStatus::Completed,
+        "tba" | "unreleased" | "upcoming" => MediaStatus::NotYetAired,
+        _ => MediaStatus::Unknown,
+    }
+}
+
+/// Maps a Kitsu anime or manga subtype
+fn media_format(subtype: &str) -> Option<MediaFormat> {
+    match subtype.to_lowercase().as_str() {
+        "tv" => Some(MediaFormat::Tv),
+        "movie" => Some(MediaFormat::Movie),
+        "special" => Some(MediaFormat::Special),
+        "ova" => Some(MediaFormat::Ova),
+        "ona" => Some(MediaFormat::Ona),
+        "music" => Some(MediaFormat::Music),
+        "manga" | "manhua" | "manhwa" | "oel" | "doujin" => Some(MediaFormat::Manga),
+        "novel" => Some(MediaFormat::Novel),
+        "oneshot" => Some(MediaFormat::OneShot),
+        _ => None,
+    }
+}
+
+/// Kitsu dates look like "1998-04-03"
+fn fuzzy_date(date: &str) -> FuzzyDate {
+    let mut parts = date.splitn(3, '-').map(|part| part.parse::<u32>().ok());
+
+    FuzzyDate {
+        year: parts.next().flatten(),
+        month: parts.next().flatten(),
+        day: parts.next().flatten(),
+    }
+}
+
+fn year(date: &str) -> Option<u32> {
+    date.get(..4).and_then(|year| year.parse().ok())
+}
+
+impl From<RawNode> for IAnimeResult {
+    fn from(node: RawNode) -> Self {
+        let localized = |key: &str| node.titles.localized.get(key).cloned();
+
+        Self {
+            url: Some(format!("https://kitsu.io/anime/{}", node.id)),
+            title: ITitle {
+                romaji: node.titles.romanized.clone().or(localized("en_jp")),
+                english: localized("en").or(localized("en_us")),
+                native: node.titles.original.clone().or(localized("ja_jp")),
+                user_preferred: node.titles.canonical.clone(),
+            },
+            id: node.id,
+            image: node
+                .poster_image
+                .and_then(|image| image.original)
+                .map(|view| view.url),
+            cover: node
+                .banner_image
+                .and_then(|image| image.original)
+                .map(|view| view.url),
+            status: node.status.as_deref().map(media_status),
+            rating: node.average_rating,
+            show_type: node.subtype.as_deref().and_then(media_format),
+            release_date: node
+                .start_date
+                .as_deref()
+                .and_then(year)
+                .map(|year| year.to_string()),
+            relation_type: None,
+        }
+    }
+}
+
+impl From<RawResource<RawMediaAttributes>> for IMangaResult {
+    fn from(manga: RawResource<RawMediaAttributes>) -> Self {
+        let attributes = manga.attributes;
+
+        Self {
+            id: manga.id,
+            title: attributes.canonical_title.clone().unwrap_or_default(),
+            alt_titles: Some(
+                attributes
+                    .titles
+                    .values()
+                    .flatten()
+                    .filter(|title| Some(*title) != attributes.canonical_title.as_ref())
+                    .cloned()
+                    .collect(),
+            ),
+            image: attributes.poster_image.and_then(|image| image.original),
+            description: attributes.synopsis,
+            status: attributes.status.as_deref().map(media_status),
+            release_date: attributes.start_date.as_deref().and_then(year),
+        }
+    }
+}
+
+/// Picks the ids of other sites out of Kitsu mappings
+fn mappings(mappings: impl Iterator<Item = RawMappingAttributes>) -> KitsuMappings {
+    let mut ids = KitsuMappings::default();
+
+    for mapping in mappings {
+        let site = mapping.external_site.as_str();
+        let id = mapping.external_id;
+
+        match site.split('/').next().unwrap_or_default() {
+            "myanimelist" => ids.mal_id = ids.mal_id.or(id.parse().ok()),
+            "anilist" => ids.anilist_id = ids.anilist_id.or(id.parse().ok()),
+            "anidb" => ids.anidb_id = ids.anidb_id.or(id.parse().ok()),
+            "thetvdb" => ids.thetvdb_id = ids.thetvdb_id.or(Some(id)),
+            _ => {}
+        }
+    }
+
+    ids
+}
+
+/// The included resources of one kind
+fn included<A: DeserializeOwned>(included: &[RawIncluded], kind: &str) -> Vec<A> {
+    included
+        .iter()
+        .filter(|resource| resource.kind == kind)
+        .filter_map(|resource| serde_json::from_value(resource.attributes.clone()).ok())
+        .collect()
+}
+
+fn search_page<T>(
+    results: Vec<T>,
+    page: usize,
+    meta: Option<RawMeta>,
+    links: Option<RawLinks>,
+) -> ISearch<T> {
+    let total_results = meta.and_then(|meta| meta.count).unwrap_or(results.len());
+
+    ISearch {
+        current_page: Some(page),
+        has_next_page: links.and_then(|links| links.next).is_some(),
+        total_pages: Some(total_results.div_ceil(PAGE_LIMIT)),
+        total_results,
+        results,
+    }
+}
+
+impl Kitsu {
+    async fn get<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+    ) -> anyhow::Result<T> {
+        Ok(CLIENT
+            .get(format!("{}{}", BASE_URL, path))
+            .header("Accept", "application/vnd.api+json")
+            .query(query)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<T>()
+            .await?)
+    }
+
+    /// Returns a future which resolves into the first page of ISearch\<IAnimeResult\>, the paging fields count every match. (*[`impl Future<Output = Result<ISearch<IAnimeResult>>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/kitsu.rs)*)\
+    /// # Parameters
+    /// * `query` - query to search for.
+    pub async fn search(&self, query: &str) -> anyhow::Result<ISearch<IAnimeResult>> {
+        let request = kitsu_search_query(query, None);
+        let per_page = request.variables.first as usize;
+
+        let data: RawSearchData = request.send(GRAPHQL_URL).await?;
+
+        let has_next_page = data
+            .search
+            .page_info
+            .is_some_and(|page_info| page_info.has_next_page);
+        let results: Vec<IAnimeResult> = data.search.nodes.into_iter().map(Into::into).collect();
+        let total_results = data.search.total_count.unwrap_or(results.len());
+
+        Ok(ISearch {
+            current_page: Some(1),
+            has_next_page,
+            total_pages: Some(total_results.div_ceil(per_page).max(1)),
+            total_results,
+            results,
+        })
+    }
+
+    /// Returns a future which resolves into ISearch\<IMangaResult\>. (*[`impl Future<Output = Result<ISearch<IMangaResult>>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/kitsu.rs)*)\
+    /// # Parameters
+    /// * `query` - query to search for.
+    /// * `page (optional)` - page number (default: 1)
+    pub async fn search_manga(
+        &self,
+        query: &str,
+        page: Option<usize>,
+    ) -> anyhow::Result<ISearch<IMangaResult>> {
+        let page = page.unwrap_or(1).max(1);
+
+        let document: RawDocument<Vec<RawResource<RawMediaAttributes>>> = self
+            .get(
+                "/manga",
+                &[
+                    ("filter[text]", query.to_owned()),
+                    ("page[limit]", PAGE_LIMIT.to_string()),
+                    ("page[offset]", ((page - 1) * PAGE_LIMIT).to_string()),
+                ],
+            )
+            .await?;
+
+        let results = document.data.into_iter().map(Into::into).collect();
+
+        Ok(search_page(results, page, document.meta, document.links))
+    }
+
+    /// Titles, year and type of an entry, the input of [`IdMapper`](crate::providers::meta::IdMapper)
+    pub(crate) async fn titles(&self, id: &str) -> anyhow::Result<MetaTitles> {
+        let document: RawDocument<RawResource<RawMediaAttributes>> = self
+            .get(
+                &format!("/anime/{}", id),
+                &[("include", String::from("mappings"))],
+            )
+            .await?;
+
+        let attributes = document.data.attributes;
+        let ids = mappings(included(&document.included, "mappings").into_iter());
+
+        let mut titles: Vec<String> = attributes.canonical_title.into_iter().collect();
+        for key in ["en_jp", "en", "en_us", "ja_jp"] {
+            if let Some(title) = attributes.titles.get(key).cloned().flatten() {
+                if !titles.contains(&title) {
+                    titles.push(title);
+                }
+            }
+        }
+        titles.extend(attributes.abbreviated_titles.unwrap_or_default());
+
+        Ok(MetaTitles {
+            titles,
+            year: attributes.start_date.as_deref().and_then(year),
+            media_type: attributes.subtype.map(|subtype| match subtype.as_str() {
+                "movie" => TvType::Movie,
+                _ => TvType::TvSeries,
+            }),
+            ids: [
+                Some(MetaId::Kitsu(document.data.id)),
+                ids.mal_id.map(MetaId::Mal),
+                ids.anilist_id.map(MetaId::Anilist),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+        })
+    }
+
+    /// Returns a future which resolves into IAnimeInfo, without episodes (see [`Kitsu::episodes`]). (*[`impl Future<Output = Result<IAnimeInfo>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/kitsu.rs)*)\
+    /// # Parameters
+    /// * `id` - the Kitsu id
+    pub async fn info(&self, id: &str) -> anyhow::Result<IAnimeInfo> {
+        let document: RawDocument<RawResource<RawMediaAttributes>> = self
+            .get(
+                &format!("/anime/{}", id),
+                &[("include", String::from("categories,mappings"))],
+            )
+            .await?;
+
+        let attributes = document.data.attributes;
+        let ids = mappings(included(&document.included, "mappings").into_iter());
+
+        let title = |key: &str| attributes.titles.get(key).cloned().flatten();
+
+        Ok(IAnimeInfo {
+            url: Some(format!("https://kitsu.io/anime/{}", document.data.id)),
+            id: document.data.id,
+            title: ITitle {
+                romaji: title("en_jp"),
+                english: title("en").or(title("en_us")),
+                native: title("ja_jp"),
+                user_preferred: attributes.canonical_title.clone(),
+            },
+            image: attributes.poster_image.and_then(|image| image.original),
+            mal_id: ids.mal_id,
+            genres: Some(
+                included::<RawCategoryAttributes>(&document.included, "categories")
+                    .into_iter()
+                    .map(|category| category.title)
+                    .collect(),
+            ),
+            description: attributes.synopsis,
+            status: attributes.status.as_deref().map(media_status),
+            total_episodes: attributes.episode_count,
+            sub_or_dub: None,
+            synonyms: attributes.abbreviated_titles,
+            country_of_origin: None,
+            is_adult: attributes.nsfw,
+            is_licensed: None,
+            season: None,
+            studios: None,
+            color: None,
+            cover: attributes.cover_image.and_then(|image| image.original),
+            trailer: None,
+            episodes: None,
+            start_date: attributes.start_date.as_deref().map(fuzzy_date),
+            end_date: attributes.end_date.as_deref().map(fuzzy_date),
+            recommendations: None,
+            relations: None,
+            characters: None,
+            next_airing_episode: None,
+        })
+    }
+
+    /// Returns a future which resolves into IMangaInfo. (*[`impl Future<Output = Result<IMangaInfo>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/kitsu.rs)*)\
+    /// # Parameters
+    /// * `id` - the Kitsu id
+    pub async fn manga_info(&self, id: &str) -> anyhow::Result<IMangaInfo> {
+        let document: RawDocument<RawResource<RawMediaAttributes>> = self
+            .get(
+                &format!("/manga/{}", id),
+                &[("include", String::from("categories,mappings"))],
+            )
+            .await?;
+
+        let ids = mappings(included(&document.included, "mappings").into_iter());
+        let genres = included::<RawCategoryAttributes>(&document.included, "categories")
+            .into_iter()
+            .map(|category| category.title)
+            .collect();
+
+        let manga: IMangaResult = document.data.into();
+
+        Ok(IMangaInfo {
+            links: Some(vec![format!("https://kitsu.io/manga/{}", manga.id)]),
+            id: manga.id,
+            title: manga.title,
+            alt_titles: manga.alt_titles,
+            image: manga.image,
+            description: manga.description,
+            status: manga.status,
+            release_date: manga.release_date,
+            mal_id: ids.mal_id,
+            authors: None,
+            genres: Some(genres),
+            characters: None,
+            recommendations: None,
+            chapters: None,
+        })
+    }
+
+    /// Returns a future which resolves into a page of ISearch\<IAnimeEpisode\>, in episode order. (*[`impl Future<Output = Result<ISearch<IAnimeEpisode>>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/kitsu.rs)*)\
+    /// # Parameters
+    /// * `id` - the Kitsu id of the anime
+    /// * `page (optional)` - page number (default: 1), 20 episodes per page
+    pub async fn episodes(
+        &self,
+        id: &str,
+        page: Option<usize>,
+    ) -> anyhow::Result<ISearch<IAnimeEpisode>> {
+        let page = page.unwrap_or(1).max(1);
+
+        let document: RawDocument<Vec<RawResource<RawEpisodeAttributes>>> = self
+            .get(
+                &format!("/anime/{}/episodes", id),
+                &[
+                    ("sort", String::from("number")),
+                    ("page[limit]", PAGE_LIMIT.to_string()),
+                    ("page[offset]", ((page - 1) * PAGE_LIMIT).to_string()),
+                ],
+            )
+            .await?;
+
+        let results = document
+            .data
+            .into_iter()
+            .map(|episode| IAnimeEpisode {
+                url: Some(format!("https://kitsu.io/api/edge/episodes/{}", episode.id)),
+                id: episode.id,
+                number: episode.attributes.number.unwrap_or_default(),
+                title: episode.attributes.canonical_title,
+                description: episode.attributes.synopsis,
+                is_filler: None,
+                image: episode
+                    .attributes
+                    .thumbnail
+                    .and_then(|thumbnail| thumbnail.original),
+                release_date: episode.attributes.airdate,
+            })
+            .collect();
+
+        Ok(search_page(results, page, document.meta, document.links))
+    }
+
+    /// Returns a future which resolves into the MAL, Anilist, AniDB and TheTVDB ids of a Kitsu entry. (*[`impl Future<Output = Result<KitsuMappings>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/kitsu.rs)*)\
+    /// # Parameters
+    /// * `id` - the Kitsu id
+    /// * `media_type` - Either AnilistType::Anime or AnilistType::Manga
+    pub async fn mappings(
+        &self,
+        id: &str,
+        media_type: AnilistType,
+    ) -> anyhow::Result<KitsuMappings> {
+        let kind = match media_type {
+            AnilistType::Anime => "anime",
+            AnilistType::Manga => "manga",
+        };
+
+        let document: RawDocument<Vec<RawResource<RawMappingAttributes>>> =
+            self.get(&format!("/{}/{}/mappings", kind, id), &[]).await?;
+
+        Ok(mappings(
+            document.data.into_iter().map(|mapping| mapping.attributes),
+        ))
+    }
+}
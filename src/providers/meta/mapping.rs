@@ -0,0 +1,427 @@
+use crate::{
+    models::TvType,
+    providers::{
+        meta::{Anilist, Kitsu, MyAnimeList, Tmdb},
+        movies::{
+            self,
+            dramacool::{DramaCoolListing, DramaCoolSearchResults},
+            DramaCool, ViewAsian,
+        },
+    },
+    utils::matching::{normalize_title, release_year, MatchCandidate, MatchQuery},
+};
+
+use anyhow::bail;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, path::Path, sync::RwLock};
+
+/// Candidates scoring at least this much are cached as confirmed mappings
+pub const AUTO_CONFIRM_CONFIDENCE: f32 = 0.95;
+
+/// How many of the metadata titles are searched on the provider
+const SEARCHED_TITLES: usize = 3;
+
+/// An id on one of the metadata sites
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum MetaId {
+    Anilist(u32),
+    Mal(u32),
+    Kitsu(String),
+    /// TMDB ids are only unique per media type
+    Tmdb(u32, TvType),
+}
+
+/// The streaming providers metadata ids are mapped onto
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum MappingProvider {
+    FlixHQ,
+    SFlix,
+    Goku,
+    HDToday,
+    MyFlixer,
+    DramaCool,
+    ViewAsian,
+}
+
+/// Where a [`MappingCandidate`] comes from
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum MappingSource {
+    /// set with [`IdMapper::set_override`]
+    Override,
+    /// confirmed with [`IdMapper::confirm`] or scored above [`AUTO_CONFIRM_CONFIDENCE`]
+    Confirmed,
+    Search,
+}
+
+/// Contains a provider media id which may belong to a metadata id
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MappingCandidate {
+    pub provider: MappingProvider,
+    pub id: String,
+    /// the title as listed by the provider, empty for overrides and confirmed mappings
+    pub title: String,
+    pub release_date: Option<String>,
+    /// from 0 to 1
+    pub confidence: f32,
+    pub source: MappingSource,
+}
+
+/// Contains a manual mapping, as saved by [`IdMapper::save_overrides`]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MappingOverride {
+    pub meta_id: MetaId,
+    pub provider: MappingProvider,
+    pub media_id: String,
+}
+
+/// Contains what the metadata sites know about an entry, the input of the title matching
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct MetaTitles {
+    /// most relevant first, e.g. romaji, english, native then synonyms
+    pub titles: Vec<String>,
+    pub year: Option<u32>,
+    pub media_type: Option<TvType>,
+    /// the ids of the same entry on the other metadata sites, where known
+    pub ids: Vec<MetaId>,
+}
+
+/// A provider search result, before scoring
+struct Listing {
+    id: String,
+    title: String,
+    release_date: Option<String>,
+    media_type: Option<TvType>,
+}
+
+type MappingKey = (MetaId, MappingProvider);
+
+/// Resolves metadata ids (Anilist, MAL, Kitsu, TMDB) to the media ids of streaming providers.
+///
+/// Candidates come from searching the provider for the titles of the entry and are ranked by
+/// confidence. Confirmed mappings are cached, and manual overrides win over everything else
+/// and can be saved to and loaded from a JSON file.
+/// ```no_run
+/// use consumet::providers::meta::{IdMapper, MappingProvider, MetaId};
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let mapper = IdMapper::new();
+///     mapper.load_overrides("overrides.json")?;
+///
+///     let frieren = MetaId::Anilist(154587);
+///     let candidates = mapper.resolve(&frieren, MappingProvider::FlixHQ).await?;
+///
+///     if let Some(best) = candidates.first() {
+///         println!("{} ({:.2})", best.id, best.confidence);
+///         mapper.confirm(&frieren, MappingProvider::FlixHQ, &best.id);
+///     }
+///
+///     mapper.set_override(
+///         MetaId::Anilist(21),
+///         MappingProvider::FlixHQ,
+///         "tv/watch-one-piece-39514",
+///     );
+///     mapper.save_overrides("overrides.json")?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Default)]
+pub struct IdMapper {
+    tmdb: Option<Tmdb>,
+    mal: MyAnimeList,
+    overrides: RwLock<HashMap<MappingKey, String>>,
+    confirmed: RwLock<HashMap<MappingKey, String>>,
+}
+
+impl MatchCandidate for Listing {
+    fn titles(&self) -> Vec<&str> {
+        vec![&self.title]
+    }
+
+    fn year(&self) -> Option<u32> {
+        self.release_date.as_deref().and_then(release_year)
+    }
+
+    fn media_type(&self) -> Option<TvType> {
+        self.media_type
+    }
+}
+
+impl From<&MetaTitles> for MatchQuery {
+    fn from(meta: &MetaTitles) -> Self {
+        Self {
+            titles: meta.titles.clone(),
+            year: meta.year,
+            media_type: meta.media_type,
+        }
+    }
+}
+
+impl IdMapper {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Needed to resolve [`MetaId::Tmdb`] ids
+    pub fn with_tmdb(mut self, tmdb: Tmdb) -> Self {
+        self.tmdb = Some(tmdb);
+        self
+    }
+
+    /// Fetches MAL ids through another Jikan compatible server
+    pub fn with_mal(mut self, mal: MyAnimeList) -> Self {
+        self.mal = mal;
+        self
+    }
+
+    /// Maps `meta_id` onto `media_id` until the override is removed, whatever the searches find
+    pub fn set_override(&self, meta_id: MetaId, provider: MappingProvider, media_id: &str) {
+        self.overrides
+            .write()
+            .unwrap()
+            .insert((meta_id, provider), media_id.to_owned());
+    }
+
+    pub fn remove_override(&self, meta_id: &MetaId, provider: MappingProvider) {
+        self.overrides
+            .write()
+            .unwrap()
+            .remove(&(meta_id.clone(), provider));
+    }
+
+    pub fn overrides(&self) -> Vec<MappingOverride> {
+        self.overrides
+            .read()
+            .unwrap()
+            .iter()
+            .map(|((meta_id, provider), media_id)| MappingOverride {
+                meta_id: meta_id.clone(),
+                provider: *provider,
+                media_id: media_id.clone(),
+            })
+            .collect()
+    }
+
+    /// Writes the overrides to a JSON file
+    /// ```
+    /// use consumet::providers::meta::{IdMapper, MappingProvider, MappingSource, MetaId};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let path = std::env::temp_dir().join("consumet-overrides.json");
+    ///
+    ///     let mapper = IdMapper::new();
+    ///     mapper.set_override(MetaId::Mal(21), MappingProvider::FlixHQ, "tv/watch-one-piece-39514");
+    ///     mapper.save_overrides(&path)?;
+    ///
+    ///     let restored = IdMapper::new();
+    ///     restored.load_overrides(&path)?;
+    ///
+    ///     // overrides are answered without searching
+    ///     let candidates = restored.resolve(&MetaId::Mal(21), MappingProvider::FlixHQ).await?;
+    ///     assert_eq!(candidates[0].id, "tv/watch-one-piece-39514");
+    ///     assert_eq!(candidates[0].source, MappingSource::Override);
+    ///
+    ///     std::fs::remove_file(path)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn save_overrides(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let overrides = serde_json::to_string_pretty(&self.overrides())?;
+
+        std::fs::write(path, overrides)?;
+
+        Ok(())
+    }
+
+    /// Adds the overrides of a JSON file written by [`IdMapper::save_overrides`], a missing file is ignored
+    pub fn load_overrides(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let json = match std::fs::read_to_string(path) {
+            Ok(json) => json,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err.into()),
+        };
+
+        let overrides: Vec<MappingOverride> = serde_json::from_str(&json)?;
+
+        let mut map = self.overrides.write().unwrap();
+        for mapping in overrides {
+            map.insert((mapping.meta_id, mapping.provider), mapping.media_id);
+        }
+
+        Ok(())
+    }
+
+    /// Caches `media_id` as the mapping of `meta_id`, later calls to [`IdMapper::resolve`] skip the search
+    pub fn confirm(&self, meta_id: &MetaId, provider: MappingProvider, media_id: &str) {
+        self.confirmed
+            .write()
+            .unwrap()
+            .insert((meta_id.clone(), provider), media_id.to_owned());
+    }
+
+    /// Forgets a confirmed mapping, e.g. after the provider changed its ids
+    pub fn forget(&self, meta_id: &MetaId, provider: MappingProvider) {
+        self.confirmed
+            .write()
+            .unwrap()
+            .remove(&(meta_id.clone(), provider));
+    }
+
+    /// Returns a future which resolves into the titles, year and type of a metadata entry. (*[`impl Future<Output = Result<MetaTitles>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/mapping.rs)*)\
+    /// # Parameters
+    /// * `meta_id` - see [`MetaId`]
+    pub async fn titles(&self, meta_id: &MetaId) -> anyhow::Result<MetaTitles> {
+        match meta_id {
+            MetaId::Anilist(id) => Anilist.titles(*id).await,
+            MetaId::Mal(id) => self.mal.titles(*id).await,
+            MetaId::Kitsu(id) => Kitsu.titles(id).await,
+            MetaId::Tmdb(id, media_type) => {
+                let Some(tmdb) = &self.tmdb else {
+                    bail!("TMDB ids need IdMapper::with_tmdb");
+                };
+
+                let info = tmdb.info(*id, *media_type).await?;
+
+                Ok(MetaTitles {
+                    titles: vec![info.title],
+                    year: info.release_date.as_deref().and_then(release_year),
+                    media_type: Some(*media_type),
+                    ids: vec![],
+                })
+            }
+        }
+    }
+
+    /// Returns a future which resolves into the candidate media ids of `provider`, most likely first. (*[`impl Future<Output = Result<Vec<MappingCandidate>>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/mapping.rs)*)\
+    /// An override or a confirmed mapping is returned alone, with a confidence of 1.
+    /// # Parameters
+    /// * `meta_id` - see [`MetaId`]
+    /// * `provider` - see [`MappingProvider`]
+    pub async fn resolve(
+        &self,
+        meta_id: &MetaId,
+        provider: MappingProvider,
+    ) -> anyhow::Result<Vec<MappingCandidate>> {
+        let key = (meta_id.clone(), provider);
+
+        let known = [
+            (&self.overrides, MappingSource::Override),
+            (&self.confirmed, MappingSource::Confirmed),
+        ];
+
+        for (mappings, source) in known {
+            if let Some(id) = mappings.read().unwrap().get(&key) {
+                return Ok(vec![MappingCandidate {
+                    provider,
+                    id: id.clone(),
+                    title: String::new(),
+                    release_date: None,
+                    confidence: 1.0,
+                    source,
+                }]);
+            }
+        }
+
+        let meta = self.titles(meta_id).await?;
+
+        let mut listings: Vec<Listing> = vec![];
+        let mut errors = vec![];
+
+        let mut searched: Vec<String> = vec![];
+        for title in &meta.titles {
+            if searched.len() == SEARCHED_TITLES {
+                break;
+            }
+            if searched
+                .iter()
+                .any(|other| normalize_title(other) == normalize_title(title))
+            {
+                continue;
+            }
+            searched.push(title.clone());
+
+            match search(provider, title).await {
+                Ok(found) => {
+                    for listing in found {
+                        if !listings.iter().any(|other| other.id == listing.id) {
+                            listings.push(listing);
+                        }
+                    }
+                }
+                Err(err) => errors.push(err.to_string()),
+            }
+        }
+
+        if listings.is_empty() && !errors.is_empty() {
+            bail!("Could not search {:?} ({})", provider, errors.join(", "));
+        }
+
+        let query = MatchQuery::from(&meta);
+
+        let candidates: Vec<MappingCandidate> = query
+            .rank(&listings)
+            .into_iter()
+            .filter(|ranked| ranked.score > 0.0)
+            .map(|ranked| MappingCandidate {
+                provider,
+                id: ranked.candidate.id.clone(),
+                title: ranked.candidate.title.clone(),
+                release_date: ranked.candidate.release_date.clone(),
+                confidence: ranked.score,
+                source: MappingSource::Search,
+            })
+            .collect();
+
+        if let Some(best) = candidates.first() {
+            if best.confidence >= AUTO_CONFIRM_CONFIDENCE {
+                self.confirm(meta_id, provider, &best.id);
+            }
+        }
+
+        Ok(candidates)
+    }
+}
+
+fn drama_listings(results: DramaCoolSearchResults<DramaCoolListing>) -> Vec<Listing> {
+    results
+        .results
+        .into_iter()
+        .map(|listing| Listing {
+            id: listing.id,
+            title: listing.title,
+            release_date: listing.release_date,
+            media_type: None,
+        })
+        .collect()
+}
+
+async fn search(provider: MappingProvider, title: &str) -> anyhow::Result<Vec<Listing>> {
+    let flixhq = match provider {
+        MappingProvider::FlixHQ => movies::FlixHQ,
+        MappingProvider::SFlix => movies::SFlix,
+        MappingProvider::Goku => movies::Goku,
+        MappingProvider::HDToday => movies::HDToday,
+        MappingProvider::MyFlixer => movies::MyFlixer,
+        MappingProvider::DramaCool => {
+            return Ok(drama_listings(DramaCool.quick_search(title, None).await?))
+        }
+        MappingProvider::ViewAsian => {
+            return Ok(drama_listings(ViewAsian.quick_search(title, None).await?))
+        }
+    };
+
+    let results = flixhq.quick_search(title, None).await?;
+
+    Ok(results
+        .results
+        .into_iter()
+        .map(|listing| Listing {
+            id: listing.id,
+            title: listing.title,
+            release_date: listing.release_date,
+            media_type: Some(listing.media_type),
+        })
+        .collect())
+}
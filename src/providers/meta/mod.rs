@@ -1 +1,11 @@
+pub mod anilist;
+pub mod kitsu;
+pub mod mapping;
+pub mod myanimelist;
+pub mod tmdb;
 
+pub use anilist::*;
+pub use kitsu::*;
+pub use mapping::*;
+pub use myanimelist::*;
+pub use tmdb::*;
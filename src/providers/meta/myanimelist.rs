@@ -0,0 +1,647 @@
+use crate::{
+    models::TvType,
+    models::{
+        FuzzyDate, IAnimeEpisode, IAnimeInfo, IAnimeResult, IMangaInfo, IMangaResult, ISearch,
+        ITitle, MediaFormat, MediaStatus, Trailer,
+    },
+    providers::meta::{
+        anilist::AnilistSeason,
+        mapping::{MetaId, MetaTitles},
+    },
+    CLIENT,
+};
+
+use serde::{de::DeserializeOwned, Deserialize};
+
+pub(crate) const BASE_URL: &str = "https://api.jikan.moe/v4";
+
+/// Metadata from MyAnimeList, through any server speaking the [Jikan](https://jikan.moe) v4 REST format
+/// ```no_run
+/// use consumet::providers::meta::{AnilistSeason, MyAnimeList};
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     // e.g. a self hosted Jikan instance, the public api is used by default
+///     let mal = MyAnimeList::new().with_base_url("http://localhost:8080/v4");
+///
+///     let results = mal.search("one piece", None).await?;
+///     let id = &results.results[0].id;
+///
+///     let info = mal.info(id).await?;
+///     let fillers: Vec<_> = mal
+///         .episodes(id, None)
+///         .await?
+///         .results
+///         .into_iter()
+///         .filter(|episode| episode.is_filler == Some(true))
+///         .collect();
+///
+///     let fall = mal.seasonal(2023, AnilistSeason::Fall, None).await?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct MyAnimeList {
+    base_url: String,
+}
+
+impl Default for MyAnimeList {
+    fn default() -> Self {
+        Self {
+            base_url: BASE_URL.to_owned(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RawPage<T> {
+    data: Vec<T>,
+    pagination: Option<RawPagination>,
+}
+
+#[derive(Deserialize)]
+struct RawData<T> {
+    data: T,
+}
+
+#[derive(Deserialize)]
+struct RawPagination {
+    last_visible_page: Option<usize>,
+    #[serde(default)]
+    has_next_page: bool,
+    current_page: Option<usize>,
+    items: Option<RawItems>,
+}
+
+#[derive(Deserialize)]
+struct RawItems {
+    total: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct RawAnime {
+    mal_id: u32,
+    url: Option<String>,
+    images: Option<RawImages>,
+    trailer: Option<RawTrailer>,
+    title: Option<String>,
+    title_english: Option<String>,
+    title_japanese: Option<String>,
+    #[serde(default)]
+    title_synonyms: Vec<String>,
+    #[serde(rename = "type")]
+    media_type: Option<String>,
+    episodes: Option<u32>,
+    status: Option<String>,
+    aired: Option<RawDates>,
+    rating: Option<String>,
+    score: Option<f32>,
+    synopsis: Option<String>,
+    season: Option<String>,
+    year: Option<u32>,
+    #[serde(default)]
+    studios: Vec<RawNamed>,
+    #[serde(default)]
+    genres: Vec<RawNamed>,
+    #[serde(default)]
+    relations: Vec<RawRelation>,
+}
+
+#[derive(Deserialize)]
+struct RawManga {
+    mal_id: u32,
+    url: Option<String>,
+    images: Option<RawImages>,
+    title: Option<String>,
+    title_english: Option<String>,
+    title_japanese: Option<String>,
+    #[serde(default)]
+    title_synonyms: Vec<String>,
+    status: Option<String>,
+    published: Option<RawDates>,
+    synopsis: Option<String>,
+    #[serde(default)]
+    authors: Vec<RawNamed>,
+    #[serde(default)]
+    genres: Vec<RawNamed>,
+}
+
+#[derive(Deserialize)]
+struct RawImages {
+    jpg: Option<RawImage>,
+}
+
+#[derive(Deserialize)]
+struct RawImage {
+    image_url: Option<String>,
+    large_image_url: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawTrailer {
+    youtube_id: Option<String>,
+    images: Option<RawTrailerImages>,
+}
+
+#[derive(Deserialize)]
+struct RawTrailerImages {
+    maximum_image_url: Option<String>,
+    image_url: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawDates {
+    prop: Option<RawDateProp>,
+}
+
+#[derive(Deserialize)]
+struct RawDateProp {
+    from: Option<FuzzyDate>,
+    to: Option<FuzzyDate>,
+}
+
+#[derive(Deserialize)]
+struct RawNamed {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct RawRelation {
+    /// e.g. "Sequel" or "Side story"
+    relation: Option<String>,
+    #[serde(default)]
+    entry: Vec<RawEntry>,
+}
+
+#[derive(Deserialize)]
+struct RawEntry {
+    mal_id: u32,
+    #[serde(rename = "type")]
+    entry_type: Option<String>,
+    #[serde(alias = "title")]
+    name: Option<String>,
+    url: Option<String>,
+    images: Option<RawImages>,
+}
+
+#[derive(Deserialize)]
+struct RawRecommendation {
+    entry: RawEntry,
+}
+
+#[derive(Deserialize)]
+struct RawEpisode {
+    mal_id: u32,
+    url: Option<String>,
+    title: Option<String>,
+    aired: Option<String>,
+    #[serde(default)]
+    filler: bool,
+}
+
+/// Maps a MAL airing or publishing status
+fn media_status(status: &str) -> MediaStatus {
+    match status {
+        "Currently Airing" | "Publishing" => MediaStatus::OnGoing,
+        "Finished Airing" | "Finished" => MediaStatus::Completed,
+        "Not yet aired" | "Not yet published" => MediaStatus::NotYetAired,
+        "On Hiatus" => MediaStatus::Hiatus,
+        "Discontinued" => MediaStatus::Cancelled,
+        _ => MediaStatus::Unknown,
+    }
+}
+
+/// Maps a MAL media type
+fn media_format(media_type: &str) -> Option<MediaFormat> {
+    match media_type {
+        "TV" => Some(MediaFormat::Tv),
+        "Movie" => Some(MediaFormat::Movie),
+        "Special" | "TV Special" => Some(MediaFormat::Special),
+        "OVA" => Some(MediaFormat::Ova),
+        "ONA" => Some(MediaFormat::Ona),
+        "Music" => Some(MediaFormat::Music),
+        "Manga" | "Manhwa" | "Manhua" | "Doujinshi" => Some(MediaFormat::Manga),
+        "Light Novel" | "Novel" => Some(MediaFormat::Novel),
+        "One-shot" => Some(MediaFormat::OneShot),
+        _ => None,
+    }
+}
+
+fn image(images: Option<RawImages>) -> Option<String> {
+    images
+        .and_then(|images| images.jpg)
+        .and_then(|jpg| jpg.large_image_url.or(jpg.image_url))
+}
+
+fn search_page<T>(results: Vec<T>, pagination: Option<RawPagination>) -> ISearch<T> {
+    let Some(pagination) = pagination else {
+        return ISearch {
+            current_page: Some(1),
+            has_next_page: false,
+            total_pages: Some(1),
+            total_results: results.len(),
+            results,
+        };
+    };
+
+    ISearch {
+        current_page: pagination.current_page,
+        has_next_page: pagination.has_next_page,
+        total_pages: pagination.last_visible_page,
+        total_results: pagination
+            .items
+            .and_then(|items| items.total)
+            .unwrap_or(results.len()),
+        results,
+    }
+}
+
+impl From<RawAnime> for IAnimeResult {
+    fn from(anime: RawAnime) -> Self {
+        Self {
+            id: anime.mal_id.to_string(),
+            title: ITitle {
+                romaji: anime.title.clone(),
+                english: anime.title_english,
+                native: anime.title_japanese,
+                user_preferred: anime.title,
+            },
+            url: anime.url,
+            image: image(anime.images),
+            cover: None,
+            status: anime.status.as_deref().map(media_status),
+            // MAL scores are out of 10, Anilist's out of 100
+            rating: anime.score.map(|score| score * 10.0),
+            show_type: anime.media_type.as_deref().and_then(media_format),
+            release_date: anime
+                .year
+                .or(anime
+                    .aired
+                    .and_then(|aired| aired.prop)
+                    .and_then(|prop| prop.from)
+                    .and_then(|from| from.year))
+                .map(|year| year.to_string()),
+            relation_type: None,
+        }
+    }
+}
+
+impl From<RawEntry> for IAnimeResult {
+    fn from(entry: RawEntry) -> Self {
+        Self {
+            id: entry.mal_id.to_string(),
+            title: ITitle {
+                romaji: entry.name.clone(),
+                english: None,
+                native: None,
+                user_preferred: entry.name,
+            },
+            url: entry.url,
+            image: image(entry.images),
+            cover: None,
+            status: None,
+            rating: None,
+            show_type: None,
+            release_date: None,
+            relation_type: None,
+        }
+    }
+}
+
+impl From<RawManga> for IMangaResult {
+    fn from(manga: RawManga) -> Self {
+        let mut alt_titles = manga.title_synonyms;
+        alt_titles.extend(manga.title_english);
+        alt_titles.extend(manga.title_japanese);
+
+        Self {
+            id: manga.mal_id.to_string(),
+            title: manga.title.unwrap_or_default(),
+            alt_titles: Some(alt_titles),
+            image: image(manga.images),
+            description: manga.synopsis,
+            status: manga.status.as_deref().map(media_status),
+            release_date: manga
+                .published
+                .and_then(|published| published.prop)
+                .and_then(|prop| prop.from)
+                .and_then(|from| from.year),
+        }
+    }
+}
+
+impl MyAnimeList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sends the requests to another Jikan compatible server, e.g. a self hosted one or a local stub
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_owned();
+        self
+    }
+
+    async fn get<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+    ) -> anyhow::Result<T> {
+        Ok(CLIENT
+            .get(format!("{}{}", self.base_url, path))
+            .query(query)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<T>()
+            .await?)
+    }
+
+    /// Returns a future which resolves into ISearch\<IAnimeResult\>. (*[`impl Future<Output = Result<ISearch<IAnimeResult>>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/myanimelist.rs)*)\
+    /// # Parameters
+    /// * `query` - query to search for.
+    /// * `page (optional)` - page number (default: 1)
+    pub async fn search(
+        &self,
+        query: &str,
+        page: Option<usize>,
+    ) -> anyhow::Result<ISearch<IAnimeResult>> {
+        let page: RawPage<RawAnime> = self
+            .get(
+                "/anime",
+                &[
+                    ("q", query.to_owned()),
+                    ("page", page.unwrap_or(1).to_string()),
+                ],
+            )
+            .await?;
+
+        Ok(search_page(
+            page.data.into_iter().map(Into::into).collect(),
+            page.pagination,
+        ))
+    }
+
+    /// Returns a future which resolves into ISearch\<IMangaResult\>. (*[`impl Future<Output = Result<ISearch<IMangaResult>>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/myanimelist.rs)*)\
+    /// # Parameters
+    /// * `query` - query to search for.
+    /// * `page (optional)` - page number (default: 1)
+    pub async fn search_manga(
+        &self,
+        query: &str,
+        page: Option<usize>,
+    ) -> anyhow::Result<ISearch<IMangaResult>> {
+        let page: RawPage<RawManga> = self
+            .get(
+                "/manga",
+                &[
+                    ("q", query.to_owned()),
+                    ("page", page.unwrap_or(1).to_string()),
+                ],
+            )
+            .await?;
+
+        Ok(search_page(
+            page.data.into_iter().map(Into::into).collect(),
+            page.pagination,
+        ))
+    }
+
+    /// Titles, year and type of an entry, the input of [`IdMapper`](crate::providers::meta::IdMapper)
+    pub(crate) async fn titles(&self, id: u32) -> anyhow::Result<MetaTitles> {
+        let anime: RawData<RawAnime> = self.get(&format!("/anime/{}", id), &[]).await?;
+        let anime = anime.data;
+
+        let mut titles: Vec<String> = [anime.title, anime.title_english, anime.title_japanese]
+            .into_iter()
+            .flatten()
+            .collect();
+        titles.extend(anime.title_synonyms);
+
+        Ok(MetaTitles {
+            titles,
+            year: anime.year.or(anime
+                .aired
+                .and_then(|aired| aired.prop)
+                .and_then(|prop| prop.from)
+                .and_then(|from| from.year)),
+            media_type: anime
+                .media_type
+                .map(|media_type| match media_type.as_str() {
+                    "Movie" => TvType::Movie,
+                    _ => TvType::TvSeries,
+                }),
+            ids: vec![MetaId::Mal(anime.mal_id)],
+        })
+    }
+
+    /// Returns a future which resolves into IAnimeInfo, without episodes (see [`MyAnimeList::episodes`]). (*[`impl Future<Output = Result<IAnimeInfo>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/myanimelist.rs)*)\
+    /// # Parameters
+    /// * `id` - the MAL id
+    pub async fn info(&self, id: &str) -> anyhow::Result<IAnimeInfo> {
+        let anime: RawData<RawAnime> = self.get(&format!("/anime/{}/full", id), &[]).await?;
+        let anime = anime.data;
+
+        let (start_date, end_date) = match anime.aired.and_then(|aired| aired.prop) {
+            Some(prop) => (prop.from, prop.to),
+            None => (None, None),
+        };
+
+        Ok(IAnimeInfo {
+            id: anime.mal_id.to_string(),
+            title: ITitle {
+                romaji: anime.title.clone(),
+                english: anime.title_english,
+                native: anime.title_japanese,
+                user_preferred: anime.title,
+            },
+            url: anime.url,
+            image: image(anime.images),
+            mal_id: Some(anime.mal_id),
+            genres: Some(anime.genres.into_iter().map(|genre| genre.name).collect()),
+            description: anime.synopsis,
+            status: anime.status.as_deref().map(media_status),
+            total_episodes: anime.episodes,
+            sub_or_dub: None,
+            synonyms: Some(anime.title_synonyms),
+            country_of_origin: None,
+            is_adult: anime.rating.map(|rating| rating.starts_with("Rx")),
+            is_licensed: None,
+            season: anime.season.map(|season| season.to_uppercase()),
+            studios: Some(
+                anime
+                    .studios
+                    .into_iter()
+                    .map(|studio| studio.name)
+                    .collect(),
+            ),
+            color: None,
+            cover: None,
+            trailer: anime.trailer.and_then(|trailer| {
+                Some(Trailer {
+                    id: trailer.youtube_id?,
+                    site: Some(String::from("youtube")),
+                    thumbnail: trailer
+                        .images
+                        .and_then(|images| images.maximum_image_url.or(images.image_url)),
+                })
+            }),
+            episodes: None,
+            start_date,
+            end_date,
+            recommendations: None,
+            relations: Some(
+                anime
+                    .relations
+                    .into_iter()
+                    .flat_map(|relation| {
+                        // Anilist style, e.g. "SIDE_STORY"
+                        let relation_type = relation
+                            .relation
+                            .map(|relation| relation.to_uppercase().replace(' ', "_"));
+
+                        relation
+                            .entry
+                            .into_iter()
+                            .filter(|entry| entry.entry_type.as_deref() == Some("anime"))
+                            .map(move |entry| IAnimeResult {
+                                relation_type: relation_type.clone(),
+                                ..entry.into()
+                            })
+                    })
+                    .collect(),
+            ),
+            characters: None,
+            next_airing_episode: None,
+        })
+    }
+
+    /// Returns a future which resolves into IMangaInfo. (*[`impl Future<Output = Result<IMangaInfo>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/myanimelist.rs)*)\
+    /// # Parameters
+    /// * `id` - the MAL id
+    pub async fn manga_info(&self, id: &str) -> anyhow::Result<IMangaInfo> {
+        let manga: RawData<RawManga> = self.get(&format!("/manga/{}/full", id), &[]).await?;
+        let mut manga = manga.data;
+
+        let mal_id = manga.mal_id;
+        let authors = std::mem::take(&mut manga.authors);
+        let genres = std::mem::take(&mut manga.genres);
+        let links = manga.url.clone().map(|url| vec![url]);
+
+        let manga: IMangaResult = manga.into();
+
+        Ok(IMangaInfo {
+            id: manga.id,
+            title: manga.title,
+            alt_titles: manga.alt_titles,
+            image: manga.image,
+            description: manga.description,
+            status: manga.status,
+            release_date: manga.release_date,
+            mal_id: Some(mal_id),
+            authors: Some(authors.into_iter().map(|author| author.name).collect()),
+            genres: Some(genres.into_iter().map(|genre| genre.name).collect()),
+            links,
+            characters: None,
+            recommendations: None,
+            chapters: None,
+        })
+    }
+
+    /// Returns a future which resolves into a page of ISearch\<IAnimeEpisode\>, with filler flags. (*[`impl Future<Output = Result<ISearch<IAnimeEpisode>>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/myanimelist.rs)*)\
+    /// # Parameters
+    /// * `id` - the MAL id of the anime
+    /// * `page (optional)` - page number (default: 1), 100 episodes per page
+    pub async fn episodes(
+        &self,
+        id: &str,
+        page: Option<usize>,
+    ) -> anyhow::Result<ISearch<IAnimeEpisode>> {
+        let page: RawPage<RawEpisode> = self
+            .get(
+                &format!("/anime/{}/episodes", id),
+                &[("page", page.unwrap_or(1).to_string())],
+            )
+            .await?;
+
+        let episodes = page
+            .data
+            .into_iter()
+            .map(|episode| IAnimeEpisode {
+                // Jikan numbers episodes through `mal_id`
+                id: format!("{}-{}", id, episode.mal_id),
+                number: episode.mal_id,
+                title: episode.title,
+                description: None,
+                is_filler: Some(episode.filler),
+                url: episode.url,
+                image: None,
+                release_date: episode.aired,
+            })
+            .collect();
+
+        Ok(search_page(episodes, page.pagination))
+    }
+
+    /// Returns a future which resolves into the anime MAL users recommend alongside `id`, most voted first. (*[`impl Future<Output = Result<Vec<IAnimeResult>>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/myanimelist.rs)*)\
+    /// # Parameters
+    /// * `id` - the MAL id of the anime
+    pub async fn recommendations(&self, id: &str) -> anyhow::Result<Vec<IAnimeResult>> {
+        let page: RawPage<RawRecommendation> = self
+            .get(&format!("/anime/{}/recommendations", id), &[])
+            .await?;
+
+        Ok(page
+            .data
+            .into_iter()
+            .map(|recommendation| recommendation.entry.into())
+            .collect())
+    }
+
+    /// Returns a future which resolves into ISearch\<IAnimeResult\>, the anime of a season. (*[`impl Future<Output = Result<ISearch<IAnimeResult>>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/myanimelist.rs)*)\
+    /// # Parameters
+    /// * `year` - e.g. 2023
+    /// * `season` - see [`AnilistSeason`]
+    /// * `page (optional)` - page number (default: 1)
+    pub async fn seasonal(
+        &self,
+        year: u32,
+        season: AnilistSeason,
+        page: Option<usize>,
+    ) -> anyhow::Result<ISearch<IAnimeResult>> {
+        let season = match season {
+            AnilistSeason::Winter => "winter",
+            AnilistSeason::Spring => "spring",
+            AnilistSeason::Summer => "summer",
+            AnilistSeason::Fall => "fall",
+        };
+
+        self.season_page(&format!("/seasons/{}/{}", year, season), page)
+            .await
+    }
+
+    /// Returns a future which resolves into ISearch\<IAnimeResult\>, the anime of the season currently airing. (*[`impl Future<Output = Result<ISearch<IAnimeResult>>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/myanimelist.rs)*)\
+    /// # Parameters
+    /// * `page (optional)` - page number (default: 1)
+    pub async fn current_season(
+        &self,
+        page: Option<usize>,
+    ) -> anyhow::Result<ISearch<IAnimeResult>> {
+        self.season_page("/seasons/now", page).await
+    }
+
+    async fn season_page(
+        &self,
+        path: &str,
+        page: Option<usize>,
+    ) -> anyhow::Result<ISearch<IAnimeResult>> {
+        let page: RawPage<RawAnime> = self
+            .get(path, &[("page", page.unwrap_or(1).to_string())])
+            .await?;
+
+        Ok(search_page(
+            page.data.into_iter().map(Into::into).collect(),
+            page.pagination,
+        ))
+    }
+}
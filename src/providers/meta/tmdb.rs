@@ -0,0 +1,488 @@
+use crate::{
+    models::TvType,
+    providers::movies::flixhq::{FlixHQ, FlixHQInfo, FlixHQListing, SeasonNotFound},
+    utils::matching::{best_match, release_year, MatchCandidate, MatchQuery, MIN_MATCH_SCORE},
+    CLIENT,
+};
+
+use anyhow::bail;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+pub(crate) const BASE_URL: &str = "https://api.themoviedb.org/3";
+pub(crate) const IMAGE_URL: &str = "https://image.tmdb.org/t/p/original";
+
+/// Metadata from TMDB, mapped onto the media and episodes of a FlixHQ site
+/// ```no_run
+/// use consumet::{models::TvType, providers::meta::Tmdb};
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let tmdb = Tmdb::new("api key").with_base_url("http://localhost:8080/3");
+///
+///     let info = tmdb.info(93405, TvType::TvSeries).await?;
+///     let flixhq_id = tmdb.flixhq_id(93405, TvType::TvSeries).await?;
+///     let episode_id = tmdb.flixhq_episode_id(93405, 1, 3).await?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Tmdb {
+    api_key: String,
+    base_url: String,
+    provider: FlixHQ,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TmdbSearchResults {
+    pub current_page: usize,
+    pub has_next_page: bool,
+    pub total_pages: usize,
+    pub total_results: usize,
+    pub results: Vec<TmdbResult>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TmdbResult {
+    pub id: u32,
+    pub title: String,
+    pub media_type: TvType,
+    /// `YYYY-MM-DD`, the first air date for tv shows
+    pub release_date: Option<String>,
+    pub image: Option<String>,
+    pub cover: Option<String>,
+    pub description: String,
+    pub rating: f32,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TmdbInfo {
+    pub id: u32,
+    pub imdb_id: Option<String>,
+    pub title: String,
+    /// the title in the original language, when it differs
+    pub original_title: Option<String>,
+    pub media_type: TvType,
+    pub release_date: Option<String>,
+    pub status: String,
+    pub description: String,
+    pub genres: Vec<String>,
+    pub rating: f32,
+    /// the runtime of the movie or of an episode, in minutes
+    pub duration: Option<u32>,
+    pub image: Option<String>,
+    pub cover: Option<String>,
+    /// empty for movies, the episodes are loaded with [`Tmdb::season`]
+    pub seasons: Vec<TmdbSeasonSummary>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TmdbSeasonSummary {
+    pub id: u32,
+    pub number: u32,
+    pub title: String,
+    pub total_episodes: u32,
+    pub air_date: Option<String>,
+    pub image: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TmdbSeason {
+    pub id: u32,
+    pub number: u32,
+    pub title: String,
+    pub description: String,
+    pub air_date: Option<String>,
+    pub image: Option<String>,
+    pub episodes: Vec<TmdbEpisode>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TmdbEpisode {
+    pub id: u32,
+    pub number: u32,
+    pub season: u32,
+    pub title: String,
+    pub description: String,
+    pub air_date: Option<String>,
+    pub still: Option<String>,
+    /// in minutes
+    pub duration: Option<u32>,
+    pub rating: f32,
+}
+
+#[derive(Deserialize)]
+struct RawPage {
+    page: usize,
+    total_pages: usize,
+    total_results: usize,
+    results: Vec<RawResult>,
+}
+
+#[derive(Deserialize)]
+struct RawResult {
+    id: u32,
+    media_type: Option<String>,
+    title: Option<String>,
+    name: Option<String>,
+    release_date: Option<String>,
+    first_air_date: Option<String>,
+    poster_path: Option<String>,
+    backdrop_path: Option<String>,
+    #[serde(default)]
+    overview: String,
+    #[serde(default)]
+    vote_average: f32,
+}
+
+#[derive(Deserialize)]
+struct RawInfo {
+    id: u32,
+    title: Option<String>,
+    name: Option<String>,
+    original_title: Option<String>,
+    original_name: Option<String>,
+    release_date: Option<String>,
+    first_air_date: Option<String>,
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    overview: String,
+    #[serde(default)]
+    genres: Vec<RawGenre>,
+    #[serde(default)]
+    vote_average: f32,
+    runtime: Option<u32>,
+    #[serde(default)]
+    episode_run_time: Vec<u32>,
+    poster_path: Option<String>,
+    backdrop_path: Option<String>,
+    imdb_id: Option<String>,
+    external_ids: Option<RawExternalIds>,
+    #[serde(default)]
+    seasons: Vec<RawSeason>,
+}
+
+#[derive(Deserialize)]
+struct RawGenre {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct RawExternalIds {
+    imdb_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawSeason {
+    id: u32,
+    season_number: u32,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    overview: String,
+    #[serde(default)]
+    episode_count: u32,
+    air_date: Option<String>,
+    poster_path: Option<String>,
+    #[serde(default)]
+    episodes: Vec<RawEpisode>,
+}
+
+#[derive(Deserialize)]
+struct RawEpisode {
+    id: u32,
+    episode_number: u32,
+    season_number: u32,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    overview: String,
+    air_date: Option<String>,
+    still_path: Option<String>,
+    runtime: Option<u32>,
+    #[serde(default)]
+    vote_average: f32,
+}
+
+fn image(path: Option<String>) -> Option<String> {
+    path.map(|path| format!("{}{}", IMAGE_URL, path))
+}
+
+fn media_path(media_type: TvType) -> anyhow::Result<&'static str> {
+    match media_type {
+        TvType::Movie => Ok("movie"),
+        TvType::TvSeries => Ok("tv"),
+        TvType::Anime => bail!("TMDB lists anime as tv shows, use TvType::TvSeries"),
+    }
+}
+
+impl MatchCandidate for FlixHQListing {
+    fn titles(&self) -> Vec<&str> {
+        vec![&self.title]
+    }
+
+    fn year(&self) -> Option<u32> {
+        self.release_date.as_deref().and_then(release_year)
+    }
+
+    fn media_type(&self) -> Option<TvType> {
+        Some(self.media_type)
+    }
+}
+
+fn year(date: &str) -> Option<&str> {
+    date.get(..4)
+        .filter(|year| year.chars().all(|c| c.is_ascii_digit()))
+}
+
+impl Tmdb {
+    /// Maps onto [`FlixHQ`](const@crate::providers::movies::FlixHQ) by default
+    pub fn new(api_key: &str) -> Self {
+        Self {
+            api_key: api_key.to_owned(),
+            base_url: BASE_URL.to_owned(),
+            provider: crate::providers::movies::FlixHQ,
+        }
+    }
+
+    /// Sends the API requests to another server, e.g. a local stub
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_owned();
+        self
+    }
+
+    /// Maps onto another FlixHQ site, e.g. [`SFlix`](crate::providers::movies::SFlix)
+    pub fn with_provider(mut self, provider: FlixHQ) -> Self {
+        self.provider = provider;
+        self
+    }
+
+    async fn get<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> anyhow::Result<T> {
+        Ok(CLIENT
+            .get(format!("{}{}", self.base_url, path))
+            .query(&[("api_key", self.api_key.as_str())])
+            .query(query)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<T>()
+            .await?)
+    }
+
+    /// Returns a future which resolves into TmdbSearchResults, people are left out. (*[`impl Future<Output = Result<TmdbSearchResults>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/tmdb.rs)*)\
+    /// # Parameters
+    /// * `query` - query to search for.
+    /// * `page (optional)` - page number (default: 1)
+    pub async fn search(
+        &self,
+        query: &str,
+        page: Option<usize>,
+    ) -> anyhow::Result<TmdbSearchResults> {
+        let page = page.unwrap_or(1).to_string();
+
+        let raw: RawPage = self
+            .get("/search/multi", &[("query", query), ("page", &page)])
+            .await?;
+
+        let results: Vec<TmdbResult> = raw
+            .results
+            .into_iter()
+            .filter_map(|result| {
+                let media_type = match result.media_type.as_deref() {
+                    Some("movie") => TvType::Movie,
+                    Some("tv") => TvType::TvSeries,
+                    _ => return None,
+                };
+
+                Some(TmdbResult {
+                    id: result.id,
+                    title: result.title.or(result.name).unwrap_or_default(),
+                    media_type,
+                    release_date: result
+                        .release_date
+                        .or(result.first_air_date)
+                        .filter(|date| !date.is_empty()),
+                    image: image(result.poster_path),
+                    cover: image(result.backdrop_path),
+                    description: result.overview,
+                    rating: result.vote_average,
+                })
+            })
+            .collect();
+
+        Ok(TmdbSearchResults {
+            current_page: raw.page,
+            has_next_page: raw.page < raw.total_pages,
+            total_pages: raw.total_pages,
+            total_results: raw.total_results,
+            results,
+        })
+    }
+
+    /// Returns a future which resolves into TmdbInfo. (*[`impl Future<Output = Result<TmdbInfo>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/tmdb.rs)*)\
+    /// # Parameters
+    /// * `id` - the TMDB id
+    /// * `media_type` - [`TvType::Movie`] or [`TvType::TvSeries`]
+    pub async fn info(&self, id: u32, media_type: TvType) -> anyhow::Result<TmdbInfo> {
+        let path = format!("/{}/{}", media_path(media_type)?, id);
+
+        let raw: RawInfo = self
+            .get(&path, &[("append_to_response", "external_ids")])
+            .await?;
+
+        let title = raw.title.or(raw.name).unwrap_or_default();
+
+        Ok(TmdbInfo {
+            id: raw.id,
+            imdb_id: raw
+                .imdb_id
+                .or(raw.external_ids.and_then(|ids| ids.imdb_id))
+                .filter(|id| !id.is_empty()),
+            original_title: raw
+                .original_title
+                .or(raw.original_name)
+                .filter(|original| !original.is_empty() && *original != title),
+            title,
+            media_type,
+            release_date: raw
+                .release_date
+                .or(raw.first_air_date)
+                .filter(|date| !date.is_empty()),
+            status: raw.status,
+            description: raw.overview,
+            genres: raw.genres.into_iter().map(|genre| genre.name).collect(),
+            rating: raw.vote_average,
+            duration: raw.runtime.or(raw.episode_run_time.first().copied()),
+            image: image(raw.poster_path),
+            cover: image(raw.backdrop_path),
+            seasons: raw
+                .seasons
+                .into_iter()
+                .map(|season| TmdbSeasonSummary {
+                    id: season.id,
+                    number: season.season_number,
+                    title: season.name,
+                    total_episodes: season.episode_count,
+                    air_date: season.air_date,
+                    image: image(season.poster_path),
+                })
+                .collect(),
+        })
+    }
+
+    /// Returns a future which resolves into TmdbSeason, with every episode and its still. (*[`impl Future<Output = Result<TmdbSeason>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/tmdb.rs)*)\
+    /// # Parameters
+    /// * `id` - the TMDB id of the tv show
+    /// * `season` - the season number
+    pub async fn season(&self, id: u32, season: u32) -> anyhow::Result<TmdbSeason> {
+        let raw: RawSeason = self
+            .get(&format!("/tv/{}/season/{}", id, season), &[])
+            .await?;
+
+        Ok(TmdbSeason {
+            id: raw.id,
+            number: raw.season_number,
+            title: raw.name,
+            description: raw.overview,
+            air_date: raw.air_date,
+            image: image(raw.poster_path),
+            episodes: raw
+                .episodes
+                .into_iter()
+                .map(|episode| TmdbEpisode {
+                    id: episode.id,
+                    number: episode.episode_number,
+                    season: episode.season_number,
+                    title: episode.name,
+                    description: episode.overview,
+                    air_date: episode.air_date,
+                    still: image(episode.still_path),
+                    duration: episode.runtime,
+                    rating: episode.vote_average,
+                })
+                .collect(),
+        })
+    }
+
+    /// Returns a future which resolves into the FlixHQ media id best matching the TMDB titles, year and type, if any scores at least [`MIN_MATCH_SCORE`]. (*[`impl Future<Output = Result<Option<String>>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/tmdb.rs)*)\
+    /// # Parameters
+    /// * `id` - the TMDB id
+    /// * `media_type` - [`TvType::Movie`] or [`TvType::TvSeries`]
+    pub async fn flixhq_id(&self, id: u32, media_type: TvType) -> anyhow::Result<Option<String>> {
+        let info = self.info(id, media_type).await?;
+
+        let mut query = MatchQuery::new(&info.title)
+            .with_titles(info.original_title.clone())
+            .with_media_type(media_type);
+        if let Some(year) = info.release_date.as_deref().and_then(release_year) {
+            query = query.with_year(year);
+        }
+
+        let mut listings: Vec<FlixHQListing> = vec![];
+        for title in &query.titles {
+            for listing in self.provider.quick_search(title, None).await?.results {
+                if listing.media_type == media_type
+                    && !listings.iter().any(|other| other.id == listing.id)
+                {
+                    listings.push(listing);
+                }
+            }
+        }
+
+        // Listings show the year of movies only, tv shows need their details page
+        if query.year.is_some() {
+            for listing in &mut listings {
+                if listing.release_date.is_none() && query.score(listing) >= MIN_MATCH_SCORE {
+                    listing.release_date = self
+                        .provider
+                        .fetch_search_result(&listing.id)
+                        .await
+                        .ok()
+                        .and_then(|result| year(&result.release_date).map(String::from));
+                }
+            }
+        }
+
+        Ok(best_match(&query, &listings).map(|best| best.candidate.id.clone()))
+    }
+
+    /// Returns a future which resolves into the FlixHQ episode id of a TMDB episode, if any. (*[`impl Future<Output = Result<Option<String>>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/meta/tmdb.rs)*)\
+    /// # Parameters
+    /// * `id` - the TMDB id of the tv show
+    /// * `season` - the season number
+    /// * `episode` - the episode number within the season
+    pub async fn flixhq_episode_id(
+        &self,
+        id: u32,
+        season: u32,
+        episode: u32,
+    ) -> anyhow::Result<Option<String>> {
+        let Some(media_id) = self.flixhq_id(id, TvType::TvSeries).await? else {
+            return Ok(None);
+        };
+
+        let show = match self
+            .provider
+            .info_with_season(&media_id, Some(season as usize), None)
+            .await
+        {
+            Ok(FlixHQInfo::TV(show)) => show,
+            Ok(FlixHQInfo::Movie(_)) => return Ok(None),
+            // FlixHQ is missing that season
+            Err(err) if err.is::<SeasonNotFound>() => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        Ok(show
+            .seasons
+            .into_iter()
+            .flat_map(|season| season.episodes)
+            .find(|flixhq_episode| flixhq_episode.number == episode)
+            .map(|flixhq_episode| flixhq_episode.id))
+    }
+}
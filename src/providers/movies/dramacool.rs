@@ -6,13 +6,20 @@ use crate::{
         streamtape::{StreamTape, StreamTapeSources},
     },
     html::movies::dramacool_html::DramaCoolHTML,
-    models::{ExtractConfig, StreamingServers, VideoExtractor},
-    CLIENT,
+    models::{
+        ExtractConfig, FetchError, FetchResults, MediaStatus, StreamingServers, SubOrSub,
+        VideoExtractor,
+    },
+    utils::{
+        mirrors::Mirrors,
+        util_funcs::{Days, DEFAULT_CONCURRENCY},
+    },
 };
 
+use anyhow::bail;
+use chrono::NaiveDateTime;
 use futures::{stream, StreamExt};
 use serde::{Deserialize, Serialize};
-use std::sync::{Arc, Mutex};
 
 // Contains all the DramaCool Info
 pub struct DramaCool;
@@ -39,12 +46,48 @@ pub struct DramaCoolSources {
 }
 
 #[derive(Debug, Deserialize, Serialize)]
-pub struct DramaCoolSearchResults {
+pub struct DramaCoolSearchResults<T = DramaCoolResult> {
     pub current_page: usize,
     pub has_next_page: bool,
     pub total_pages: usize,
     pub total_results: usize,
-    pub results: Vec<DramaCoolResult>,
+    pub results: Vec<T>,
+    /// the results whose details could not be fetched
+    pub errors: Vec<FetchError>,
+}
+
+/// Contains a search result as shown on the listing page, without its details
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DramaCoolListing {
+    pub id: String,
+    pub title: String,
+    pub url: String,
+    pub image: String,
+    pub release_date: Option<String>,
+}
+
+/// Contains an episode from the recently added list
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DramaCoolRecentEpisode {
+    pub id: String,
+    pub title: String,
+    pub url: String,
+    pub image: String,
+    pub sub_type: SubOrSub,
+    pub episode_number: Option<u32>,
+    /// relative air date as shown on the site, e.g. "5 hours ago"
+    pub release_date: String,
+}
+
+/// Contains a drama from the upcoming list of the home page
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DramaCoolUpcoming {
+    pub id: String,
+    pub title: String,
+    pub url: String,
+    pub image: String,
+    pub episode_number: Option<u32>,
+    pub release_date: String,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -53,35 +96,48 @@ pub struct DramaCoolResult {
     pub title: String,
     pub url: String,
     pub image: String,
-    pub status: String,
-    pub release_date: String,
+    pub status: MediaStatus,
+    /// the year the drama started airing
+    pub release_date: Option<u32>,
     pub other_names: Vec<String>,
     pub description: String,
     pub genres: Vec<String>,
     pub country: Vec<String>,
+    pub original_network: Vec<String>,
+    /// the days new episodes air on
+    pub airs_on: Vec<Days>,
+    /// the episode duration as shown on the site, e.g. "1 hr. 10 min."
+    pub duration: Option<String>,
 }
 
-#[derive(Default, Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct DramaCoolEpisode {
     pub id: String,
     pub title: String,
-    pub sub_type: String,
-    pub release_date: String,
+    pub number: Option<u32>,
+    pub sub_type: SubOrSub,
+    pub release_date: Option<NaiveDateTime>,
     pub url: String,
 }
 
-#[derive(Default, Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct DramaCoolInfo {
     pub id: String,
     pub title: String,
     pub url: String,
     pub image: String,
-    pub status: String,
-    pub release_date: String,
+    pub status: MediaStatus,
+    /// the year the drama started airing
+    pub release_date: Option<u32>,
     pub other_names: Vec<String>,
     pub description: String,
     pub genres: Vec<String>,
     pub country: Vec<String>,
+    pub original_network: Vec<String>,
+    /// the days new episodes air on
+    pub airs_on: Vec<Days>,
+    /// the episode duration as shown on the site, e.g. "1 hr. 10 min."
+    pub duration: Option<String>,
     pub total_episodes: usize,
     pub episodes: Vec<DramaCoolEpisode>,
 }
@@ -97,72 +153,136 @@ pub struct DramaCoolServer {
     pub url: String,
 }
 
-pub(crate) const BASE_URL: &'static str = "https://dramacool.com.pa";
+pub(crate) const BASE_URL: &str = "https://dramacool.com.pa";
+
+static MIRRORS: Mirrors = Mirrors::new(&[BASE_URL], "DramaCool");
 
 impl DramaCool {
+    /// Replaces the ranked list of mirror domains requests are sent to, the first one is tried first.\
+    /// Returned ids are paths and returned urls always use [`BASE_URL`], whichever mirror served them.\
+    /// The list is global: every `DramaCool` in the process switches to it, e.g. `DramaCool::set_mirrors(domains)`.
+    pub fn set_mirrors(domains: Vec<String>) -> anyhow::Result<()> {
+        MIRRORS.set(domains)
+    }
+
+    /// The ranked list of mirror domains shared by every `DramaCool`, and the one currently in use
+    pub fn mirrors() -> (Vec<String>, String) {
+        (MIRRORS.domains(), MIRRORS.active())
+    }
+
     pub async fn search(
         &self,
         query: &str,
         page: Option<usize>,
     ) -> anyhow::Result<DramaCoolSearchResults> {
+        let listings = self.quick_search(query, page).await?;
+
+        let fetched = self
+            .fetch_search_results(
+                listings
+                    .results
+                    .into_iter()
+                    .map(|listing| listing.id)
+                    .collect(),
+                None,
+            )
+            .await?;
+
+        Ok(DramaCoolSearchResults {
+            current_page: listings.current_page,
+            has_next_page: listings.has_next_page,
+            total_pages: listings.total_pages,
+            total_results: fetched.results.len(),
+            results: fetched.results,
+            errors: fetched.errors,
+        })
+    }
+
+    /// Searches without fetching the details page of every result, use [`DramaCool::fetch_search_result`] for those
+    pub async fn quick_search(
+        &self,
+        query: &str,
+        page: Option<usize>,
+    ) -> anyhow::Result<DramaCoolSearchResults<DramaCoolListing>> {
         let current_page = page.unwrap_or(1);
 
         let parsed_query = query.replace(' ', "-");
-        let page_html = CLIENT
-            .get(format!(
-                "{}/search?keyword={}&page={}",
-                BASE_URL, parsed_query, current_page
+        let page_html = MIRRORS
+            .get(&format!(
+                "/search?keyword={}&page={}",
+                parsed_query, current_page
             ))
-            .send()
-            .await?
-            .text()
             .await?;
 
-        let (ids, has_next_page, total_pages) = self.parse_search(page_html);
-        let mut urls = vec![];
+        let (results, has_next_page, total_pages) = self.parse_search(page_html);
 
-        for id in ids.iter().flatten() {
-            let url = format!("{}/{}", BASE_URL, id);
-            urls.push(url);
-        }
+        Ok(DramaCoolSearchResults {
+            current_page,
+            has_next_page,
+            total_pages,
+            total_results: results.len(),
+            results,
+            errors: vec![],
+        })
+    }
 
-        let bodies = stream::iter(urls.clone())
-            .enumerate()
-            .map(|(index, url)| {
-                let client = &CLIENT;
-                async move {
-                    let resp = client.get(url).send().await?;
-                    resp.text().await.map(|text| (index, text))
-                }
-            })
-            .buffer_unordered(urls.len());
-
-        let results: Arc<Mutex<Vec<DramaCoolResult>>> = Arc::new(Mutex::new(vec![]));
-
-        bodies
-            .for_each(|result| {
-                let urls = urls.clone();
-                let results = Arc::clone(&results);
-
-                async move {
-                    match result {
-                        Ok((index, text)) => {
-                            let url = &urls[index];
-                            let id = url.splitn(4, "/").collect::<Vec<&str>>()[3];
-
-                            let result = self.single_page(text, id, url.to_string());
-
-                            results.lock().unwrap().push(result);
-                        }
-                        Err(err) => {
-                            eprintln!("Error processing url: {}", err);
-                        }
-                    }
-                }
+    pub async fn fetch_search_result(&self, media_id: &str) -> anyhow::Result<DramaCoolResult> {
+        let url = format!("{}/{}", BASE_URL, media_id);
+
+        let media_html = MIRRORS.get(media_id).await?;
+
+        Ok(self.single_page(&media_html, media_id, url))
+    }
+
+    /// Fetches the details page of every media id, `concurrency` pages at a time (default: [`DEFAULT_CONCURRENCY`])
+    pub async fn fetch_search_results(
+        &self,
+        media_ids: Vec<String>,
+        concurrency: Option<usize>,
+    ) -> anyhow::Result<FetchResults<DramaCoolResult>> {
+        let concurrency = concurrency.unwrap_or(DEFAULT_CONCURRENCY).max(1);
+
+        let bodies: Vec<_> = stream::iter(media_ids.into_iter().enumerate())
+            .map(|(index, id)| async move {
+                let url = format!("{}/{}", BASE_URL, id);
+
+                let text = MIRRORS.get(&id).await;
+
+                (index, id, url, text)
             })
+            .buffered(concurrency)
+            .collect()
             .await;
 
-        let results = Arc::try_unwrap(results).unwrap().into_inner().unwrap();
+        let mut results = vec![];
+        let mut errors = vec![];
+
+        for (index, id, url, text) in bodies {
+            match text {
+                Ok(text) => results.push(self.single_page(&text, &id, url)),
+                Err(err) => errors.push(FetchError {
+                    index,
+                    id,
+                    error: err.to_string(),
+                }),
+            }
+        }
+
+        Ok(FetchResults { results, errors })
+    }
+
+    /// Returns the most recently added episodes
+    pub async fn recent_episodes(
+        &self,
+        page: Option<usize>,
+    ) -> anyhow::Result<DramaCoolSearchResults<DramaCoolRecentEpisode>> {
+        let current_page = page.unwrap_or(1);
+
+        let page_html = MIRRORS
+            .get(&format!("/recently-added?page={}", current_page))
+            .await?;
+
+        let (results, has_next_page, total_pages) = self.parse_recent_episodes(page_html);
 
         Ok(DramaCoolSearchResults {
             current_page,
@@ -170,24 +290,63 @@ impl DramaCool {
             total_pages,
             total_results: results.len(),
             results,
+            errors: vec![],
         })
     }
 
-    pub async fn info(&self, media_id: &str) -> anyhow::Result<DramaCoolInfo> {
-        let info_html = CLIENT
-            .get(format!("{}/{}", BASE_URL, media_id))
-            .send()
-            .await?
-            .text()
+    /// Returns the popular dramas which are still airing
+    pub async fn popular_ongoing(
+        &self,
+        page: Option<usize>,
+    ) -> anyhow::Result<DramaCoolSearchResults<DramaCoolListing>> {
+        self.listing_page("popular-ongoing-series", page).await
+    }
+
+    /// Returns the most popular dramas of all time
+    pub async fn most_popular(
+        &self,
+        page: Option<usize>,
+    ) -> anyhow::Result<DramaCoolSearchResults<DramaCoolListing>> {
+        self.listing_page("most-popular-drama", page).await
+    }
+
+    /// Returns the upcoming dramas listed on the home page
+    pub async fn upcoming(&self) -> anyhow::Result<Vec<DramaCoolUpcoming>> {
+        let home_html = MIRRORS.get("/").await?;
+
+        Ok(self.parse_upcoming(home_html))
+    }
+
+    async fn listing_page(
+        &self,
+        path: &str,
+        page: Option<usize>,
+    ) -> anyhow::Result<DramaCoolSearchResults<DramaCoolListing>> {
+        let current_page = page.unwrap_or(1);
+
+        let page_html = MIRRORS
+            .get(&format!("/{}?page={}", path, current_page))
             .await?;
 
-        let search_result = self.single_page(
-            info_html.clone(),
-            media_id,
-            format!("{}/{}", BASE_URL, media_id),
-        );
+        let (results, has_next_page, total_pages) = self.parse_listings(page_html);
 
-        let episodes = self.info_episode(info_html.clone());
+        Ok(DramaCoolSearchResults {
+            current_page,
+            has_next_page,
+            total_pages,
+            total_results: results.len(),
+            results,
+            errors: vec![],
+        })
+    }
+
+    pub async fn info(&self, media_id: &str) -> anyhow::Result<DramaCoolInfo> {
+        let info_html = MIRRORS.get(&format!("/{}", media_id)).await?;
+
+        let search_result =
+            self.single_page(&info_html, media_id, format!("{}/{}", BASE_URL, media_id));
+
+        let episodes = self.info_episode(&info_html);
 
         Ok(DramaCoolInfo {
             total_episodes: episodes.len(),
@@ -202,16 +361,14 @@ impl DramaCool {
             description: search_result.description,
             genres: search_result.genres,
             country: search_result.country,
+            original_network: search_result.original_network,
+            airs_on: search_result.airs_on,
+            duration: search_result.duration,
         })
     }
 
     pub async fn servers(&self, episode_id: &str) -> anyhow::Result<DramaCoolServers> {
-        let server_html = CLIENT
-            .get(format!("{}{}.html", BASE_URL, episode_id))
-            .send()
-            .await?
-            .text()
-            .await?;
+        let server_html = MIRRORS.get(&format!("{}.html", episode_id)).await?;
 
         let servers = self.info_server(server_html);
 
@@ -227,32 +384,19 @@ impl DramaCool {
 
         let servers = self.servers(episode_id).await?;
 
-        let i = match servers
+        let Some(dramacool_server) = servers
             .servers
             .iter()
-            .position(|s| s.name == server.to_string().to_lowercase())
-        {
-            Some(index) => index,
-            None => panic!("Server not found!"),
+            .find(|s| s.name == server.to_string().to_lowercase())
+        else {
+            bail!("Server {} not found for {}", server, episode_id);
         };
 
-        let server_url = &servers.servers[i].url;
+        let server_url = &dramacool_server.url;
 
         match server {
             StreamingServers::AsianLoad => {
-                let mut asianload = AsianLoad {
-                    sources: vec![],
-                    subtitles: vec![],
-                };
-
-                asianload
-                    .extract(
-                        server_url.to_string(),
-                        ExtractConfig {
-                            ..Default::default()
-                        },
-                    )
-                    .await?;
+                let asianload = AsianLoad.extract(server_url, &ExtractConfig::new()).await?;
 
                 Ok(DramaCoolSources {
                     sources: DramaCoolSourceType::AsianLoad(asianload.sources),
@@ -261,19 +405,7 @@ impl DramaCool {
                 })
             }
             StreamingServers::MixDrop => {
-                let mut mix_drop = MixDrop {
-                    sources: vec![],
-                    subtitles: vec![],
-                };
-
-                mix_drop
-                    .extract(
-                        server_url.to_string(),
-                        ExtractConfig {
-                            ..Default::default()
-                        },
-                    )
-                    .await?;
+                let mix_drop = MixDrop.extract(server_url, &ExtractConfig::new()).await?;
 
                 Ok(DramaCoolSources {
                     sources: DramaCoolSourceType::MixDrop(mix_drop.sources),
@@ -282,15 +414,8 @@ impl DramaCool {
                 })
             }
             StreamingServers::StreamTape => {
-                let mut streamtape = StreamTape { sources: vec![] };
-
-                streamtape
-                    .extract(
-                        server_url.to_string(),
-                        ExtractConfig {
-                            ..Default::default()
-                        },
-                    )
+                let streamtape = StreamTape
+                    .extract(server_url, &ExtractConfig::new())
                     .await?;
 
                 Ok(DramaCoolSources {
@@ -300,16 +425,7 @@ impl DramaCool {
                 })
             }
             StreamingServers::StreamSB => {
-                let mut streamsb = StreamSB { sources: vec![] };
-
-                streamsb
-                    .extract(
-                        server_url.to_string(),
-                        ExtractConfig {
-                            ..Default::default()
-                        },
-                    )
-                    .await?;
+                let streamsb = StreamSB.extract(server_url, &ExtractConfig::new()).await?;
 
                 Ok(DramaCoolSources {
                     sources: DramaCoolSourceType::StreamSB(streamsb.sources),
@@ -317,9 +433,7 @@ impl DramaCool {
                     headers: None,
                 })
             }
-            _ => {
-                panic!("Please try a different server.")
-            }
+            _ => bail!("{} is not available on DramaCool", server),
         }
     }
 }
@@ -4,16 +4,188 @@ use crate::{
         vidcloud::{VidCloud, VidCloudSource, VidCloudSubtitle},
     },
     html::movies::flixhq_html::FlixHQHTML,
-    models::{ExtractConfig, StreamingServers, TvType, VideoExtractor},
-    CLIENT,
+    models::{
+        ExtractConfig, FetchError, FetchResults, IMovieEpisode, IMovieSeason, StreamingServers,
+        TvType, VideoExtractor,
+    },
+    utils::{mirrors::Mirrors, util_funcs::DEFAULT_CONCURRENCY},
 };
 
+use anyhow::bail;
 use futures::{stream, StreamExt};
 use serde::{Deserialize, Serialize};
-use std::sync::{Arc, Mutex};
 
 /// Contains all the FlixHQ Info
-pub struct FlixHQ;
+///
+/// The same engine scrapes every site built on FlixHQ's theme, pick one of the ready-made
+/// providers ([`FlixHQ`](const@FlixHQ), [`SFlix`], [`Goku`], [`HDToday`], [`MyFlixer`]) or
+/// use [`FlixHQ::with_profile`] for another sibling site.
+#[derive(Clone, Copy)]
+pub struct FlixHQ {
+    profile: &'static FlixHQProfile,
+}
+
+/// FlixHQ itself
+#[allow(non_upper_case_globals)]
+pub const FlixHQ: FlixHQ = FlixHQ::with_profile(&FLIXHQ_PROFILE);
+/// SFlix, a FlixHQ sibling site
+#[allow(non_upper_case_globals)]
+pub const SFlix: FlixHQ = FlixHQ::with_profile(&SFLIX_PROFILE);
+/// Goku, a FlixHQ sibling site with its own theme, see [`GOKU_PROFILE`]
+#[allow(non_upper_case_globals)]
+pub const Goku: FlixHQ = FlixHQ::with_profile(&GOKU_PROFILE);
+/// HDToday, a FlixHQ sibling site
+#[allow(non_upper_case_globals)]
+pub const HDToday: FlixHQ = FlixHQ::with_profile(&HDTODAY_PROFILE);
+/// MyFlixer, a FlixHQ sibling site
+#[allow(non_upper_case_globals)]
+pub const MyFlixer: FlixHQ = FlixHQ::with_profile(&MYFLIXER_PROFILE);
+
+/// Describes a site sharing FlixHQ's pages, ajax api and servers
+pub struct FlixHQProfile {
+    /// the canonical domain returned urls are built from
+    pub base_url: &'static str,
+    /// the ranked domains requests are sent to, see [`Mirrors`]
+    pub mirrors: Mirrors,
+    pub selectors: FlixHQSelectors,
+}
+
+impl FlixHQProfile {
+    /// Replaces the ranked list of mirror domains requests are sent to, the first one is tried first.\
+    /// Returned ids are paths and returned urls always use the profile's `base_url`, whichever mirror served them.\
+    /// Profiles are shared statics, so every provider built from this one switches too, e.g.
+    /// `SFLIX_PROFILE.set_mirrors(domains)` changes every [`SFlix`] in the process.
+    pub fn set_mirrors(&self, domains: Vec<String>) -> anyhow::Result<()> {
+        self.mirrors.set(domains)
+    }
+
+    /// The ranked list of mirror domains shared by every provider built from this profile, and the one currently in use
+    pub fn mirrors(&self) -> (Vec<String>, String) {
+        (self.mirrors.domains(), self.mirrors.active())
+    }
+}
+
+/// The selectors of the pages a FlixHQ sibling site serves
+#[derive(Clone, Copy, Debug)]
+pub struct FlixHQSelectors {
+    /// a film card in listings and home page sections
+    pub card: &'static str,
+    /// the link to the media page inside a card
+    pub card_link: &'static str,
+    /// the title inside a card, read from its `title` attribute or its text
+    pub card_title: &'static str,
+    /// the poster inside a card, read from its `data-src` or `src` attribute
+    pub card_image: &'static str,
+    /// a slide of the home page slider
+    pub spotlight: &'static str,
+    pub trending_movies: &'static str,
+    pub trending_shows: &'static str,
+    pub recent_movies: &'static str,
+    pub recent_shows: &'static str,
+    pub coming_soon: &'static str,
+    /// the first pagination item, which is `active` on every page but the last
+    pub first_page: &'static str,
+    /// the link to the last page, its `href` ends with `page=<number>`
+    pub last_page: &'static str,
+    pub detail_title: &'static str,
+    /// read from its `src` attribute
+    pub detail_image: &'static str,
+    /// read from its `background-image` style
+    pub detail_cover: &'static str,
+    pub detail_description: &'static str,
+    /// the rows of "Country:", "Genre:", ... labels, matched by position
+    pub detail_label: &'static str,
+    /// the quality, rating and duration badges, matched by position
+    pub detail_stat: &'static str,
+    /// a season of the season list
+    pub season: &'static str,
+    /// an episode of a season
+    pub episode: &'static str,
+    /// a server of an episode or movie
+    pub server: &'static str,
+}
+
+impl FlixHQSelectors {
+    /// The selectors of FlixHQ, most sibling sites use them unchanged
+    pub const FLIXHQ: Self = Self {
+        card: "div.flw-item",
+        card_link: "div.film-poster > a",
+        card_title: "h2.film-name > a",
+        card_image: "img.film-poster-img",
+        spotlight: "#slider div.swiper-slide",
+        trending_movies: "div#trending-movies",
+        trending_shows: "div#trending-tv",
+        recent_movies: "section.block_area:contains('Latest Movies')",
+        recent_shows: "section.block_area:contains('Latest TV Shows')",
+        coming_soon: "section.block_area:contains('Coming Soon')",
+        first_page: "div.pre-pagination:nth-child(3) > nav:nth-child(1) > ul:nth-child(1) > li:nth-child(1)",
+        last_page: "div.pre-pagination:nth-child(3) > nav:nth-child(1) > ul:nth-child(1) > li.page-item:last-child a",
+        detail_title: "#main-wrapper > div.movie_information > div > div.m_i-detail > div.m_i-d-content > h2",
+        detail_image: "div.m_i-d-poster > div > img",
+        detail_cover: "div.w_b-cover",
+        detail_description: "#main-wrapper > div.movie_information > div > div.m_i-detail > div.m_i-d-content > div.description",
+        detail_label: "div.m_i-d-content > div.elements > div",
+        detail_stat: "span.item",
+        season: ".dropdown-menu > a",
+        episode: "ul > li > a",
+        server: "ul > li > a",
+    };
+}
+
+pub(crate) const BASE_URL: &str = "https://flixhq.to";
+
+pub static FLIXHQ_PROFILE: FlixHQProfile = FlixHQProfile {
+    base_url: BASE_URL,
+    mirrors: Mirrors::new(&[BASE_URL], "FlixHQ"),
+    selectors: FlixHQSelectors::FLIXHQ,
+};
+
+pub static SFLIX_PROFILE: FlixHQProfile = FlixHQProfile {
+    base_url: "https://sflix.to",
+    mirrors: Mirrors::new(&["https://sflix.to"], "SFlix"),
+    selectors: FlixHQSelectors::FLIXHQ,
+};
+
+/// Goku serves the same ajax api and ids under a different theme, so only its selectors differ
+pub static GOKU_PROFILE: FlixHQProfile = FlixHQProfile {
+    base_url: "https://goku.sx",
+    mirrors: Mirrors::new(&["https://goku.sx"], "Goku"),
+    selectors: FlixHQSelectors {
+        card: "div.item",
+        card_link: "div.movie-info > a",
+        card_title: "h3.movie-name",
+        card_image: "div.movie-thumbnail img",
+        spotlight: "div.swiper-slide",
+        trending_movies: "div#trending-movies",
+        trending_shows: "div#trending-tv",
+        recent_movies: "div.section-last:contains('Latest Movies')",
+        recent_shows: "div.section-last:contains('Latest TV Series')",
+        coming_soon: "div.section-last:contains('Coming Soon')",
+        first_page: "ul.pagination > li:nth-child(1)",
+        last_page: "ul.pagination > li.page-item:last-child a",
+        detail_title: "div.movie-detail > div.is-name > h3",
+        detail_image: "div.movie-thumbnail > img",
+        detail_cover: "div.movie-cover",
+        detail_description: "div.is-description > div.dropdown-text",
+        detail_label: "div.movie-detail > div.is-info > div.value > div",
+        detail_stat: "div.is-sub > div.item",
+        season: "div.dropdown-menu > a",
+        episode: "div.episodes > div.item > a",
+        server: "div.list-server > a",
+    },
+};
+
+pub static HDTODAY_PROFILE: FlixHQProfile = FlixHQProfile {
+    base_url: "https://hdtoday.cc",
+    mirrors: Mirrors::new(&["https://hdtoday.cc", "https://hdtoday.tv"], "HDToday"),
+    selectors: FlixHQSelectors::FLIXHQ,
+};
+
+pub static MYFLIXER_PROFILE: FlixHQProfile = FlixHQProfile {
+    base_url: "https://myflixerz.to",
+    mirrors: Mirrors::new(&["https://myflixerz.to"], "MyFlixer"),
+    selectors: FlixHQSelectors::FLIXHQ,
+};
 
 #[derive(Debug, Deserialize, Serialize)]
 pub enum FlixHQSourceType {
@@ -47,25 +219,77 @@ pub struct FlixHQServer {
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct FlixHQSeason {
-    pub total_seasons: usize,
-    pub episodes: Vec<Vec<FlixHQEpisode>>,
+    pub id: String,
+    /// the season number, starting at 1
+    pub number: usize,
+    pub title: String,
+    pub total_episodes: usize,
+    pub episodes: Vec<FlixHQEpisode>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct FlixHQEpisode {
     pub id: String,
+    /// the episode number within its season, starting at 1
+    pub number: u32,
+    /// the number of the season this episode belongs to
+    pub season: usize,
+    /// the episode title without its "Eps 3:" prefix
     pub title: String,
     pub url: String,
 }
 
+impl From<FlixHQEpisode> for IMovieEpisode {
+    fn from(episode: FlixHQEpisode) -> Self {
+        IMovieEpisode {
+            id: episode.id,
+            title: Some(episode.title),
+            url: episode.url,
+            number: Some(episode.number),
+            season: Some(episode.season),
+            description: None,
+            image: None,
+            release_date: None,
+        }
+    }
+}
+
+impl From<FlixHQSeason> for IMovieSeason {
+    fn from(season: FlixHQSeason) -> Self {
+        IMovieSeason {
+            season: Some(season.number),
+            image: None,
+            episodes: Some(vec![season
+                .episodes
+                .into_iter()
+                .map(IMovieEpisode::from)
+                .collect()]),
+        }
+    }
+}
+
 /// Contains Search Results
 #[derive(Debug, Deserialize, Serialize)]
-pub struct FlixHQSearchResults {
+pub struct FlixHQSearchResults<T = FlixHQResult> {
     pub current_page: usize,
     pub has_next_page: bool,
     pub total_pages: usize,
     pub total_results: usize,
-    pub results: Vec<FlixHQResult>,
+    pub results: Vec<T>,
+    /// the results whose details could not be fetched
+    pub errors: Vec<FetchError>,
+}
+
+/// Contains a search result as shown on the listing page, without its details
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FlixHQListing {
+    pub id: String,
+    pub title: String,
+    pub url: String,
+    pub image: String,
+    /// the release year for movies, listing pages show the season instead for tv shows
+    pub release_date: Option<String>,
+    pub media_type: TvType,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -88,6 +312,47 @@ pub struct FlixHQResult {
     pub tags: Vec<String>,
 }
 
+/// Contains a media shown in the home page slider
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FlixHQSpotlight {
+    pub id: String,
+    pub title: String,
+    pub url: String,
+    pub cover: String,
+    pub description: String,
+    pub quality: String,
+    pub rating: String,
+    pub duration: String,
+    pub genres: Vec<String>,
+    pub media_type: TvType,
+}
+
+/// Contains every section of the home page, in the order the site lists them
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FlixHQHome {
+    pub spotlight: Vec<FlixHQSpotlight>,
+    pub trending_movies: Vec<FlixHQListing>,
+    pub trending_shows: Vec<FlixHQListing>,
+    pub recent_movies: Vec<FlixHQListing>,
+    pub recent_shows: Vec<FlixHQListing>,
+    pub coming_soon: Vec<FlixHQListing>,
+}
+
+/// The error of [`FlixHQ::info_with_season`] when the show has no season with that number
+#[derive(Debug)]
+pub struct SeasonNotFound {
+    pub media_id: String,
+    pub season: usize,
+}
+
+impl std::fmt::Display for SeasonNotFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Season {} not found for {}", self.season, self.media_id)
+    }
+}
+
+impl std::error::Error for SeasonNotFound {}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub enum FlixHQInfo {
     TV(FlixHQShow),
@@ -132,8 +397,11 @@ pub struct FlixHQShow {
     pub production: Vec<String>,
     pub casts: Vec<String>,
     pub tags: Vec<String>,
+    /// the number of episodes across every loaded season
     pub total_episodes: usize,
-    pub seasons: FlixHQSeason,
+    /// the number of seasons the show has, even when only one was loaded
+    pub total_seasons: usize,
+    pub seasons: Vec<FlixHQSeason>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -141,9 +409,415 @@ pub struct FlixHQServerInfo {
     link: String,
 }
 
-pub(crate) const BASE_URL: &'static str = "https://flixhq.to";
+/// Genres used by the FlixHQ filter and genre listings
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum FlixHQGenre {
+    Action,
+    ActionAndAdventure,
+    Adventure,
+    Animation,
+    Biography,
+    Comedy,
+    Crime,
+    Documentary,
+    Drama,
+    Family,
+    Fantasy,
+    History,
+    Horror,
+    Kids,
+    Music,
+    Mystery,
+    News,
+    Reality,
+    Romance,
+    SciFiAndFantasy,
+    ScienceFiction,
+    Soap,
+    Talk,
+    Thriller,
+    TvMovie,
+    War,
+    WarAndPolitics,
+    Western,
+}
+
+impl FlixHQGenre {
+    /// The id used by the `/filter` endpoint
+    pub fn id(&self) -> u32 {
+        match self {
+            Self::Action => 10,
+            Self::ActionAndAdventure => 24,
+            Self::Adventure => 18,
+            Self::Animation => 3,
+            Self::Biography => 37,
+            Self::Comedy => 7,
+            Self::Crime => 2,
+            Self::Documentary => 11,
+            Self::Drama => 4,
+            Self::Family => 9,
+            Self::Fantasy => 13,
+            Self::History => 19,
+            Self::Horror => 14,
+            Self::Kids => 27,
+            Self::Music => 15,
+            Self::Mystery => 1,
+            Self::News => 34,
+            Self::Reality => 22,
+            Self::Romance => 12,
+            Self::SciFiAndFantasy => 31,
+            Self::ScienceFiction => 5,
+            Self::Soap => 35,
+            Self::Talk => 29,
+            Self::Thriller => 16,
+            Self::TvMovie => 8,
+            Self::War => 17,
+            Self::WarAndPolitics => 28,
+            Self::Western => 6,
+        }
+    }
+
+    /// The path segment used by `/genre/{slug}`
+    pub fn slug(&self) -> &'static str {
+        match self {
+            Self::Action => "action",
+            Self::ActionAndAdventure => "action-adventure",
+            Self::Adventure => "adventure",
+            Self::Animation => "animation",
+            Self::Biography => "biography",
+            Self::Comedy => "comedy",
+            Self::Crime => "crime",
+            Self::Documentary => "documentary",
+            Self::Drama => "drama",
+            Self::Family => "family",
+            Self::Fantasy => "fantasy",
+            Self::History => "history",
+            Self::Horror => "horror",
+            Self::Kids => "kids",
+            Self::Music => "music",
+            Self::Mystery => "mystery",
+            Self::News => "news",
+            Self::Reality => "reality",
+            Self::Romance => "romance",
+            Self::SciFiAndFantasy => "sci-fi-fantasy",
+            Self::ScienceFiction => "science-fiction",
+            Self::Soap => "soap",
+            Self::Talk => "talk",
+            Self::Thriller => "thriller",
+            Self::TvMovie => "tv-movie",
+            Self::War => "war",
+            Self::WarAndPolitics => "war-politics",
+            Self::Western => "western",
+        }
+    }
+}
+
+impl std::fmt::Display for FlixHQGenre {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let genre = match self {
+            Self::Action => "Action",
+            Self::ActionAndAdventure => "Action & Adventure",
+            Self::Adventure => "Adventure",
+            Self::Animation => "Animation",
+            Self::Biography => "Biography",
+            Self::Comedy => "Comedy",
+            Self::Crime => "Crime",
+            Self::Documentary => "Documentary",
+            Self::Drama => "Drama",
+            Self::Family => "Family",
+            Self::Fantasy => "Fantasy",
+            Self::History => "History",
+            Self::Horror => "Horror",
+            Self::Kids => "Kids",
+            Self::Music => "Music",
+            Self::Mystery => "Mystery",
+            Self::News => "News",
+            Self::Reality => "Reality",
+            Self::Romance => "Romance",
+            Self::SciFiAndFantasy => "Sci-Fi & Fantasy",
+            Self::ScienceFiction => "Science Fiction",
+            Self::Soap => "Soap",
+            Self::Talk => "Talk",
+            Self::Thriller => "Thriller",
+            Self::TvMovie => "TV Movie",
+            Self::War => "War",
+            Self::WarAndPolitics => "War & Politics",
+            Self::Western => "Western",
+        };
+
+        write!(f, "{}", genre)
+    }
+}
+
+/// Countries used by the FlixHQ filter and country listings
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum FlixHQCountry {
+    Argentina,
+    Australia,
+    Austria,
+    Belgium,
+    Brazil,
+    Canada,
+    China,
+    Denmark,
+    Finland,
+    France,
+    Germany,
+    HongKong,
+    India,
+    Ireland,
+    Italy,
+    Japan,
+    Mexico,
+    Netherlands,
+    NewZealand,
+    Norway,
+    Poland,
+    Russia,
+    SouthAfrica,
+    SouthKorea,
+    Spain,
+    Sweden,
+    Taiwan,
+    Thailand,
+    UnitedKingdom,
+    UnitedStates,
+}
+
+impl FlixHQCountry {
+    /// The id used by the `/filter` endpoint
+    pub fn id(&self) -> u32 {
+        match self {
+            Self::Argentina => 11,
+            Self::Australia => 151,
+            Self::Austria => 4,
+            Self::Belgium => 44,
+            Self::Brazil => 190,
+            Self::Canada => 147,
+            Self::China => 101,
+            Self::Denmark => 222,
+            Self::Finland => 158,
+            Self::France => 3,
+            Self::Germany => 96,
+            Self::HongKong => 93,
+            Self::India => 105,
+            Self::Ireland => 196,
+            Self::Italy => 205,
+            Self::Japan => 173,
+            Self::Mexico => 40,
+            Self::Netherlands => 172,
+            Self::NewZealand => 122,
+            Self::Norway => 219,
+            Self::Poland => 23,
+            Self::Russia => 109,
+            Self::SouthAfrica => 200,
+            Self::SouthKorea => 135,
+            Self::Spain => 62,
+            Self::Sweden => 114,
+            Self::Taiwan => 119,
+            Self::Thailand => 57,
+            Self::UnitedKingdom => 180,
+            Self::UnitedStates => 129,
+        }
+    }
+
+    /// The ISO 3166-1 code used by `/country/{code}`
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Argentina => "AR",
+            Self::Australia => "AU",
+            Self::Austria => "AT",
+            Self::Belgium => "BE",
+            Self::Brazil => "BR",
+            Self::Canada => "CA",
+            Self::China => "CN",
+            Self::Denmark => "DK",
+            Self::Finland => "FI",
+            Self::France => "FR",
+            Self::Germany => "DE",
+            Self::HongKong => "HK",
+            Self::India => "IN",
+            Self::Ireland => "IE",
+            Self::Italy => "IT",
+            Self::Japan => "JP",
+            Self::Mexico => "MX",
+            Self::Netherlands => "NL",
+            Self::NewZealand => "NZ",
+            Self::Norway => "NO",
+            Self::Poland => "PL",
+            Self::Russia => "RU",
+            Self::SouthAfrica => "ZA",
+            Self::SouthKorea => "KR",
+            Self::Spain => "ES",
+            Self::Sweden => "SE",
+            Self::Taiwan => "TW",
+            Self::Thailand => "TH",
+            Self::UnitedKingdom => "GB",
+            Self::UnitedStates => "US",
+        }
+    }
+}
+
+/// Video quality used by the FlixHQ filter
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum FlixHQQuality {
+    HD,
+    SD,
+    CAM,
+}
+
+impl std::fmt::Display for FlixHQQuality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let quality = match self {
+            Self::HD => "HD",
+            Self::SD => "SD",
+            Self::CAM => "CAM",
+        };
+
+        write!(f, "{}", quality)
+    }
+}
+
+/// Sort order used by the FlixHQ filter
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum FlixHQSort {
+    Default,
+    RecentlyAdded,
+    MostWatched,
+    Name,
+    Imdb,
+}
+
+impl std::fmt::Display for FlixHQSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sort = match self {
+            Self::Default => "default",
+            Self::RecentlyAdded => "recently_added",
+            Self::MostWatched => "most_watched",
+            Self::Name => "name_az",
+            Self::Imdb => "imdb",
+        };
+
+        write!(f, "{}", sort)
+    }
+}
+
+/// Options for [`FlixHQ::filter`], unset options match everything
+/// ```
+/// use consumet::{
+///     models::TvType,
+///     providers::movies::flixhq::{FlixHQFilter, FlixHQGenre},
+/// };
+///
+/// let filter = FlixHQFilter {
+///     media_type: Some(TvType::Movie),
+///     genres: vec![FlixHQGenre::Action, FlixHQGenre::Comedy],
+///     release_year: Some(2023),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     filter.query(),
+///     "type=movie&quality=all&release_year=2023&genre=10-7&country=all&sort=default&page=1"
+/// );
+/// ```
+#[derive(Default, Clone, Debug)]
+pub struct FlixHQFilter {
+    pub media_type: Option<TvType>,
+    pub quality: Option<FlixHQQuality>,
+    pub release_year: Option<u32>,
+    pub genres: Vec<FlixHQGenre>,
+    pub countries: Vec<FlixHQCountry>,
+    pub sort: Option<FlixHQSort>,
+    /// page number (default: 1)
+    pub page: Option<usize>,
+}
+
+impl FlixHQFilter {
+    /// The query string sent to the `/filter` endpoint
+    pub fn query(&self) -> String {
+        let media_type = match self.media_type {
+            Some(TvType::Movie) => "movie",
+            Some(TvType::TvSeries) => "tv",
+            _ => "all",
+        };
+
+        let quality = self
+            .quality
+            .map(|quality| quality.to_string())
+            .unwrap_or(String::from("all"));
+
+        let release_year = self
+            .release_year
+            .map(|year| year.to_string())
+            .unwrap_or(String::from("all"));
+
+        let join_ids = |ids: Vec<u32>| {
+            if ids.is_empty() {
+                return String::from("all");
+            }
+
+            ids.iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join("-")
+        };
+
+        format!(
+            "type={}&quality={}&release_year={}&genre={}&country={}&sort={}&page={}",
+            media_type,
+            quality,
+            release_year,
+            join_ids(self.genres.iter().map(FlixHQGenre::id).collect()),
+            join_ids(self.countries.iter().map(FlixHQCountry::id).collect()),
+            self.sort.unwrap_or(FlixHQSort::Default),
+            self.page.unwrap_or(1)
+        )
+    }
+}
 
 impl FlixHQ {
+    /// Scrapes the site described by `profile`
+    pub const fn with_profile(profile: &'static FlixHQProfile) -> Self {
+        Self { profile }
+    }
+
+    pub fn profile(&self) -> &'static FlixHQProfile {
+        self.profile
+    }
+
+    /// Parses the film cards of a listing page with the profile's selectors
+    /// ```
+    /// use consumet::{models::TvType, providers::movies};
+    ///
+    /// let page_html = r#"
+    ///     <div class="section-items">
+    ///         <div class="item">
+    ///             <div class="movie-thumbnail">
+    ///                 <a href="/movie/watch-oppenheimer-98469"><img src="https://goku.sx/poster.jpg"></a>
+    ///             </div>
+    ///             <div class="movie-info">
+    ///                 <a href="/movie/watch-oppenheimer-98469"><h3 class="movie-name">Oppenheimer</h3></a>
+    ///             </div>
+    ///         </div>
+    ///     </div>
+    /// "#;
+    ///
+    /// let listings = movies::Goku.listings(page_html);
+    /// assert_eq!(listings.len(), 1);
+    /// assert_eq!(listings[0].id, "movie/watch-oppenheimer-98469");
+    /// assert_eq!(listings[0].title, "Oppenheimer");
+    /// assert_eq!(listings[0].url, "https://goku.sx/movie/watch-oppenheimer-98469");
+    /// assert_eq!(listings[0].image, "https://goku.sx/poster.jpg");
+    /// assert_eq!(listings[0].media_type, TvType::Movie);
+    ///
+    /// // FlixHQ's selectors find no cards in Goku's theme
+    /// assert!(movies::FlixHQ.listings(page_html).is_empty());
+    /// ```
+    pub fn listings(&self, page_html: &str) -> Vec<FlixHQListing> {
+        self.parse_search(page_html.to_owned()).0
+    }
+
     /// Returns a future which resolves into FlixHQSearchResults. (*[`impl Future<Output = Result<FlixHQSearchResults>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/movies/flixhq.rs#L60-L68)*)\
     /// # Parameters
     /// `query` - query to search for. (*In this case, We're searching for `Vincenzo`*) P.S: `vincenzo` is a really good korean drama i highly recommend it. |
@@ -156,118 +830,165 @@ impl FlixHQ {
         let current_page = page.unwrap_or(1);
 
         let parsed_query = query.replace(' ', "-");
-        let page_html = CLIENT
-            .get(format!(
-                "{}/search/{}?page={}",
-                BASE_URL, parsed_query, current_page
-            ))
-            .send()
-            .await?
-            .text()
-            .await?;
 
-        let (ids, has_next_page, total_pages) = self.parse_search(page_html);
+        self.search_page(
+            format!("/search/{}?page={}", parsed_query, current_page),
+            current_page,
+        )
+        .await
+    }
+
+    /// Returns a future which resolves into FlixHQSearchResults with only the details shown on the search page. (*[`impl Future<Output = Result<FlixHQSearchResults<FlixHQListing>>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/movies/flixhq.rs#L60-L68)*)\
+    /// Unlike [`FlixHQ::search`] this costs a single request, use [`FlixHQ::fetch_search_result`] to get the details of a result.
+    /// # Parameters
+    /// `query` - query to search for.
+    /// `page (optional)` - page number (default: 1)
+    pub async fn quick_search(
+        &self,
+        query: &str,
+        page: Option<usize>,
+    ) -> anyhow::Result<FlixHQSearchResults<FlixHQListing>> {
+        let current_page = page.unwrap_or(1);
+
+        let parsed_query = query.replace(' ', "-");
 
-        let mut urls = vec![];
+        self.listing_page(
+            format!("/search/{}?page={}", parsed_query, current_page),
+            current_page,
+        )
+        .await
+    }
 
-        for id in ids.iter().flatten() {
-            let url = format!("{}/{}", BASE_URL, id);
-            urls.push(url);
-        }
+    /// Returns a future which resolves into FlixHQResult. (*[`impl Future<Output = Result<FlixHQResult>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/movies/flixhq.rs#L70-L88)*)\
+    /// # Parameters
+    /// * `media_id` - takes media id as a parameter. (*media id can be found in the media search results*)
+    pub async fn fetch_search_result(&self, media_id: &str) -> anyhow::Result<FlixHQResult> {
+        let url = format!("{}/{}", self.profile.base_url, media_id);
 
-        let bodies = stream::iter(urls.clone())
-            .enumerate()
-            .map(|(index, url)| {
-                let client = &CLIENT;
-                async move {
-                    let resp = client.get(url).send().await?;
-                    resp.text().await.map(|text| (index, text))
-                }
-            })
-            .buffer_unordered(urls.len());
-
-        let results: Arc<Mutex<Vec<FlixHQResult>>> = Arc::new(Mutex::new(vec![]));
-
-        bodies
-            .for_each(|result| {
-                let urls = urls.clone(); // Clone urls again for each closure
-                let results = Arc::clone(&results);
-                async move {
-                    match result {
-                        Ok((index, text)) => {
-                            let url = &urls[index];
-                            let id = url.splitn(4, "/").collect::<Vec<&str>>()[3];
-                            let result = self.single_page(text, id, url.to_string()); // Assuming single_page function is defined somewhere
-                            results.lock().unwrap().push(result);
-                        }
-                        Err(err) => {
-                            eprintln!("Error processing url: {}", err);
-                        }
-                    }
-                }
+        let media_html = self.profile.mirrors.get(media_id).await?;
+
+        self.single_page(media_html, media_id, url)
+    }
+
+    /// Returns a future which resolves into the details of every media id, in the given order. (*[`impl Future<Output = Result<FetchResults<FlixHQResult>>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/movies/flixhq.rs#L70-L88)*)\
+    /// # Parameters
+    /// * `media_ids` - takes media ids as a parameter. (*media ids can be found in the media search results*)
+    /// * `concurrency (optional)` - how many pages are fetched at once (default: [`DEFAULT_CONCURRENCY`])
+    pub async fn fetch_search_results(
+        &self,
+        media_ids: Vec<String>,
+        concurrency: Option<usize>,
+    ) -> anyhow::Result<FetchResults<FlixHQResult>> {
+        let concurrency = concurrency.unwrap_or(DEFAULT_CONCURRENCY).max(1);
+
+        let bodies: Vec<_> = stream::iter(media_ids.into_iter().enumerate())
+            .map(|(index, id)| async move {
+                let url = format!("{}/{}", self.profile.base_url, id);
+
+                let text = self.profile.mirrors.get(&id).await;
+
+                (index, id, url, text)
             })
+            .buffered(concurrency)
+            .collect()
             .await;
 
-        let results = Arc::try_unwrap(results).unwrap().into_inner().unwrap();
+        let mut results = vec![];
+        let mut errors = vec![];
+
+        for (index, id, url, text) in bodies {
+            match text.and_then(|text| self.single_page(text, &id, url)) {
+                Ok(result) => results.push(result),
+                Err(err) => errors.push(FetchError {
+                    index,
+                    id,
+                    error: err.to_string(),
+                }),
+            }
+        }
 
-        Ok(FlixHQSearchResults {
-            current_page,
-            has_next_page,
-            total_pages,
-            total_results: results.len(),
-            results,
-        })
+        Ok(FetchResults { results, errors })
     }
 
     /// Returns a future which resolves into an enum containing extra media info (including the episodes). (*[`impl Future<Output = Result<FlixHQInfo>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/movies/flixhq.rs#L90-L94)*)\
     /// # Parameters
     /// * `media_id` - takes media id or url as a parameter. (*media id or url can be found in the media search results as shown on the above method*)
     pub async fn info(&self, media_id: &str) -> anyhow::Result<FlixHQInfo> {
-        let info_html = CLIENT
-            .get(format!("{}/{}", BASE_URL, media_id))
-            .send()
-            .await?
-            .text()
-            .await?;
+        self.info_with_season(media_id, None, None).await
+    }
 
-        let search_result =
-            self.single_page(info_html, media_id, format!("{}/{}", BASE_URL, media_id));
+    /// Returns a future which resolves into an enum containing extra media info, loading the episodes of one or every season. (*[`impl Future<Output = Result<FlixHQInfo>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/movies/flixhq.rs#L90-L94)*)\
+    /// # Parameters
+    /// * `media_id` - takes media id or url as a parameter. (*media id or url can be found in the media search results as shown on the above method*)
+    /// * `season (optional)` - only load the episodes of the season with this number, `seasons` then holds just that season
+    /// * `concurrency (optional)` - how many seasons are fetched at once (default: [`DEFAULT_CONCURRENCY`])
+    pub async fn info_with_season(
+        &self,
+        media_id: &str,
+        season: Option<usize>,
+        concurrency: Option<usize>,
+    ) -> anyhow::Result<FlixHQInfo> {
+        let info_html = self.profile.mirrors.get(&format!("/{}", media_id)).await?;
+
+        let search_result = self.single_page(
+            info_html,
+            media_id,
+            format!("{}/{}", self.profile.base_url, media_id),
+        )?;
 
         let media_type = search_result.media_type;
         let is_seasons = matches!(media_type, TvType::TvSeries);
 
         if is_seasons {
-            let id = media_id.split('-').last().unwrap_or_default().to_owned();
-
-            let season_html = CLIENT
-                .get(format!("{}/ajax/v2/tv/seasons/{}", BASE_URL, id))
-                .send()
-                .await?
-                .text()
+            let id = media_id
+                .split('-')
+                .next_back()
+                .unwrap_or_default()
+                .to_owned();
+
+            let season_html = self
+                .profile
+                .mirrors
+                .get(&format!("/ajax/v2/tv/seasons/{}", id))
                 .await?;
 
-            let season_ids = self.info_season(season_html);
+            let mut seasons = self.info_season(season_html);
+            let total_seasons = seasons.len();
 
-            let mut seasons_and_episodes = vec![];
+            if let Some(number) = season {
+                seasons.retain(|season| season.number == number);
 
-            for season in season_ids {
-                let episode_html = CLIENT
-                    .get(format!("{}/ajax/v2/season/episodes/{}", BASE_URL, &season))
-                    .send()
-                    .await?
-                    .text()
-                    .await?;
+                if seasons.is_empty() {
+                    bail!(SeasonNotFound {
+                        media_id: media_id.to_owned(),
+                        season: number,
+                    });
+                }
+            }
+
+            let concurrency = concurrency.unwrap_or(DEFAULT_CONCURRENCY).max(1);
+
+            // `buffered` yields in input order, so the seasons stay sorted
+            let episode_pages: Vec<anyhow::Result<String>> = stream::iter(seasons.iter())
+                .map(|season| async move {
+                    self.profile
+                        .mirrors
+                        .get(&format!("/ajax/v2/season/episodes/{}", season.id))
+                        .await
+                })
+                .buffered(concurrency)
+                .collect()
+                .await;
 
-                let episodes = self.info_episode(episode_html);
-                seasons_and_episodes.push(episodes);
+            for (season, episode_html) in seasons.iter_mut().zip(episode_pages) {
+                season.episodes = self.info_episode(episode_html?, season.number);
+                season.total_episodes = season.episodes.len();
             }
 
             Ok(FlixHQInfo::TV(FlixHQShow {
-                total_episodes: seasons_and_episodes.last().map(|x| x.len()).unwrap(),
-                seasons: FlixHQSeason {
-                    total_seasons: seasons_and_episodes.len(),
-                    episodes: seasons_and_episodes.clone(),
-                },
+                total_episodes: seasons.iter().map(|season| season.total_episodes).sum(),
+                total_seasons,
+                seasons,
                 id: search_result.id,
                 cover: search_result.cover,
                 title: search_result.title,
@@ -312,10 +1033,10 @@ impl FlixHQ {
     /// * `episode_id` - take an episode id or url as a parameter. (*episode id or episode url can be found in the media info object*)
     /// * `media_id` - takes media id as a parameter. (*media id can be found in the media info object*
     pub async fn servers(&self, episode_id: &str, media_id: &str) -> anyhow::Result<FlixHQServers> {
-        let episode_id = format!(
-            "{}/ajax/{}",
-            BASE_URL,
-            if !episode_id.starts_with(&format!("{}/ajax", BASE_URL)) && !media_id.contains("movie")
+        let episode_path = format!(
+            "/ajax/{}",
+            if !episode_id.starts_with(&format!("{}/ajax", self.profile.base_url))
+                && !media_id.contains("movie")
             {
                 format!("v2/episode/servers/{}", episode_id)
             } else {
@@ -323,7 +1044,7 @@ impl FlixHQ {
             }
         );
 
-        let server_html = CLIENT.get(episode_id).send().await?.text().await?;
+        let server_html = self.profile.mirrors.get(&episode_path).await?;
 
         let servers = self.info_server(server_html, media_id);
 
@@ -344,16 +1065,15 @@ impl FlixHQ {
         let server: StreamingServers = server.unwrap_or(StreamingServers::UpCloud);
         let servers = self.servers(episode_id, media_id).await?;
 
-        let i = match servers
+        let Some(flixhq_server) = servers
             .servers
             .iter()
-            .position(|s| s.name == server.to_string())
-        {
-            Some(index) => index,
-            None => panic!("Server not found!"),
+            .find(|s| s.name == server.to_string())
+        else {
+            bail!("Server {} not found for {}", server, episode_id);
         };
 
-        let parts = &servers.servers[i].url;
+        let parts = &flixhq_server.url;
 
         let server_id: &str = parts
             .split('.')
@@ -362,29 +1082,18 @@ impl FlixHQ {
             .copied()
             .unwrap_or_default();
 
-        let server_json = CLIENT
-            .get(format!("{}/ajax/get_link/{}", BASE_URL, server_id))
-            .send()
-            .await?
-            .text()
+        let server_json = self
+            .profile
+            .mirrors
+            .get(&format!("/ajax/get_link/{}", server_id))
             .await?;
 
         let server_info: FlixHQServerInfo = serde_json::from_str(&server_json)?;
 
         match server {
             StreamingServers::MixDrop => {
-                let mut mix_drop = MixDrop {
-                    sources: vec![],
-                    subtitles: vec![],
-                };
-
-                mix_drop
-                    .extract(
-                        server_info.link.clone(),
-                        ExtractConfig {
-                            ..Default::default()
-                        },
-                    )
+                let mix_drop = MixDrop
+                    .extract(&server_info.link, &ExtractConfig::new())
                     .await?;
 
                 Ok(FlixHQSources {
@@ -394,18 +1103,10 @@ impl FlixHQ {
                 })
             }
             StreamingServers::VidCloud => {
-                let mut vid_cloud = VidCloud {
-                    sources: vec![],
-                    subtitles: vec![],
-                };
-
-                vid_cloud
+                let vid_cloud = VidCloud
                     .extract(
-                        server_info.link.clone(),
-                        ExtractConfig {
-                            is_alternative: Some(true),
-                            ..Default::default()
-                        },
+                        &server_info.link,
+                        &ExtractConfig::new().with_alternative(true),
                     )
                     .await?;
 
@@ -416,18 +1117,8 @@ impl FlixHQ {
                 })
             }
             StreamingServers::UpCloud => {
-                let mut vid_cloud = VidCloud {
-                    sources: vec![],
-                    subtitles: vec![],
-                };
-
-                vid_cloud
-                    .extract(
-                        server_info.link.clone(),
-                        ExtractConfig {
-                            ..Default::default()
-                        },
-                    )
+                let vid_cloud = VidCloud
+                    .extract(&server_info.link, &ExtractConfig::new())
                     .await?;
 
                 Ok(FlixHQSources {
@@ -436,241 +1127,181 @@ impl FlixHQ {
                     headers: server_info.link,
                 })
             }
-            _ => {
-                panic!("Please try a different server.")
-            }
+            _ => bail!(
+                "{} is not available on {}",
+                server,
+                self.profile.mirrors.name()
+            ),
         }
     }
 
-    /// Returns a future which resolves into an vector of movies. (*[`impl Future<Output = Result<Vec<FlixHQResult>>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/movies/flixhq.rs#L70-L88)*)\
+    /// Returns a future which resolves into FlixHQHome. (*[`impl Future<Output = Result<FlixHQHome>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/movies/flixhq.rs)*)\
+    /// Every section comes from a single home page request, use [`FlixHQ::fetch_search_results`] to get the details of the listed media.
     /// # Parameters
     /// * `None`
-    pub async fn recent_movies(&self) -> anyhow::Result<Vec<FlixHQResult>> {
-        let recent_html = CLIENT
-            .get(format!("{}/home", BASE_URL))
-            .send()
-            .await?
-            .text()
-            .await?;
-
-        let ids = self.parse_recent_movies(recent_html);
-
-        let mut urls = vec![];
-
-        for id in ids.iter().flatten() {
-            let url = format!("{}/{}", BASE_URL, id);
-            urls.push(url);
-        }
-
-        let bodies = stream::iter(urls.clone())
-            .enumerate()
-            .map(|(index, url)| {
-                let client = &CLIENT;
-                async move {
-                    let resp = client.get(url).send().await?;
-                    resp.text().await.map(|text| (index, text))
-                }
-            })
-            .buffer_unordered(urls.len());
-
-        let results: Arc<Mutex<Vec<FlixHQResult>>> = Arc::new(Mutex::new(Vec::new()));
-
-        bodies
-            .for_each(|result| {
-                let urls = urls.clone(); // Clone urls again for each closure
-                let results = Arc::clone(&results);
-                async move {
-                    match result {
-                        Ok((index, text)) => {
-                            let url = &urls[index];
-                            let id = url.splitn(4, "/").collect::<Vec<&str>>()[3];
-                            let result = self.single_page(text, id, url.to_string()); // Assuming single_page function is defined somewhere
-                            results.lock().unwrap().push(result);
-                        }
-                        Err(err) => {
-                            eprintln!("Error processing url: {}", err);
-                        }
-                    }
-                }
-            })
-            .await;
+    pub async fn home(&self) -> anyhow::Result<FlixHQHome> {
+        let home_html = self.profile.mirrors.get("/home").await?;
 
-        let results = Arc::try_unwrap(results).unwrap().into_inner().unwrap();
-
-        Ok(results)
+        Ok(self.parse_home(home_html))
     }
 
-    /// Returns a future which resolves into an vector of tv shows. (*[`impl Future<Output = Result<Vec<FlixHQResult>>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/movies/flixhq.rs#L70-L88)*)\
+    /// Returns a future which resolves into the movies, in the order the home page lists them. (*[`impl Future<Output = Result<FetchResults<FlixHQResult>>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/movies/flixhq.rs#L70-L88)*)\
     /// # Parameters
     /// * `None`
-    pub async fn recent_shows(&self) -> anyhow::Result<Vec<FlixHQResult>> {
-        let recent_html = CLIENT
-            .get(format!("{}/home", BASE_URL))
-            .send()
-            .await?
-            .text()
-            .await?;
-
-        let ids = self.parse_recent_shows(recent_html);
-
-        let mut urls = vec![];
-
-        for id in ids.iter().flatten() {
-            let url = format!("{}/{}", BASE_URL, id);
-            urls.push(url);
-        }
-
-        let bodies = stream::iter(urls.clone())
-            .enumerate()
-            .map(|(index, url)| {
-                let client = &CLIENT;
-                async move {
-                    let resp = client.get(url).send().await?;
-                    resp.text().await.map(|text| (index, text))
-                }
-            })
-            .buffer_unordered(urls.len());
-
-        let results: Arc<Mutex<Vec<FlixHQResult>>> = Arc::new(Mutex::new(Vec::new()));
-
-        bodies
-            .for_each(|result| {
-                let urls = urls.clone(); // Clone urls again for each closure
-                let results = Arc::clone(&results);
-                async move {
-                    match result {
-                        Ok((index, text)) => {
-                            let url = &urls[index];
-                            let id = url.splitn(4, "/").collect::<Vec<&str>>()[3];
-                            let result = self.single_page(text, id, url.to_string()); // Assuming single_page function is defined somewhere
-                            results.lock().unwrap().push(result);
-                        }
-                        Err(err) => {
-                            eprintln!("Error processing url: {}", err);
-                        }
-                    }
-                }
-            })
-            .await;
-
-        let results = Arc::try_unwrap(results).unwrap().into_inner().unwrap();
-
-        Ok(results)
+    pub async fn recent_movies(&self) -> anyhow::Result<FetchResults<FlixHQResult>> {
+        let home = self.home().await?;
+
+        self.fetch_search_results(
+            home.recent_movies
+                .into_iter()
+                .map(|listing| listing.id)
+                .collect(),
+            None,
+        )
+        .await
     }
 
-    /// Returns a future which resolves into an vector of movies. (*[`impl Future<Output = Result<Vec<FlixHQResult>>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/movies/flixhq.rs#L70-L88)*)\
+    /// Returns a future which resolves into the tv shows, in the order the home page lists them. (*[`impl Future<Output = Result<FetchResults<FlixHQResult>>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/movies/flixhq.rs#L70-L88)*)\
     /// # Parameters
     /// * `None`
-    pub async fn trending_movies(&self) -> anyhow::Result<Vec<FlixHQResult>> {
-        let trending_html = CLIENT
-            .get(format!("{}/home", BASE_URL))
-            .send()
-            .await?
-            .text()
-            .await?;
-
-        let ids = self.parse_trending_movies(trending_html);
+    pub async fn recent_shows(&self) -> anyhow::Result<FetchResults<FlixHQResult>> {
+        let home = self.home().await?;
+
+        self.fetch_search_results(
+            home.recent_shows
+                .into_iter()
+                .map(|listing| listing.id)
+                .collect(),
+            None,
+        )
+        .await
+    }
 
-        let mut urls = vec![];
+    /// Returns a future which resolves into the movies, in the order the home page lists them. (*[`impl Future<Output = Result<FetchResults<FlixHQResult>>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/movies/flixhq.rs#L70-L88)*)\
+    /// # Parameters
+    /// * `None`
+    pub async fn trending_movies(&self) -> anyhow::Result<FetchResults<FlixHQResult>> {
+        let home = self.home().await?;
+
+        self.fetch_search_results(
+            home.trending_movies
+                .into_iter()
+                .map(|listing| listing.id)
+                .collect(),
+            None,
+        )
+        .await
+    }
 
-        for id in ids.iter().flatten() {
-            let url = format!("{}/{}", BASE_URL, id);
-            urls.push(url);
-        }
+    /// Returns a future which resolves into the tv shows, in the order the home page lists them. (*[`impl Future<Output = Result<FetchResults<FlixHQResult>>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/movies/flixhq.rs#L70-L88)*)\
+    /// # Parameters
+    /// * `None`
+    pub async fn trending_shows(&self) -> anyhow::Result<FetchResults<FlixHQResult>> {
+        let home = self.home().await?;
+
+        self.fetch_search_results(
+            home.trending_shows
+                .into_iter()
+                .map(|listing| listing.id)
+                .collect(),
+            None,
+        )
+        .await
+    }
 
-        let bodies = stream::iter(urls.clone())
-            .enumerate()
-            .map(|(index, url)| {
-                let client = &CLIENT;
-                async move {
-                    let resp = client.get(url).send().await?;
-                    resp.text().await.map(|text| (index, text))
-                }
-            })
-            .buffer_unordered(urls.len());
-
-        let results: Arc<Mutex<Vec<FlixHQResult>>> = Arc::new(Mutex::new(Vec::new()));
-
-        bodies
-            .for_each(|result| {
-                let urls = urls.clone(); // Clone urls again for each closure
-                let results = Arc::clone(&results);
-                async move {
-                    match result {
-                        Ok((index, text)) => {
-                            let url = &urls[index];
-                            let id = url.splitn(4, "/").collect::<Vec<&str>>()[3];
-                            let result = self.single_page(text, id, url.to_string()); // Assuming single_page function is defined somewhere
-                            results.lock().unwrap().push(result);
-                        }
-                        Err(err) => {
-                            eprintln!("Error processing url: {}", err);
-                        }
-                    }
-                }
-            })
-            .await;
+    /// Returns a future which resolves into FlixHQSearchResults. (*[`impl Future<Output = Result<FlixHQSearchResults>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/movies/flixhq.rs#L60-L68)*)\
+    /// # Parameters
+    /// * `filter` - [`FlixHQFilter`] | the genres, countries, type, quality, release year, sort order and page to browse
+    pub async fn filter(&self, filter: FlixHQFilter) -> anyhow::Result<FlixHQSearchResults> {
+        self.search_page(
+            format!("/filter?{}", filter.query()),
+            filter.page.unwrap_or(1),
+        )
+        .await
+    }
 
-        let results = Arc::try_unwrap(results).unwrap().into_inner().unwrap();
+    /// Returns a future which resolves into FlixHQSearchResults. (*[`impl Future<Output = Result<FlixHQSearchResults>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/movies/flixhq.rs#L60-L68)*)\
+    /// # Parameters
+    /// * `genre` - [`FlixHQGenre`] | genre to list
+    /// * `page (optional)` - page number (default: 1)
+    pub async fn genre(
+        &self,
+        genre: FlixHQGenre,
+        page: Option<usize>,
+    ) -> anyhow::Result<FlixHQSearchResults> {
+        let current_page = page.unwrap_or(1);
 
-        Ok(results)
+        self.search_page(
+            format!("/genre/{}?page={}", genre.slug(), current_page),
+            current_page,
+        )
+        .await
     }
 
-    /// Returns a future which resolves into an vector of tv shows. (*[`impl Future<Output = Result<Vec<FlixHQResult>>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/movies/flixhq.rs#L70-L88)*)\
+    /// Returns a future which resolves into FlixHQSearchResults. (*[`impl Future<Output = Result<FlixHQSearchResults>>`](https://github.com/eatmynerds/consumet.rs/blob/master/src/providers/movies/flixhq.rs#L60-L68)*)\
     /// # Parameters
-    /// * `None`
-    pub async fn trending_shows(&self) -> anyhow::Result<Vec<FlixHQResult>> {
-        let trending_html = CLIENT
-            .get(format!("{}/home", BASE_URL))
-            .send()
-            .await?
-            .text()
-            .await?;
+    /// * `country` - [`FlixHQCountry`] | country to list
+    /// * `page (optional)` - page number (default: 1)
+    pub async fn country(
+        &self,
+        country: FlixHQCountry,
+        page: Option<usize>,
+    ) -> anyhow::Result<FlixHQSearchResults> {
+        let current_page = page.unwrap_or(1);
 
-        let ids = self.parse_trending_shows(trending_html);
+        self.search_page(
+            format!("/country/{}?page={}", country.code(), current_page),
+            current_page,
+        )
+        .await
+    }
 
-        let mut urls = vec![];
+    /// Fetches a paginated listing page from the active mirror and the details of every result on it
+    async fn search_page(
+        &self,
+        page_path: String,
+        current_page: usize,
+    ) -> anyhow::Result<FlixHQSearchResults> {
+        let listings = self.listing_page(page_path, current_page).await?;
+
+        let fetched = self
+            .fetch_search_results(
+                listings
+                    .results
+                    .into_iter()
+                    .map(|listing| listing.id)
+                    .collect(),
+                None,
+            )
+            .await?;
 
-        for id in ids.iter().flatten() {
-            let url = format!("{}/{}", BASE_URL, id);
-            urls.push(url);
-        }
+        Ok(FlixHQSearchResults {
+            current_page,
+            has_next_page: listings.has_next_page,
+            total_pages: listings.total_pages,
+            total_results: fetched.results.len(),
+            results: fetched.results,
+            errors: fetched.errors,
+        })
+    }
 
-        let bodies = stream::iter(urls.clone())
-            .enumerate()
-            .map(|(index, url)| {
-                let client = &CLIENT;
-                async move {
-                    let resp = client.get(url).send().await?;
-                    resp.text().await.map(|text| (index, text))
-                }
-            })
-            .buffer_unordered(urls.len());
-
-        let results: Arc<Mutex<Vec<FlixHQResult>>> = Arc::new(Mutex::new(Vec::new()));
-
-        bodies
-            .for_each(|result| {
-                let urls = urls.clone(); // Clone urls again for each closure
-                let results = Arc::clone(&results);
-                async move {
-                    match result {
-                        Ok((index, text)) => {
-                            let url = &urls[index];
-                            let id = url.splitn(4, "/").collect::<Vec<&str>>()[3];
-                            let result = self.single_page(text, id, url.to_string()); // Assuming single_page function is defined somewhere
-                            results.lock().unwrap().push(result);
-                        }
-                        Err(err) => {
-                            eprintln!("Error processing url: {}", err);
-                        }
-                    }
-                }
-            })
-            .await;
+    /// Fetches a paginated listing page from the active mirror
+    async fn listing_page(
+        &self,
+        page_path: String,
+        current_page: usize,
+    ) -> anyhow::Result<FlixHQSearchResults<FlixHQListing>> {
+        let page_html = self.profile.mirrors.get(&page_path).await?;
 
-        let results = Arc::try_unwrap(results).unwrap().into_inner().unwrap();
+        let (results, has_next_page, total_pages) = self.parse_search(page_html);
 
-        Ok(results)
+        Ok(FlixHQSearchResults {
+            current_page,
+            has_next_page,
+            total_pages,
+            total_results: results.len(),
+            results,
+            errors: vec![],
+        })
     }
 }
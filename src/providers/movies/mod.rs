@@ -2,3 +2,5 @@ pub mod flixhq;
 pub use flixhq::*;
 pub mod dramacool;
 pub use dramacool::*;
+pub mod viewasian;
+pub use viewasian::*;
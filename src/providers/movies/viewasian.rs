@@ -0,0 +1,251 @@
+use crate::{
+    extractors::{asianload::AsianLoad, mixdrop::MixDrop, streamtape::StreamTape},
+    html::movies::viewasian_html::ViewAsianHTML,
+    models::{ExtractConfig, FetchError, FetchResults, StreamingServers, VideoExtractor},
+    providers::movies::dramacool::{
+        DramaCoolEpisode, DramaCoolInfo, DramaCoolListing, DramaCoolResult, DramaCoolSearchResults,
+        DramaCoolServers, DramaCoolSourceType, DramaCoolSources, DramaCoolSubtitles,
+    },
+    utils::{mirrors::Mirrors, util_funcs::DEFAULT_CONCURRENCY},
+};
+
+use anyhow::bail;
+use futures::{stream, StreamExt};
+
+/// Asian dramas from ViewAsian, returning the same types as [`DramaCool`](crate::providers::movies::DramaCool)
+/// so either provider can stand in for the other
+pub struct ViewAsian;
+
+pub(crate) const BASE_URL: &str = "https://viewasian.co";
+
+static MIRRORS: Mirrors = Mirrors::new(&[BASE_URL], "ViewAsian");
+
+impl ViewAsian {
+    /// Replaces the ranked list of mirror domains requests are sent to, the first one is tried first.\
+    /// Returned ids are paths and returned urls always use [`BASE_URL`], whichever mirror served them.\
+    /// The list is global: every `ViewAsian` in the process switches to it, e.g. `ViewAsian::set_mirrors(domains)`.
+    pub fn set_mirrors(domains: Vec<String>) -> anyhow::Result<()> {
+        MIRRORS.set(domains)
+    }
+
+    /// The ranked list of mirror domains shared by every `ViewAsian`, and the one currently in use
+    pub fn mirrors() -> (Vec<String>, String) {
+        (MIRRORS.domains(), MIRRORS.active())
+    }
+
+    pub async fn search(
+        &self,
+        query: &str,
+        page: Option<usize>,
+    ) -> anyhow::Result<DramaCoolSearchResults> {
+        let listings = self.quick_search(query, page).await?;
+
+        let fetched = self
+            .fetch_search_results(
+                listings
+                    .results
+                    .into_iter()
+                    .map(|listing| listing.id)
+                    .collect(),
+                None,
+            )
+            .await?;
+
+        Ok(DramaCoolSearchResults {
+            current_page: listings.current_page,
+            has_next_page: listings.has_next_page,
+            total_pages: listings.total_pages,
+            total_results: fetched.results.len(),
+            results: fetched.results,
+            errors: fetched.errors,
+        })
+    }
+
+    /// Searches without fetching the details page of every result, use [`ViewAsian::fetch_search_result`] for those
+    pub async fn quick_search(
+        &self,
+        query: &str,
+        page: Option<usize>,
+    ) -> anyhow::Result<DramaCoolSearchResults<DramaCoolListing>> {
+        let current_page = page.unwrap_or(1);
+
+        let parsed_query = query.replace(' ', "-");
+        let page_html = MIRRORS
+            .get(&format!(
+                "/movie/search/{}?page={}",
+                parsed_query, current_page
+            ))
+            .await?;
+
+        let (results, has_next_page, total_pages) = self.parse_search(page_html);
+
+        Ok(DramaCoolSearchResults {
+            current_page,
+            has_next_page,
+            total_pages,
+            total_results: results.len(),
+            results,
+            errors: vec![],
+        })
+    }
+
+    pub async fn fetch_search_result(&self, media_id: &str) -> anyhow::Result<DramaCoolResult> {
+        let url = format!("{}/{}", BASE_URL, media_id);
+
+        let media_html = MIRRORS.get(media_id).await?;
+
+        Ok(self.single_page(&media_html, media_id, url))
+    }
+
+    /// Fetches the details page of every media id, `concurrency` pages at a time (default: [`DEFAULT_CONCURRENCY`])
+    pub async fn fetch_search_results(
+        &self,
+        media_ids: Vec<String>,
+        concurrency: Option<usize>,
+    ) -> anyhow::Result<FetchResults<DramaCoolResult>> {
+        let concurrency = concurrency.unwrap_or(DEFAULT_CONCURRENCY).max(1);
+
+        let bodies: Vec<_> = stream::iter(media_ids.into_iter().enumerate())
+            .map(|(index, id)| async move {
+                let url = format!("{}/{}", BASE_URL, id);
+
+                let text = MIRRORS.get(&id).await;
+
+                (index, id, url, text)
+            })
+            .buffered(concurrency)
+            .collect()
+            .await;
+
+        let mut results = vec![];
+        let mut errors = vec![];
+
+        for (index, id, url, text) in bodies {
+            match text {
+                Ok(text) => results.push(self.single_page(&text, &id, url)),
+                Err(err) => errors.push(FetchError {
+                    index,
+                    id,
+                    error: err.to_string(),
+                }),
+            }
+        }
+
+        Ok(FetchResults { results, errors })
+    }
+
+    pub async fn info(&self, media_id: &str) -> anyhow::Result<DramaCoolInfo> {
+        let info_html = MIRRORS.get(media_id).await?;
+
+        let search_result =
+            self.single_page(&info_html, media_id, format!("{}/{}", BASE_URL, media_id));
+
+        let episodes = self.info_episode(&info_html);
+
+        Ok(DramaCoolInfo {
+            total_episodes: episodes.len(),
+            episodes,
+            id: search_result.id,
+            title: search_result.title,
+            url: search_result.url,
+            image: search_result.image,
+            status: search_result.status,
+            release_date: search_result.release_date,
+            other_names: search_result.other_names,
+            description: search_result.description,
+            genres: search_result.genres,
+            country: search_result.country,
+            original_network: search_result.original_network,
+            airs_on: search_result.airs_on,
+            duration: search_result.duration,
+        })
+    }
+
+    /// Parses the episode list of a drama page, ids are paths without a leading `/`
+    /// ```
+    /// use consumet::providers::movies::ViewAsian;
+    ///
+    /// let media_html = r#"
+    ///     <ul class="episodes">
+    ///         <li>
+    ///             <a href="/watch/vincenzo/watching.html$episode=20">
+    ///                 <span class="ep">Episode 20</span>
+    ///                 <span class="type">SUB</span>
+    ///                 <span class="time">2021-05-02 12:00:00</span>
+    ///             </a>
+    ///         </li>
+    ///     </ul>
+    /// "#;
+    ///
+    /// let episodes = ViewAsian.episodes(media_html);
+    /// assert_eq!(episodes[0].id, "watch/vincenzo/watching.html$episode=20");
+    /// assert_eq!(
+    ///     episodes[0].url,
+    ///     "https://viewasian.co/watch/vincenzo/watching.html$episode=20"
+    /// );
+    /// assert_eq!(episodes[0].number, Some(20));
+    /// ```
+    pub fn episodes(&self, media_html: &str) -> Vec<DramaCoolEpisode> {
+        self.info_episode(media_html)
+    }
+
+    pub async fn servers(&self, episode_id: &str) -> anyhow::Result<DramaCoolServers> {
+        let server_html = MIRRORS.get(episode_id).await?;
+
+        let servers = self.info_server(server_html);
+
+        Ok(DramaCoolServers { servers })
+    }
+
+    pub async fn sources(
+        &self,
+        episode_id: &str,
+        server: Option<StreamingServers>,
+    ) -> anyhow::Result<DramaCoolSources> {
+        let server: StreamingServers = server.unwrap_or(StreamingServers::AsianLoad);
+
+        let servers = self.servers(episode_id).await?;
+
+        let Some(server_url) = servers
+            .servers
+            .iter()
+            .find(|s| s.name == server.to_string().to_lowercase())
+            .map(|s| &s.url)
+        else {
+            bail!("Server {} not found for {}", server, episode_id);
+        };
+
+        match server {
+            StreamingServers::AsianLoad => {
+                let asianload = AsianLoad.extract(server_url, &ExtractConfig::new()).await?;
+
+                Ok(DramaCoolSources {
+                    sources: DramaCoolSourceType::AsianLoad(asianload.sources),
+                    subtitles: Some(DramaCoolSubtitles::AsianLoad(asianload.subtitles)),
+                    headers: None,
+                })
+            }
+            StreamingServers::MixDrop => {
+                let mix_drop = MixDrop.extract(server_url, &ExtractConfig::new()).await?;
+
+                Ok(DramaCoolSources {
+                    sources: DramaCoolSourceType::MixDrop(mix_drop.sources),
+                    subtitles: Some(DramaCoolSubtitles::MixDrop(mix_drop.subtitles)),
+                    headers: Some(server_url.to_string()),
+                })
+            }
+            StreamingServers::StreamTape => {
+                let streamtape = StreamTape
+                    .extract(server_url, &ExtractConfig::new())
+                    .await?;
+
+                Ok(DramaCoolSources {
+                    sources: DramaCoolSourceType::StreamTape(streamtape.sources),
+                    subtitles: None,
+                    headers: None,
+                })
+            }
+            _ => bail!("{} is not available on ViewAsian", server),
+        }
+    }
+}
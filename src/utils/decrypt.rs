@@ -12,7 +12,9 @@ pub fn decrypt_url(encrypted_url: &str, key: &[u8]) -> Result<String, Encryption
     let decoded_ciphertext =
         openssl::base64::decode_block(encrypted_url).map_err(EncryptionError::OpenSSLError)?;
 
-    assert_eq!(&decoded_ciphertext[0..8], "Salted__".as_bytes());
+    if decoded_ciphertext.len() < 16 || !decoded_ciphertext.starts_with(b"Salted__") {
+        return Err(EncryptionError::ParsingError);
+    }
 
     let cipher = openssl::symm::Cipher::aes_256_cbc();
     let key_iv_pair = openssl::pkcs5::bytes_to_key(
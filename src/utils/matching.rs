@@ -0,0 +1,476 @@
+use crate::models::{IAnimeResult, IMovieResult, ITitle, MediaFormat, TvType};
+
+use std::cmp::Ordering;
+
+/// Best matches scoring below this are not returned by [`best_match`]
+pub const MIN_MATCH_SCORE: f32 = 0.5;
+
+const ORDINALS: [(&str, &str); 10] = [
+    ("first", "1"),
+    ("second", "2"),
+    ("third", "3"),
+    ("fourth", "4"),
+    ("fifth", "5"),
+    ("sixth", "6"),
+    ("seventh", "7"),
+    ("eighth", "8"),
+    ("ninth", "9"),
+    ("tenth", "10"),
+];
+
+const ROMAN_NUMERALS: [(&str, &str); 9] = [
+    ("ii", "2"),
+    ("iii", "3"),
+    ("iv", "4"),
+    ("v", "5"),
+    ("vi", "6"),
+    ("vii", "7"),
+    ("viii", "8"),
+    ("ix", "9"),
+    ("x", "10"),
+];
+
+/// "2nd" and "second" into "2"
+fn ordinal(word: &str) -> Option<String> {
+    if let Some((_, number)) = ORDINALS.iter().find(|(name, _)| *name == word) {
+        return Some(number.to_string());
+    }
+
+    let digits = word.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let suffix = &word[digits.len()..];
+
+    (!digits.is_empty()
+        && digits.chars().all(|c| c.is_ascii_digit())
+        && ["st", "nd", "rd", "th"].contains(&suffix))
+    .then(|| digits.trim_start_matches('0').to_string())
+}
+
+/// Drops a trailing year in parentheses, years which are part of the title are kept
+fn without_year(title: &str) -> &str {
+    let trimmed = title.trim_end();
+
+    match trimmed
+        .strip_suffix(')')
+        .and_then(|rest| rest.rsplit_once('('))
+    {
+        Some((title, year)) if year.len() == 4 && year.chars().all(|c| c.is_ascii_digit()) => title,
+        _ => trimmed,
+    }
+}
+
+fn number(word: &str) -> Option<String> {
+    (!word.is_empty() && word.chars().all(|c| c.is_ascii_digit()))
+        .then(|| word.trim_start_matches('0').to_string())
+}
+
+/// Lowercases the title, drops punctuation and a trailing "(2021)", and writes season markers
+/// ("2nd Season", "Second Season", "Season 2", "S2", a trailing "II") as "season 2".
+///
+/// "Season 1" is dropped, so the first season compares equal to the bare title.
+/// ```
+/// use consumet::utils::matching::normalize_title;
+///
+/// assert_eq!(normalize_title("Kaguya-sama: Love is War - 2nd Season"), "kaguya sama love is war season 2");
+/// assert_eq!(normalize_title("Kaguya-sama: Love Is War Season 2"), "kaguya sama love is war season 2");
+/// assert_eq!(normalize_title("Overlord II"), "overlord season 2");
+/// assert_eq!(normalize_title("Hunter x Hunter (2011)"), "hunter x hunter");
+/// assert_eq!(normalize_title("Blade Runner 2049"), "blade runner 2049");
+/// assert_eq!(normalize_title("Dr. Stone"), normalize_title("Dr Stone"));
+/// ```
+pub fn normalize_title(title: &str) -> String {
+    let lowercase = without_year(title).to_lowercase().replace('&', " and ");
+    let words: Vec<&str> = lowercase
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    let mut normalized: Vec<String> = vec![];
+    let mut i = 0;
+
+    while i < words.len() {
+        let word = words[i];
+        let next = words.get(i + 1).copied();
+
+        if let (Some(n), Some(marker @ ("season" | "part" | "cour"))) = (ordinal(word), next) {
+            let marker = if marker == "cour" { "season" } else { marker };
+            normalized.extend([marker.to_string(), n]);
+            i += 2;
+            continue;
+        }
+
+        if let (marker @ ("season" | "part" | "cour"), Some(n)) = (
+            word,
+            next.and_then(|next| number(next).or_else(|| ordinal(next))),
+        ) {
+            let marker = if marker == "cour" { "season" } else { marker };
+            normalized.extend([marker.to_string(), n]);
+            i += 2;
+            continue;
+        }
+
+        if let Some(n) = word.strip_prefix('s').and_then(number) {
+            normalized.extend(["season".to_string(), n]);
+            i += 1;
+            continue;
+        }
+
+        let last = i + 1 == words.len();
+        match ROMAN_NUMERALS.iter().find(|(numeral, _)| *numeral == word) {
+            Some((_, n)) if last && i > 0 => {
+                normalized.extend(["season".to_string(), n.to_string()])
+            }
+            _ => normalized.push(word.to_string()),
+        }
+        i += 1;
+    }
+
+    if let Some(at) = normalized
+        .windows(2)
+        .position(|pair| pair[0] == "season" && pair[1] == "1")
+    {
+        normalized.drain(at..at + 2);
+    }
+
+    normalized.join(" ")
+}
+
+/// The number after "season" in a normalized title, 1 when there is none
+fn season(normalized: &str) -> u32 {
+    let words: Vec<&str> = normalized.split(' ').collect();
+
+    words
+        .windows(2)
+        .find(|pair| pair[0] == "season")
+        .and_then(|pair| pair[1].parse().ok())
+        .unwrap_or(1)
+}
+
+/// Dice coefficient of two lists, counting repeated items once per occurrence
+fn dice<T: PartialEq>(a: &[T], b: &[T]) -> f32 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let mut unmatched: Vec<&T> = b.iter().collect();
+    let mut shared = 0;
+
+    for item in a {
+        if let Some(at) = unmatched.iter().position(|other| *other == item) {
+            unmatched.swap_remove(at);
+            shared += 1;
+        }
+    }
+
+    2.0 * shared as f32 / (a.len() + b.len()) as f32
+}
+
+fn bigrams(normalized: &str) -> Vec<(char, char)> {
+    let chars: Vec<char> = normalized.chars().filter(|c| *c != ' ').collect();
+
+    chars.windows(2).map(|pair| (pair[0], pair[1])).collect()
+}
+
+/// How alike two titles are, from 0 to 1, after [`normalize_title`].
+///
+/// The better of the word and the letter pair Dice coefficients, halved when the titles name
+/// different seasons. Only titles which normalize to the same string score 1.
+/// ```
+/// use consumet::utils::matching::title_similarity;
+///
+/// assert_eq!(title_similarity("Attack on Titan", "Attack on Titan (2013)"), 1.0);
+/// assert!(title_similarity("Shingeki no Kyojin", "Shingeki no Kyoujin") > 0.8);
+/// assert!(title_similarity("Attack on Titan Season 2", "Attack on Titan Season 3") < 0.5);
+/// assert_eq!(title_similarity("Naruto", ""), 0.0);
+/// ```
+pub fn title_similarity(a: &str, b: &str) -> f32 {
+    let (a, b) = (normalize_title(a), normalize_title(b));
+
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    if a == b {
+        return 1.0;
+    }
+
+    let words = dice(
+        &a.split(' ').collect::<Vec<_>>(),
+        &b.split(' ').collect::<Vec<_>>(),
+    );
+    let letters = dice(&bigrams(&a), &bigrams(&b));
+
+    // Different spellings can share every letter pair, keep exact matches on top
+    let mut similarity = words.max(letters).min(0.99);
+
+    if season(&a) != season(&b) {
+        similarity *= 0.5;
+    }
+
+    similarity
+}
+
+/// The highest [`title_similarity`] between any of the titles and any of the other titles
+/// ```
+/// use consumet::utils::matching::best_title_similarity;
+///
+/// let titles = ["Shingeki no Kyojin", "Attack on Titan", "進撃の巨人"];
+/// assert_eq!(best_title_similarity(titles, ["Attack on Titan"]), 1.0);
+/// ```
+pub fn best_title_similarity<'a, 'b>(
+    titles: impl IntoIterator<Item = &'a str>,
+    others: impl IntoIterator<Item = &'b str> + Clone,
+) -> f32 {
+    titles
+        .into_iter()
+        .flat_map(|title| {
+            others
+                .clone()
+                .into_iter()
+                .map(move |other| title_similarity(title, other))
+        })
+        .fold(0.0, f32::max)
+}
+
+/// The non empty titles of an [`ITitle`], user preferred first
+pub fn alt_titles(title: &ITitle) -> Vec<&str> {
+    [
+        &title.user_preferred,
+        &title.english,
+        &title.romaji,
+        &title.native,
+    ]
+    .into_iter()
+    .flatten()
+    .map(String::as_str)
+    .filter(|title| !title.is_empty())
+    .collect()
+}
+
+/// The year at the start of a release date like "2021" or "2021-10-03"
+pub fn release_year(date: &str) -> Option<u32> {
+    date.get(..4)
+        .filter(|year| year.chars().all(|c| c.is_ascii_digit()))
+        .and_then(|year| year.parse().ok())
+}
+
+/// Something a provider listed which can be matched against a [`MatchQuery`]
+pub trait MatchCandidate {
+    /// most relevant first
+    fn titles(&self) -> Vec<&str>;
+    fn year(&self) -> Option<u32>;
+    fn media_type(&self) -> Option<TvType>;
+}
+
+impl MatchCandidate for IAnimeResult {
+    fn titles(&self) -> Vec<&str> {
+        alt_titles(&self.title)
+    }
+
+    fn year(&self) -> Option<u32> {
+        self.release_date.as_deref().and_then(release_year)
+    }
+
+    fn media_type(&self) -> Option<TvType> {
+        self.show_type.and_then(anime_type)
+    }
+}
+
+impl MatchCandidate for IMovieResult {
+    fn titles(&self) -> Vec<&str> {
+        std::iter::once(self.title.as_str())
+            .chain(self.other_names.iter().flatten().map(String::as_str))
+            .collect()
+    }
+
+    fn year(&self) -> Option<u32> {
+        release_year(&self.release_date)
+    }
+
+    fn media_type(&self) -> Option<TvType> {
+        self.media_type
+    }
+}
+
+/// Anime movies are movies, every other anime format is a series
+fn anime_type(format: MediaFormat) -> Option<TvType> {
+    match format {
+        MediaFormat::Movie => Some(TvType::Movie),
+        MediaFormat::Tv
+        | MediaFormat::TvShort
+        | MediaFormat::Special
+        | MediaFormat::Ova
+        | MediaFormat::Ona => Some(TvType::TvSeries),
+        _ => None,
+    }
+}
+
+/// Contains what is known about the entry being looked up on a provider
+/// ```
+/// use consumet::models::TvType;
+/// use consumet::utils::matching::MatchQuery;
+///
+/// let synonyms = vec!["SnK".to_owned(), "AoT".to_owned()];
+///
+/// let query = MatchQuery::new("Shingeki no Kyojin")
+///     .with_titles(synonyms)
+///     .with_year(2013)
+///     .with_media_type(TvType::TvSeries);
+///
+/// assert_eq!(query.titles.len(), 3);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MatchQuery {
+    /// most relevant first, e.g. the main title, then alternative titles and synonyms
+    pub titles: Vec<String>,
+    pub year: Option<u32>,
+    pub media_type: Option<TvType>,
+}
+
+impl MatchQuery {
+    pub fn new(title: &str) -> Self {
+        Self {
+            titles: vec![title.to_owned()],
+            ..Default::default()
+        }
+    }
+
+    /// Adds alternative titles, such as `other_names` or `synonyms`
+    pub fn with_titles(mut self, titles: impl IntoIterator<Item = String>) -> Self {
+        self.titles
+            .extend(titles.into_iter().filter(|title| !title.is_empty()));
+        self
+    }
+
+    pub fn with_year(mut self, year: u32) -> Self {
+        self.year = Some(year);
+        self
+    }
+
+    pub fn with_media_type(mut self, media_type: TvType) -> Self {
+        self.media_type = Some(media_type);
+        self
+    }
+
+    /// How well the candidate matches, from 0 to 1.
+    ///
+    /// The best similarity between any two titles, times 0.9 when the years are one apart,
+    /// 0.5 when they are further apart and 0.5 when the media types differ. Missing years and
+    /// types are not held against the candidate.
+    pub fn score(&self, candidate: &impl MatchCandidate) -> f32 {
+        let mut score =
+            best_title_similarity(self.titles.iter().map(String::as_str), candidate.titles());
+
+        if let (Some(expected), Some(found)) = (self.year, candidate.year()) {
+            score *= match expected.abs_diff(found) {
+                0 => 1.0,
+                1 => 0.9,
+                _ => 0.5,
+            };
+        }
+
+        if let (Some(expected), Some(found)) = (self.media_type, candidate.media_type()) {
+            if expected != found {
+                score *= 0.5;
+            }
+        }
+
+        score
+    }
+
+    /// Orders candidates of equal score, exact years then matching types first
+    fn tie_break<T: MatchCandidate>(&self, a: &T, b: &T) -> Ordering {
+        let exact_year = |candidate: &T| self.year.is_some() && candidate.year() == self.year;
+        let same_type =
+            |candidate: &T| self.media_type.is_some() && candidate.media_type() == self.media_type;
+
+        exact_year(b)
+            .cmp(&exact_year(a))
+            .then(same_type(b).cmp(&same_type(a)))
+    }
+
+    /// Sorts the candidates from best to worst match, along with their scores
+    pub fn rank<'a, T: MatchCandidate>(&self, candidates: &'a [T]) -> Vec<Match<'a, T>> {
+        let mut ranked: Vec<Match<T>> = candidates
+            .iter()
+            .map(|candidate| Match {
+                score: self.score(candidate),
+                candidate,
+            })
+            .collect();
+
+        // Stable, so the provider's order decides what is left
+        ranked.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| self.tie_break(a.candidate, b.candidate))
+        });
+
+        ranked
+    }
+}
+
+impl From<&IAnimeResult> for MatchQuery {
+    fn from(result: &IAnimeResult) -> Self {
+        Self {
+            titles: result.titles().into_iter().map(str::to_owned).collect(),
+            year: result.year(),
+            media_type: result.media_type(),
+        }
+    }
+}
+
+impl From<&IMovieResult> for MatchQuery {
+    fn from(result: &IMovieResult) -> Self {
+        Self {
+            titles: result.titles().into_iter().map(str::to_owned).collect(),
+            year: result.year(),
+            media_type: result.media_type(),
+        }
+    }
+}
+
+/// Contains a provider result and how well it matched
+#[derive(Debug)]
+pub struct Match<'a, T> {
+    pub candidate: &'a T,
+    /// from 0 to 1
+    pub score: f32,
+}
+
+/// The provider result matching the query best, if it scores at least [`MIN_MATCH_SCORE`]
+/// ```
+/// use consumet::models::{IMovieResult, TvType};
+/// use consumet::utils::matching::{best_match, MatchQuery};
+///
+/// let listing = |title: &str, year: &str, media_type| IMovieResult {
+///     id: Some(title.to_lowercase()),
+///     cover: None,
+///     title: title.to_owned(),
+///     other_names: None,
+///     url: None,
+///     image: None,
+///     release_date: year.to_owned(),
+///     media_type: Some(media_type),
+/// };
+///
+/// let results = vec![
+///     listing("Dune", "1984", TvType::Movie),
+///     listing("Dune: Part Two", "2024", TvType::Movie),
+///     listing("Dune", "2021", TvType::Movie),
+/// ];
+///
+/// let query = MatchQuery::new("Dune").with_year(2021).with_media_type(TvType::Movie);
+/// let best = best_match(&query, &results).unwrap();
+///
+/// assert_eq!(best.candidate.release_date, "2021");
+/// assert_eq!(best.score, 1.0);
+/// ```
+pub fn best_match<'a, T: MatchCandidate>(
+    query: &MatchQuery,
+    candidates: &'a [T],
+) -> Option<Match<'a, T>> {
+    query
+        .rank(candidates)
+        .into_iter()
+        .next()
+        .filter(|best| best.score >= MIN_MATCH_SCORE)
+}
@@ -0,0 +1,277 @@
+use crate::CLIENT;
+
+use anyhow::bail;
+use reqwest::Url;
+use std::sync::RwLock;
+
+/// A ranked list of mirror domains for a provider.
+///
+/// Requests go to the active mirror. When it is dead (DNS, connection or server failure) or
+/// parked (redirected to another site), the other mirrors are probed in rank order and the
+/// first healthy one becomes the active mirror. A probe loads the home page and also checks
+/// that its `<title>` contains the provider's name, which catches parking pages served on
+/// the original domain.
+///
+/// The active mirror is probed on its first use, again after [`Mirrors::set`], and whenever
+/// it answers with a page whose title lacks the provider's name. Redirects within the same
+/// registrable domain (e.g. to `www.`) are followed.
+///
+/// ```
+/// use consumet::utils::mirrors::Mirrors;
+///
+/// static MIRRORS: Mirrors = Mirrors::new(&["https://flixhq.to"], "FlixHQ");
+///
+/// let mirrors = &MIRRORS;
+/// assert_eq!(mirrors.active(), "https://flixhq.to");
+///
+/// mirrors
+///     .set(vec![
+///         String::from("https://flixhq.ws/"),
+///         String::from("https://flixhq.to"),
+///     ])
+///     .unwrap();
+/// assert_eq!(mirrors.domains(), ["https://flixhq.ws", "https://flixhq.to"]);
+/// assert_eq!(mirrors.active(), "https://flixhq.ws");
+///
+/// assert!(mirrors.set(vec![]).is_err());
+/// ```
+pub struct Mirrors {
+    name: &'static str,
+    defaults: &'static [&'static str],
+    state: RwLock<MirrorState>,
+}
+
+struct MirrorState {
+    /// empty until [`Mirrors::set`] replaces the defaults
+    domains: Vec<String>,
+    active: usize,
+    /// whether the active mirror passed a probe
+    verified: bool,
+}
+
+enum Attempt {
+    Done(anyhow::Result<String>),
+    Down(String),
+}
+
+impl Mirrors {
+    /// `domains` are ranked from most to least preferred, `name` is expected in the page title
+    pub const fn new(domains: &'static [&'static str], name: &'static str) -> Self {
+        Self {
+            name,
+            defaults: domains,
+            state: RwLock::new(MirrorState {
+                domains: Vec::new(),
+                active: 0,
+                verified: false,
+            }),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Replaces the ranked list of domains and makes the first one active
+    pub fn set(&self, domains: Vec<String>) -> anyhow::Result<()> {
+        if domains.is_empty() {
+            bail!("At least one mirror domain is required");
+        }
+
+        let mut state = self.state.write().unwrap();
+        state.domains = domains
+            .into_iter()
+            .map(|domain| domain.trim_end_matches('/').to_owned())
+            .collect();
+        state.active = 0;
+        state.verified = false;
+
+        Ok(())
+    }
+
+    pub fn domains(&self) -> Vec<String> {
+        self.snapshot().0
+    }
+
+    /// The domain requests are currently sent to
+    pub fn active(&self) -> String {
+        let (domains, active) = self.snapshot();
+        domains[active].clone()
+    }
+
+    fn snapshot(&self) -> (Vec<String>, usize) {
+        let state = self.state.read().unwrap();
+
+        if state.domains.is_empty() {
+            let defaults = self
+                .defaults
+                .iter()
+                .map(|domain| domain.trim_end_matches('/').to_owned())
+                .collect();
+
+            return (defaults, state.active);
+        }
+
+        (state.domains.clone(), state.active)
+    }
+
+    /// Makes `index` the active mirror, unless another caller replaced the list meanwhile
+    fn activate(&self, domains: &[String], index: usize) {
+        if self.domains() == domains {
+            let mut state = self.state.write().unwrap();
+            state.active = index;
+            state.verified = true;
+        }
+    }
+
+    /// Fetches `path` from the active mirror, failing over to the next healthy mirror when it is down
+    pub async fn get(&self, path: &str) -> anyhow::Result<String> {
+        let (domains, active) = self.snapshot();
+        let verified = self.state.read().unwrap().verified;
+
+        let mut failures = vec![];
+
+        let checked = if verified {
+            Ok(())
+        } else {
+            self.probe(&domains[active]).await
+        };
+
+        match checked {
+            Ok(()) => {
+                if !verified {
+                    self.activate(&domains, active);
+                }
+
+                match self.fetch(&domains[active], path).await {
+                    Attempt::Done(result) => return result,
+                    Attempt::Down(reason) => {
+                        failures.push(format!("{}: {}", domains[active], reason))
+                    }
+                }
+            }
+            Err(reason) => failures.push(format!("{}: {}", domains[active], reason)),
+        }
+
+        for (index, domain) in domains.iter().enumerate() {
+            if index == active {
+                continue;
+            }
+
+            if let Err(reason) = self.probe(domain).await {
+                failures.push(format!("{}: {}", domain, reason));
+                continue;
+            }
+
+            match self.fetch(domain, path).await {
+                Attempt::Done(result) => {
+                    self.activate(&domains, index);
+
+                    return result;
+                }
+                Attempt::Down(reason) => failures.push(format!("{}: {}", domain, reason)),
+            }
+        }
+
+        bail!(
+            "Every {} mirror is down ({})",
+            self.name,
+            failures.join(", ")
+        )
+    }
+
+    /// Fetches `path` from `domain`, probing it when the page does not look like the provider's
+    async fn fetch(&self, domain: &str, path: &str) -> Attempt {
+        let fetched = attempt(domain, path).await;
+
+        // Fragments have no title, a full page should name the provider
+        if let Attempt::Done(Ok(html)) = &fetched {
+            if page_title(html).is_some_and(|title| !self.owns(&title)) {
+                if let Err(reason) = self.probe(domain).await {
+                    return Attempt::Down(reason);
+                }
+            }
+        }
+
+        fetched
+    }
+
+    /// Checks that the home page of `domain` loads and belongs to the provider
+    async fn probe(&self, domain: &str) -> Result<(), String> {
+        let home_html = match attempt(domain, "").await {
+            Attempt::Done(result) => result.map_err(|err| err.to_string())?,
+            Attempt::Down(reason) => return Err(reason),
+        };
+
+        let title = page_title(&home_html).unwrap_or_default();
+
+        if !self.owns(&title) {
+            return Err(format!("unexpected page title {:?}", title));
+        }
+
+        Ok(())
+    }
+
+    fn owns(&self, title: &str) -> bool {
+        title.to_lowercase().contains(&self.name.to_lowercase())
+    }
+}
+
+async fn attempt(domain: &str, path: &str) -> Attempt {
+    let url = format!("{}/{}", domain, path.trim_start_matches('/'));
+
+    let response = match CLIENT.get(&url).send().await {
+        Ok(response) => response,
+        Err(err) if err.is_connect() || err.is_timeout() || err.is_redirect() => {
+            return Attempt::Down(err.to_string())
+        }
+        Err(err) => return Attempt::Done(Err(err.into())),
+    };
+
+    let site = Url::parse(domain)
+        .ok()
+        .and_then(|url| url.host_str().map(registrable_domain));
+
+    if response.url().host_str().map(registrable_domain) != site {
+        return Attempt::Down(format!("redirected to {}", response.url()));
+    }
+
+    // e.g. Cloudflare answering for an origin which is gone
+    if response.status().is_server_error() {
+        return Attempt::Down(format!("responded with {}", response.status()));
+    }
+
+    Attempt::Done(async { Ok(response.error_for_status()?.text().await?) }.await)
+}
+
+/// The part of a host a site registers, e.g. "flixhq.to" for "www.flixhq.to" and
+/// "example.co.uk" for "www.example.co.uk"
+/// ```
+/// use consumet::utils::mirrors::registrable_domain;
+///
+/// assert_eq!(registrable_domain("www.flixhq.to"), "flixhq.to");
+/// assert_eq!(registrable_domain("flixhq.to"), "flixhq.to");
+/// assert_eq!(registrable_domain("www.example.co.uk"), "example.co.uk");
+/// assert_ne!(registrable_domain("flixhq.ws"), registrable_domain("flixhq.to"));
+/// ```
+pub fn registrable_domain(host: &str) -> String {
+    let labels: Vec<&str> = host.trim_end_matches('.').split('.').collect();
+
+    // Country code TLDs often register under a second level, e.g. ".co.uk" or ".com.br"
+    let second_level = matches!(
+        labels.get(labels.len().wrapping_sub(2)).copied(),
+        Some("co" | "com" | "net" | "org" | "gov" | "edu" | "ac")
+    ) && labels.last().is_some_and(|tld| tld.len() == 2);
+
+    let keep = if second_level { 3 } else { 2 };
+
+    labels[labels.len().saturating_sub(keep)..].join(".")
+}
+
+fn page_title(html: &str) -> Option<String> {
+    let start = html.find("<title")?;
+    let start = start + html[start..].find('>')? + 1;
+    let end = start + html[start..].find("</title>")?;
+
+    Some(html[start..end].trim().to_owned())
+}
@@ -1,4 +1,7 @@
 pub mod decrypt;
 pub mod get_comics;
+pub mod matching;
+pub mod mirrors;
 pub mod queries;
+pub mod subtitles;
 pub mod util_funcs;
@@ -1,8 +1,14 @@
-use serde::Serialize;
+use crate::CLIENT;
+
+use anyhow::bail;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashSet;
 
 /// Contains the type of what Anilist could be
-#[derive(Serialize)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
 pub enum AnilistType {
+    #[default]
     #[serde(rename = "ANIME")]
     Anime,
     #[serde(rename = "MANGA")]
@@ -19,128 +25,461 @@ impl std::fmt::Display for AnilistType {
     }
 }
 
+/// A GraphQL request, a static query document along with the variables it declares.
+///
+/// Values never end up in the document itself, so titles such as "JoJo's Bizarre Adventure"
+/// need no escaping.
+/// ```
+/// use consumet::utils::queries::{anilist_search_query, AnilistType};
+///
+/// let request = anilist_search_query("JoJo's Bizarre Adventure", None, None, AnilistType::Anime);
+/// request.validate().unwrap();
+///
+/// assert!(!request.query.contains("JoJo"));
+/// assert_eq!(
+///     serde_json::to_value(&request).unwrap()["variables"],
+///     serde_json::json!({
+///         "search": "JoJo's Bizarre Adventure",
+///         "page": 1,
+///         "size": 20,
+///         "type": "ANIME"
+///     })
+/// );
+/// ```
+#[derive(Clone, Debug, Serialize)]
+pub struct GraphQLRequest<V> {
+    pub query: &'static str,
+    pub variables: V,
+}
+
+impl<V: Serialize> GraphQLRequest<V> {
+    /// Checks that the document is well formed, that it uses exactly the variables it declares
+    /// and that the variables sent along are declared, with every required one present
+    /// ```
+    /// use consumet::utils::queries::{
+    ///     anilist_advanced_query, anilist_airing_schedule_query, anilist_character_query,
+    ///     anilist_genres_query, anilist_media_detail_query, anilist_popular_query,
+    ///     anilist_site_statistics_query, anilist_staff_query, anilist_trending_query,
+    ///     kitsu_search_query, AnilistType, GraphQLRequest,
+    /// };
+    ///
+    /// let mut filters = serde_json::Map::new();
+    /// filters.insert("genres".into(), serde_json::json!(["Action"]));
+    /// anilist_advanced_query(filters).validate().unwrap();
+    ///
+    /// anilist_media_detail_query(21).validate().unwrap();
+    /// anilist_trending_query(Some(2), Some(50), AnilistType::Manga).validate().unwrap();
+    /// anilist_popular_query(None, None, AnilistType::Anime).validate().unwrap();
+    /// anilist_genres_query(vec![String::from("Slice of Life")], None, None).validate().unwrap();
+    /// anilist_airing_schedule_query(None, None, 1692576000, 1693180800, true).validate().unwrap();
+    /// anilist_site_statistics_query().validate().unwrap();
+    /// anilist_character_query(40).validate().unwrap();
+    /// anilist_staff_query(95061).validate().unwrap();
+    /// kitsu_search_query("Cowboy \"Bebop\"", None).validate().unwrap();
+    ///
+    /// let unbalanced = GraphQLRequest {
+    ///     query: "query ($id: Int) { Media(id: $id) { title { romaji } }",
+    ///     variables: serde_json::json!({ "id": 1 }),
+    /// };
+    /// assert!(unbalanced.validate().is_err());
+    ///
+    /// let undeclared = GraphQLRequest {
+    ///     query: "query ($id: Int) { Media(id: $id) { id } }",
+    ///     variables: serde_json::json!({ "id": 1, "search": "bebop" }),
+    /// };
+    /// assert!(undeclared.validate().is_err());
+    ///
+    /// let missing = GraphQLRequest {
+    ///     query: "query ($title: String!) { searchAnimeByTitle(title: $title) { nodes { id } } }",
+    ///     variables: serde_json::json!({}),
+    /// };
+    /// assert!(missing.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let mut open = vec![];
+
+        for c in self.query.chars() {
+            match c {
+                '{' | '(' | '[' => open.push(c),
+                '}' | ')' | ']' => {
+                    let expected = match c {
+                        '}' => '{',
+                        ')' => '(',
+                        _ => '[',
+                    };
+
+                    if open.pop() != Some(expected) {
+                        bail!("Unexpected {} in the query document", c);
+                    }
+                }
+                '"' | '\'' => bail!("The query document should not contain string literals"),
+                _ => {}
+            }
+        }
+
+        if !open.is_empty() {
+            bail!("Unclosed {:?} in the query document", open);
+        }
+
+        let Some(selection_start) = self.query.find('{') else {
+            bail!("The query document has no selection set");
+        };
+        let (header, selection) = self.query.split_at(selection_start);
+
+        // `$name: Type = default` pairs of the operation header
+        let mut declared = HashSet::new();
+        let mut required = HashSet::new();
+
+        for definition in header.split('$').skip(1) {
+            let Some((name, rest)) = definition.split_once(':') else {
+                bail!("Variable ${} has no type", definition.trim());
+            };
+            let (variable_type, default) = match rest.split_once('=') {
+                Some((variable_type, default)) => (variable_type, Some(default)),
+                None => (rest, None),
+            };
+
+            let name = name.trim();
+            if !declared.insert(name) {
+                bail!("Variable ${} is declared twice", name);
+            }
+
+            let variable_type = variable_type.trim().trim_end_matches([',', ')']).trim();
+            if variable_type.ends_with('!') && default.is_none() {
+                required.insert(name);
+            }
+        }
+
+        let used: HashSet<&str> = selection
+            .split('$')
+            .skip(1)
+            .map(|rest| {
+                let end = rest
+                    .find(|c: char| !c.is_alphanumeric() && c != '_')
+                    .unwrap_or(rest.len());
+
+                &rest[..end]
+            })
+            .collect();
+
+        if let Some(name) = used.difference(&declared).next() {
+            bail!("Variable ${} is used but not declared", name);
+        }
+        if let Some(name) = declared.difference(&used).next() {
+            bail!("Variable ${} is declared but not used", name);
+        }
+
+        let variables = match serde_json::to_value(&self.variables)? {
+            Value::Object(variables) => variables,
+            Value::Null => Map::new(),
+            other => bail!("Variables should serialize into an object, not {}", other),
+        };
+
+        if let Some(name) = variables
+            .keys()
+            .find(|name| !declared.contains(name.as_str()))
+        {
+            bail!("Variable ${} is not declared by the query document", name);
+        }
+        if let Some(name) = required
+            .iter()
+            .find(|name| variables.get(**name).is_none_or(Value::is_null))
+        {
+            bail!("Required variable ${} is missing", name);
+        }
+
+        Ok(())
+    }
+
+    /// Posts the request to a GraphQL endpoint and unwraps the `data` of the response
+    pub(crate) async fn send<T: DeserializeOwned>(&self, url: &str) -> anyhow::Result<T> {
+        // invalid queries are answered with a 400 and the reason in `errors`
+        let response: GraphQLResponse<T> = CLIENT.post(url).json(self).send().await?.json().await?;
+
+        if !response.errors.is_empty() {
+            let messages: Vec<String> = response
+                .errors
+                .into_iter()
+                .map(|error| error.message)
+                .collect();
+
+            bail!("{} responded with {}", url, messages.join(", "));
+        }
+
+        match response.data {
+            Some(data) => Ok(data),
+            None => bail!("{} responded without data", url),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GraphQLResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphQLError>,
+}
+
+#[derive(Deserialize)]
+struct GraphQLError {
+    message: String,
+}
+
+/// Variables of [`anilist_search_query`]
+#[derive(Clone, Debug, Serialize)]
+pub struct AnilistSearchVariables {
+    pub search: String,
+    pub page: u32,
+    pub size: u32,
+    #[serde(rename = "type")]
+    pub media_type: AnilistType,
+}
+
+/// Variables of [`anilist_trending_query`] and [`anilist_popular_query`]
+#[derive(Clone, Debug, Serialize)]
+pub struct AnilistPageVariables {
+    pub page: u32,
+    pub size: u32,
+    #[serde(rename = "type")]
+    pub media_type: AnilistType,
+}
+
+/// Variables of [`anilist_genres_query`]
+#[derive(Clone, Debug, Serialize)]
+pub struct AnilistGenresVariables {
+    pub genres: Vec<String>,
+    pub page: u32,
+    pub size: u32,
+}
+
+/// Variables of [`anilist_airing_schedule_query`]
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnilistAiringScheduleVariables {
+    pub page: u32,
+    pub size: u32,
+    pub week_start: i64,
+    pub week_end: i64,
+    /// `Some(false)` would keep only the episodes which already aired, so no filter is sent instead
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub not_yet_aired: Option<bool>,
+}
+
+/// Variables of the queries fetching a single Anilist entry
+#[derive(Clone, Debug, Serialize)]
+pub struct AnilistIdVariables {
+    pub id: u32,
+}
+
+/// Variables of [`kitsu_search_query`]
+#[derive(Clone, Debug, Serialize)]
+pub struct KitsuSearchVariables {
+    pub title: String,
+    pub first: u32,
+}
+
+const ANILIST_ADVANCED_QUERY: &str = "query ($page: Int, $id: Int, $type: MediaType, $isAdult: Boolean = false, $search: String, $format: [MediaFormat], $status: MediaStatus, $size: Int, $countryOfOrigin: CountryCode, $source: MediaSource, $season: MediaSeason, $seasonYear: Int, $year: String, $onList: Boolean, $yearLesser: FuzzyDateInt, $yearGreater: FuzzyDateInt, $episodeLesser: Int, $episodeGreater: Int, $durationLesser: Int, $durationGreater: Int, $chapterLesser: Int, $chapterGreater: Int, $volumeLesser: Int, $volumeGreater: Int, $licensedBy: [String], $isLicensed: Boolean, $genres: [String], $excludedGenres: [String], $tags: [String], $excludedTags: [String], $minimumTagRank: Int, $sort: [MediaSort] = [POPULARITY_DESC, SCORE_DESC]) { Page(page: $page, perPage: $size) { pageInfo { total perPage currentPage lastPage hasNextPage } media(id: $id, type: $type, season: $season, format_in: $format, status: $status, countryOfOrigin: $countryOfOrigin, source: $source, search: $search, onList: $onList, seasonYear: $seasonYear, startDate_like: $year, startDate_lesser: $yearLesser, startDate_greater: $yearGreater, episodes_lesser: $episodeLesser, episodes_greater: $episodeGreater, duration_lesser: $durationLesser, duration_greater: $durationGreater, chapters_lesser: $chapterLesser, chapters_greater: $chapterGreater, volumes_lesser: $volumeLesser, volumes_greater: $volumeGreater, licensedBy_in: $licensedBy, isLicensed: $isLicensed, genre_in: $genres, genre_not_in: $excludedGenres, tag_in: $tags, tag_not_in: $excludedTags, minimumTagRank: $minimumTagRank, sort: $sort, isAdult: $isAdult) { id idMal type status(version: 2) title { userPreferred romaji english native } bannerImage coverImage { extraLarge large medium color } episodes season popularity description format seasonYear genres averageScore countryOfOrigin nextAiringEpisode { airingAt timeUntilAiring episode } } } }";
+
+const ANILIST_SEARCH_QUERY: &str = "query ($page: Int = 1, $id: Int, $type: MediaType = ANIME, $search: String, $isAdult: Boolean = false, $size: Int = 20) { Page(page: $page, perPage: $size) { pageInfo { total perPage currentPage lastPage hasNextPage } media(id: $id, type: $type, search: $search, isAdult: $isAdult) { id idMal type status(version: 2) title { userPreferred romaji english native } bannerImage popularity coverImage { extraLarge large medium color } episodes format season description seasonYear chapters volumes averageScore genres nextAiringEpisode { airingAt timeUntilAiring episode } } } }";
+
+const ANILIST_MEDIA_DETAIL_QUERY: &str = "query ($id: Int) { Media(id: $id) { id idMal title { english native romaji userPreferred } synonyms countryOfOrigin isLicensed isAdult externalLinks { url site type language } coverImage { extraLarge large color } startDate { year month day } endDate { year month day } bannerImage season seasonYear description type format status(version: 2) episodes duration chapters volumes trailer { id site thumbnail } genres source averageScore popularity meanScore nextAiringEpisode { airingAt timeUntilAiring episode } characters(sort: ROLE) { edges { role node { id name { first middle last full native userPreferred } image { large medium } } voiceActors(sort: LANGUAGE) { id languageV2 name { first middle last full native userPreferred } image { large medium } } } } recommendations { edges { node { id mediaRecommendation { id idMal title { romaji english native userPreferred } status episodes coverImage { extraLarge large medium color } bannerImage format chapters meanScore nextAiringEpisode { episode timeUntilAiring airingAt } } } } } relations { edges { id relationType node { id idMal status coverImage { extraLarge large medium color } bannerImage title { romaji english native userPreferred } episodes chapters format nextAiringEpisode { airingAt timeUntilAiring episode } meanScore } } } studios(isMain: true) { edges { isMain node { id name } } } } }";
+
+const ANILIST_TRENDING_QUERY: &str = "query ($page: Int = 1, $id: Int, $type: MediaType = ANIME, $isAdult: Boolean = false, $size: Int = 20, $sort: [MediaSort] = [TRENDING_DESC, POPULARITY_DESC]) { Page(page: $page, perPage: $size) { pageInfo { total perPage currentPage lastPage hasNextPage } media(id: $id, type: $type, isAdult: $isAdult, sort: $sort) { id idMal type status(version: 2) title { userPreferred romaji english native } genres trailer { id site thumbnail } description format bannerImage coverImage { extraLarge large medium color } episodes meanScore duration season seasonYear averageScore nextAiringEpisode { airingAt timeUntilAiring episode } } } }";
+
+const ANILIST_POPULAR_QUERY: &str = "query ($page: Int = 1, $id: Int, $type: MediaType = ANIME, $isAdult: Boolean = false, $size: Int = 20, $sort: [MediaSort] = [POPULARITY_DESC]) { Page(page: $page, perPage: $size) { pageInfo { total perPage currentPage lastPage hasNextPage } media(id: $id, type: $type, isAdult: $isAdult, sort: $sort) { id idMal type status(version: 2) title { userPreferred romaji english native } trailer { id site thumbnail } format genres bannerImage description coverImage { extraLarge large medium color } episodes meanScore duration season seasonYear averageScore nextAiringEpisode { airingAt timeUntilAiring episode } } } }";
+
+const ANILIST_GENRES_QUERY: &str = "query ($genres: [String], $page: Int = 1, $type: MediaType = ANIME, $isAdult: Boolean = false, $size: Int = 20) { Page(page: $page, perPage: $size) { pageInfo { total perPage currentPage lastPage hasNextPage } media(type: $type, isAdult: $isAdult, genre_in: $genres) { id idMal status(version: 2) title { userPreferred romaji english native } trailer { id site thumbnail } format bannerImage description coverImage { extraLarge large medium color } episodes meanScore duration season seasonYear averageScore nextAiringEpisode { airingAt timeUntilAiring episode } } } }";
+
+const ANILIST_AIRING_SCHEDULE_QUERY: &str = "query ($page: Int = 1, $size: Int = 20, $notYetAired: Boolean, $weekStart: Int, $weekEnd: Int) { Page(page: $page, perPage: $size) { pageInfo { total perPage currentPage lastPage hasNextPage } airingSchedules(notYetAired: $notYetAired, airingAt_greater: $weekStart, airingAt_lesser: $weekEnd, sort: TIME) { airingAt timeUntilAiring episode media { id idMal title { romaji english userPreferred native } countryOfOrigin description popularity bannerImage coverImage { extraLarge large medium color } genres averageScore seasonYear format } } } }";
+
+const ANILIST_SITE_STATISTICS_QUERY: &str =
+    "query { SiteStatistics { anime(sort: DATE_DESC, perPage: 1) { nodes { date count } } } }";
+
+const ANILIST_CHARACTER_QUERY: &str = "query character($id: Int) { Character(id: $id) { id name { first middle last full native userPreferred alternative alternativeSpoiler } image { large medium } description gender dateOfBirth { year month day } bloodType age favourites media { edges { characterRole node { id idMal title { romaji english native userPreferred } coverImage { extraLarge large medium color } averageScore startDate { year month day } episodes format status } } } } }";
+
+const ANILIST_STAFF_QUERY: &str = "query staff($id: Int, $sort: [MediaSort], $characterPage: Int, $staffPage: Int, $onList: Boolean, $type: MediaType, $withCharacterRoles: Boolean = false, $withStaffRoles: Boolean = false) { Staff(id: $id) { id name { first middle last full native userPreferred alternative } image { large } description favourites isFavourite isFavouriteBlocked age gender yearsActive homeTown bloodType primaryOccupations dateOfBirth { year month day } dateOfDeath { year month day } language: languageV2 characterMedia(page: $characterPage, sort: $sort, onList: $onList) @include(if: $withCharacterRoles) { pageInfo { total perPage currentPage lastPage hasNextPage } edges { characterRole characterName node { id type bannerImage isAdult title { userPreferred } coverImage { large } startDate { year } mediaListEntry { id status } } characters { id name { userPreferred } image { large } } } } staffMedia(page: $staffPage, type: $type, sort: $sort, onList: $onList) @include(if: $withStaffRoles) { pageInfo { total perPage currentPage lastPage hasNextPage } edges { staffRole node { id type isAdult title { userPreferred } coverImage { large } mediaListEntry { id status } } } } } }";
+
+const KITSU_SEARCH_QUERY: &str = "query ($title: String!, $first: Int = 20) { searchAnimeByTitle(first: $first, title: $title) { totalCount pageInfo { hasNextPage } nodes { id slug season startDate status subtype averageRating episodeCount titles { canonical localized romanized original } posterImage { original { url } } bannerImage { original { url } } } } }";
+
 /// Advanced Query
 /// # Parameters
-/// * `None`
-pub fn anilist_advanced_query() -> &'static str {
-    "query ($page: Int, $id: Int, $type: MediaType, $isAdult: Boolean = false, $search: String, $format: [MediaFormat], $status: MediaStatus, $size: Int, $countryOfOrigin: CountryCode, $source: MediaSource, $season: MediaSeason, $seasonYear: Int, $year: String, $onList: Boolean, $yearLesser: FuzzyDateInt, $yearGreater: FuzzyDateInt, $episodeLesser: Int, $episodeGreater: Int, $durationLesser: Int, $durationGreater: Int, $chapterLesser: Int, $chapterGreater: Int, $volumeLesser: Int, $volumeGreater: Int, $licensedBy: [String], $isLicensed: Boolean, $genres: [String], $excludedGenres: [String], $tags: [String], $excludedTags: [String], $minimumTagRank: Int, $sort: [MediaSort] = [POPULARITY_DESC, SCORE_DESC]) {{ Page(page: $page, perPage: $size) {{ pageInfo {{ total perPage currentPage lastPage hasNextPage }} media(id: $id, type: $type, season: $season, format_in: $format, status: $status, countryOfOrigin: $countryOfOrigin, source: $source, search: $search, onList: $onList, seasonYear: $seasonYear, startDate_like: $year, startDate_lesser: $yearLesser, startDate_greater: $yearGreater, episodes_lesser: $episodeLesser, episodes_greater: $episodeGreater, duration_lesser: $durationLesser, duration_greater: $durationGreater, chapters_lesser: $chapterLesser, chapters_greater: $chapterGreater, volumes_lesser: $volumeLesser, volumes_greater: $volumeGreater, licensedBy_in: $licensedBy, isLicensed: $isLicensed, genre_in: $genres, genre_not_in: $excludedGenres, tag_in: $tags, tag_not_in: $excludedTags, minimumTagRank: $minimumTagRank, sort: $sort, isAdult: $isAdult) {{  id idMal status(version: 2) title {{ userPreferred romaji english native } bannerImage coverImage{{ extraLarge large medium color }} episodes season popularity description format seasonYear genres averageScore countryOfOrigin nextAiringEpisode {{ airingAt timeUntilAiring episode }}  }} }} }}"
+/// * `variables` - any of the variables the document declares, e.g. `genres`, `season`, `sort`
+pub fn anilist_advanced_query<V: Serialize>(variables: V) -> GraphQLRequest<V> {
+    GraphQLRequest {
+        query: ANILIST_ADVANCED_QUERY,
+        variables,
+    }
 }
 
 /// Search Query
 /// # Parameters
 /// * `query` - query to search for.
 /// * `page` - page number (default: 1)
-/// * `per_page` - number of results per page (default: 25)
+/// * `per_page` - number of results per page (default: 20)
 /// * `query_type` - Either AnilistType::Anime or AnilistType::Manga
 pub fn anilist_search_query(
-    query: String,
+    query: &str,
     page: Option<u32>,
     per_page: Option<u32>,
     query_type: AnilistType,
-) -> String {
-    let page = page.unwrap_or(1);
-    let per_page = per_page.unwrap_or(20);
-
-    format!("query ($page: Int = {page}, $id: Int, $type: MediaType = {query_type}, $search: String = '{query}', $isAdult: Boolean = false, $size: Int = {per_page}) {{ Page(page: $page, perPage: $size) {{ pageInfo {{ total perPage currentPage lastPage hasNextPage }} media(id: $id, type: $type, search: $search, isAdult: $isAdult) {{ id idMal status(version: 2) title {{ userPreferred romaji english native }} bannerImage popularity coverImage{{ extraLarge large medium color }} episodes format season description seasonYear chapters volumes averageScore genres nextAiringEpisode {{ airingAt timeUntilAiring episode }}  }} }} }}")
+) -> GraphQLRequest<AnilistSearchVariables> {
+    GraphQLRequest {
+        query: ANILIST_SEARCH_QUERY,
+        variables: AnilistSearchVariables {
+            search: query.to_owned(),
+            page: page.unwrap_or(1),
+            size: per_page.unwrap_or(20),
+            media_type: query_type,
+        },
+    }
 }
 
 /// Media Detail Query
 /// # Parameters
 /// * `id` - id to fetch info for.
-pub fn anilist_media_detail_query(id: String) -> String {
-    format!("query ($id: Int = {id}) {{ Media(id: $id) {{ id idMal title {{ english native romaji }} synonyms countryOfOrigin isLicensed isAdult externalLinks {{ url site type language }} coverImage {{ extraLarge large color }} startDate {{ year month day }} endDate {{ year month day }} bannerImage season seasonYear description type format status(version: 2) episodes duration chapters volumes trailer {{ id site thumbnail }} genres source averageScore popularity meanScore nextAiringEpisode {{ airingAt timeUntilAiring episode }} characters(sort: ROLE) {{ edges {{ role node {{ id name {{ first middle last full native userPreferred }} image {{ large medium }} }} voiceActors(sort: LANGUAGE) {{ id languageV2 name {{ first middle last full native userPreferred }} image {{ large medium }} }} }} }} recommendations {{ edges {{ node {{ id mediaRecommendation {{ id idMal title {{ romaji english native userPreferred }} status episodes coverImage {{ extraLarge large medium color }} bannerImage format chapters meanScore nextAiringEpisode {{ episode timeUntilAiring airingAt }} }} }} }} }} relations {{ edges {{ id relationType node {{ id idMal status coverImage {{ extraLarge large medium color }} bannerImage title {{ romaji english native userPreferred }} episodes chapters format nextAiringEpisode {{ airingAt timeUntilAiring episode }} meanScore }} }} }} studios(isMain: true) {{ edges {{ isMain node {{ id name }} }} }} }} }}")
+pub fn anilist_media_detail_query(id: u32) -> GraphQLRequest<AnilistIdVariables> {
+    GraphQLRequest {
+        query: ANILIST_MEDIA_DETAIL_QUERY,
+        variables: AnilistIdVariables { id },
+    }
 }
 
 /// Anilist Trending Query
 /// * `page` - page number (default: 1)
-/// * `per_page` - number of results per page (default: 25)
+/// * `per_page` - number of results per page (default: 20)
 /// * `query_type` - Either AnilistType::Anime or AnilistType::Manga
 pub fn anilist_trending_query(
     page: Option<u32>,
     per_page: Option<u32>,
     query_type: AnilistType,
-) -> String {
-    let page = page.unwrap_or(1);
-    let per_page = per_page.unwrap_or(20);
-
-    format!("query ($page: Int = {page}, $id: Int, $type: MediaType = {query_type}, $isAdult: Boolean = false, $size: Int = {per_page}, $sort: [MediaSort] = [TRENDING_DESC, POPULARITY_DESC]) {{ Page(page: $page, perPage: $size) {{ pageInfo {{ total perPage currentPage lastPage hasNextPage }} media(id: $id, type: $type, isAdult: $isAdult, sort: $sort) {{ id idMal status(version: 2) title {{ userPreferred romaji english native }} genres trailer {{ id site thumbnail }} description format bannerImage coverImage{{ extraLarge large medium color }} episodes meanScore duration season seasonYear averageScore nextAiringEpisode {{ airingAt timeUntilAiring episode }}  }} }} }}")
+) -> GraphQLRequest<AnilistPageVariables> {
+    GraphQLRequest {
+        query: ANILIST_TRENDING_QUERY,
+        variables: AnilistPageVariables {
+            page: page.unwrap_or(1),
+            size: per_page.unwrap_or(20),
+            media_type: query_type,
+        },
+    }
 }
 
 /// Anilist Popular Query
 /// * `page` - page number (default: 1)
-/// * `per_page` - number of results per page (default: 25)
+/// * `per_page` - number of results per page (default: 20)
 /// * `query_type` - Either AnilistType::Anime or AnilistType::Manga
 pub fn anilist_popular_query(
     page: Option<u32>,
     per_page: Option<u32>,
     query_type: AnilistType,
-) -> String {
-    let page = page.unwrap_or(1);
-    let per_page = per_page.unwrap_or(20);
-
-    format!("query ($page: Int = {page}, $id: Int, $type: MediaType = {query_type}, $isAdult: Boolean = false, $size: Int = ${per_page}, $sort: [MediaSort] = [POPULARITY_DESC]) {{ Page(page: $page, perPage: $size) {{ pageInfo {{ total perPage currentPage lastPage hasNextPage }} media(id: $id, type: $type, isAdult: $isAdult, sort: $sort) {{ id idMal status(version: 2) title {{ userPreferred romaji english native }} trailer {{ id site thumbnail }} format genres bannerImage description coverImage {{ extraLarge large medium color }} episodes meanScore duration season seasonYear averageScore nextAiringEpisode {{ airingAt timeUntilAiring episode }}  }} }} }}")
+) -> GraphQLRequest<AnilistPageVariables> {
+    GraphQLRequest {
+        query: ANILIST_POPULAR_QUERY,
+        variables: AnilistPageVariables {
+            page: page.unwrap_or(1),
+            size: per_page.unwrap_or(20),
+            media_type: query_type,
+        },
+    }
 }
 
 /// Anlist Genre Query
+/// * `genres` - anime in any of these genres
 /// * `page` - page number (default: 1)
-/// * `per_page` - number of results per page (default: 25)
-/// * `query_type` - Either AnilistType::Anime or AnilistType::Manga
-
+/// * `per_page` - number of results per page (default: 20)
 pub fn anilist_genres_query(
     genres: Vec<String>,
     page: Option<u32>,
     per_page: Option<u32>,
-) -> String {
-    let page = page.unwrap_or(1);
-    let per_page = per_page.unwrap_or(20);
-    let genres = serde_json::to_string(&genres).unwrap();
-
-    format!("query ($genres: [String] = {genres}, $page: Int = ${page}, $type: MediaType = ANIME, $isAdult: Boolean = false, $size: Int = ${per_page}) {{Page(page: $page, perPage: $size) {{ pageInfo {{ total perPage currentPage lastPage hasNextPage }} media(type: $type, isAdult: $isAdult, genre_in: $genres) {{ id idMal status(version: 2) title {{ userPreferred romaji english native }} trailer {{ id site thumbnail }} format bannerImage description coverImage {{ extraLarge large medium color }} episodes meanScore duration season seasonYear averageScore nextAiringEpisode {{ airingAt timeUntilAiring episode }}  }} }} }}")
+) -> GraphQLRequest<AnilistGenresVariables> {
+    GraphQLRequest {
+        query: ANILIST_GENRES_QUERY,
+        variables: AnilistGenresVariables {
+            genres,
+            page: page.unwrap_or(1),
+            size: per_page.unwrap_or(20),
+        },
+    }
 }
 
 /// Anilist Airing Schedule Query
 /// * `page` - page number (default: 1)
-/// * `per_page` - number of results per page (default: 25)
-/// * `week_start` - where the week started 1-6
-/// * `week_end` - where the week ends 1-6
-/// * `not_yet_aired` - check if its available yet
+/// * `per_page` - number of results per page (default: 20)
+/// * `week_start` - Unix timestamp the episodes air after
+/// * `week_end` - Unix timestamp the episodes air before
+/// * `not_yet_aired` - only list episodes which have not aired yet, `false` lists both
+/// ```
+/// use consumet::utils::queries::anilist_airing_schedule_query;
+///
+/// let every_episode = anilist_airing_schedule_query(None, None, 0, 604_800, false);
+/// let variables = serde_json::to_value(&every_episode.variables).unwrap();
+/// assert!(variables.get("notYetAired").is_none());
+///
+/// let upcoming = anilist_airing_schedule_query(None, None, 0, 604_800, true);
+/// let variables = serde_json::to_value(&upcoming.variables).unwrap();
+/// assert_eq!(variables["notYetAired"], true);
+/// ```
 pub fn anilist_airing_schedule_query(
     page: Option<u32>,
     per_page: Option<u32>,
-    week_start: u32,
-    week_end: u32,
+    week_start: i64,
+    week_end: i64,
     not_yet_aired: bool,
-) -> String {
-    let page = page.unwrap_or(1);
-    let per_page = per_page.unwrap_or(20);
-
-    format!("query {{ Page(page: ${page}, perPage: ${per_page}) {{ pageInfo {{ total perPage currentPage lastPage hasNextPage }} airingSchedules( notYetAired: ${not_yet_aired}, airingAt_greater: ${week_start}, airingAt_lesser: ${week_end}) {{ airingAt episode media {{ id description idMal title {{ romaji english userPreferred native }} countryOfOrigin description popularity bannerImage coverImage {{ extraLarge large medium color }} genres averageScore seasonYear format }} }} }} }}")
+) -> GraphQLRequest<AnilistAiringScheduleVariables> {
+    GraphQLRequest {
+        query: ANILIST_AIRING_SCHEDULE_QUERY,
+        variables: AnilistAiringScheduleVariables {
+            page: page.unwrap_or(1),
+            size: per_page.unwrap_or(20),
+            week_start,
+            week_end,
+            not_yet_aired: not_yet_aired.then_some(true),
+        },
+    }
 }
 
 /// Anilist Site Statistics Query
 /// # Parameters
 /// * `None`
-pub fn anilist_site_statistics_query() -> &'static str {
-    "query { SiteStatistics { anime { nodes { count } } } }"
+pub fn anilist_site_statistics_query() -> GraphQLRequest<Map<String, Value>> {
+    GraphQLRequest {
+        query: ANILIST_SITE_STATISTICS_QUERY,
+        variables: Map::new(),
+    }
 }
 
-/// Anilist Chracter Query
+/// Anilist Character Query
 /// # Parameters
-/// * `None`
-pub fn anilist_character_query() -> &'static str {
-    "query character($id: Int) { Character(id: $id) { id name { first middle last full native userPreferred alternative alternativeSpoiler } image { large medium } description gender dateOfBirth { year month day } bloodType age favourites media { edges { characterRole node { id idMal title { romaji english native userPreferred } coverImage { extraLarge large medium color } averageScore startDate { year month day } episodes format status } } } } }"
+/// * `id` - id of the character
+pub fn anilist_character_query(id: u32) -> GraphQLRequest<AnilistIdVariables> {
+    GraphQLRequest {
+        query: ANILIST_CHARACTER_QUERY,
+        variables: AnilistIdVariables { id },
+    }
 }
 
 /// Anilist Staff Query
 /// # Parameters
-/// * `None`
-pub fn anilist_staff_query() -> &'static str {
-    "query staff($id: Int, $sort: [MediaSort], $characterPage: Int, $staffPage: Int, $onList: Boolean, $type: MediaType, $withCharacterRoles: Boolean = false, $withStaffRoles: Boolean = false) { Staff(id: $id) { id name { first middle last full native userPreferred alternative } image { large } description favourites isFavourite isFavouriteBlocked age gender yearsActive homeTown bloodType primaryOccupations dateOfBirth { year month day } dateOfDeath { year month day } language: languageV2 characterMedia(page: $characterPage, sort: $sort, onList: $onList) @include(if: $withCharacterRoles) { pageInfo { total perPage currentPage lastPage hasNextPage } edges { characterRole characterName node { id type bannerImage isAdult title { userPreferred } coverImage { large } startDate { year } mediaListEntry { id status } } characters { id name { userPreferred } image { large } } } } staffMedia(page: $staffPage, type: $type, sort: $sort, onList: $onList) @include(if: $withStaffRoles) { pageInfo { total perPage currentPage lastPage hasNextPage } edges { staffRole node { id type isAdult title { userPreferred } coverImage { large } mediaListEntry { id status } } } } } }"
+/// * `id` - id of the staff member
+pub fn anilist_staff_query(id: u32) -> GraphQLRequest<AnilistIdVariables> {
+    GraphQLRequest {
+        query: ANILIST_STAFF_QUERY,
+        variables: AnilistIdVariables { id },
+    }
 }
 
 /// Kitsu Search query
 /// # Parameters
 /// * `query` - query to search for.
-pub fn kitsu_search_query(query: String) -> String {
-    format!("query{{ searchAnimeByTitle(first:5, title:'{query}'){{ nodes {{id season startDate titles {{ localized }} episodes(first: 2000){{ nodes {{ number createdAt titles {{ canonical }} description thumbnail {{ original {{ url }} }} }} }} }} }} }}")
+/// * `per_page` - number of results (default: 20)
+pub fn kitsu_search_query(
+    query: &str,
+    per_page: Option<u32>,
+) -> GraphQLRequest<KitsuSearchVariables> {
+    GraphQLRequest {
+        query: KITSU_SEARCH_QUERY,
+        variables: KitsuSearchVariables {
+            title: query.to_owned(),
+            first: per_page.unwrap_or(20),
+        },
+    }
 }
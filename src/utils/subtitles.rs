@@ -0,0 +1,521 @@
+use crate::CLIENT;
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Subtitle formats that can be parsed and written
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum SubtitleFormat {
+    Vtt,
+    Srt,
+    Ass,
+}
+
+impl std::fmt::Display for SubtitleFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let extension = match self {
+            Self::Vtt => "vtt",
+            Self::Srt => "srt",
+            Self::Ass => "ass",
+        };
+
+        write!(f, "{}", extension)
+    }
+}
+
+impl SubtitleFormat {
+    /// Guesses the format of a subtitle file from its contents
+    /// ```
+    /// use consumet::utils::subtitles::SubtitleFormat;
+    ///
+    /// assert_eq!(SubtitleFormat::detect("WEBVTT\n\n"), Some(SubtitleFormat::Vtt));
+    /// assert_eq!(SubtitleFormat::detect("[Script Info]\n"), Some(SubtitleFormat::Ass));
+    /// assert_eq!(
+    ///     SubtitleFormat::detect("1\n00:00:01,000 --> 00:00:02,000\nHi\n"),
+    ///     Some(SubtitleFormat::Srt)
+    /// );
+    /// ```
+    pub fn detect(text: &str) -> Option<Self> {
+        let text = text.trim_start_matches('\u{feff}').trim_start();
+
+        if text.starts_with("WEBVTT") {
+            Some(Self::Vtt)
+        } else if text.starts_with("[Script Info]") || text.contains("\nDialogue:") {
+            Some(Self::Ass)
+        } else if text.lines().any(|line| line.contains("-->")) {
+            Some(Self::Srt)
+        } else {
+            None
+        }
+    }
+}
+
+/// A single timed line of subtitle text
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Cue {
+    pub start: Duration,
+    pub end: Duration,
+    pub text: String,
+}
+
+/// Contains the cues of a parsed subtitle track
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SubtitleTrack {
+    /// The format the track was parsed from
+    pub format: SubtitleFormat,
+    pub cues: Vec<Cue>,
+}
+
+impl SubtitleTrack {
+    /// Downloads a subtitle track (e.g. the `url` of a `VidCloudSubtitle` or `ISubtitle`) and parses it
+    pub async fn fetch(url: &str) -> anyhow::Result<Self> {
+        let text = CLIENT.get(url).send().await?.text().await?;
+
+        Self::parse(&text)
+    }
+
+    /// Parses a track, detecting its format from the contents
+    pub fn parse(text: &str) -> anyhow::Result<Self> {
+        let format = SubtitleFormat::detect(text)
+            .ok_or_else(|| anyhow::anyhow!("Unable to detect the subtitle format"))?;
+
+        Self::parse_as(text, format)
+    }
+
+    /// Parses a track in the given format
+    /// ```
+    /// use consumet::utils::subtitles::{SubtitleFormat, SubtitleTrack};
+    ///
+    /// let srt = "1\n00:00:01,500 --> 00:00:03,000\n<i>Hello</i>\nthere\n";
+    /// let track = SubtitleTrack::parse_as(srt, SubtitleFormat::Srt).unwrap();
+    ///
+    /// assert_eq!(track.cues[0].text, "<i>Hello</i>\nthere");
+    /// assert_eq!(
+    ///     track.to_vtt(),
+    ///     "WEBVTT\n\n00:00:01.500 --> 00:00:03.000\n<i>Hello</i>\nthere\n"
+    /// );
+    ///
+    /// // Malformed timestamps are errors rather than panics
+    /// let srt = "1\n00:00:01,5é --> 00:00:03,000\nHi\n";
+    /// assert!(SubtitleTrack::parse_as(srt, SubtitleFormat::Srt).is_err());
+    /// ```
+    pub fn parse_as(text: &str, format: SubtitleFormat) -> anyhow::Result<Self> {
+        let text = text.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+
+        let cues = match format {
+            SubtitleFormat::Vtt | SubtitleFormat::Srt => parse_blocks(&text)?,
+            SubtitleFormat::Ass => parse_ass(&text)?,
+        };
+
+        Ok(Self { format, cues })
+    }
+
+    /// Moves every cue by `offset_ms` milliseconds, a negative offset makes the subtitles show earlier.
+    /// Cues that would start before `0` are clamped to it.
+    /// ```
+    /// use consumet::utils::subtitles::{SubtitleFormat, SubtitleTrack};
+    /// use std::time::Duration;
+    ///
+    /// let vtt = "WEBVTT\n\n00:01.000 --> 00:02.000\nHi\n";
+    /// let mut track = SubtitleTrack::parse_as(vtt, SubtitleFormat::Vtt).unwrap();
+    ///
+    /// track.shift(-1500);
+    ///
+    /// assert_eq!(track.cues[0].start, Duration::ZERO);
+    /// assert_eq!(track.cues[0].end, Duration::from_millis(500));
+    /// ```
+    pub fn shift(&mut self, offset_ms: i64) {
+        let offset = Duration::from_millis(offset_ms.unsigned_abs());
+
+        for cue in self.cues.iter_mut() {
+            if offset_ms >= 0 {
+                cue.start += offset;
+                cue.end += offset;
+            } else {
+                cue.start = cue.start.saturating_sub(offset);
+                cue.end = cue.end.saturating_sub(offset);
+            }
+        }
+    }
+
+    /// Writes the track in the given format
+    pub fn convert(&self, format: SubtitleFormat) -> String {
+        match format {
+            SubtitleFormat::Vtt => self.to_vtt(),
+            SubtitleFormat::Srt => self.to_srt(),
+            SubtitleFormat::Ass => self.to_ass(),
+        }
+    }
+
+    pub fn to_vtt(&self) -> String {
+        let mut vtt = String::from("WEBVTT\n");
+
+        for cue in &self.cues {
+            vtt.push_str(&format!(
+                "\n{} --> {}\n{}\n",
+                format_timestamp(cue.start, '.'),
+                format_timestamp(cue.end, '.'),
+                cue.text
+            ));
+        }
+
+        vtt
+    }
+
+    /// ```
+    /// use consumet::utils::subtitles::{SubtitleFormat, SubtitleTrack};
+    ///
+    /// let ass = "[Events]\nFormat: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\nDialogue: 0,0:00:01.00,0:00:02.50,Default,,0,0,0,,{\\i1}Hi,{\\i0} you\\Nthere\n";
+    /// let track = SubtitleTrack::parse_as(ass, SubtitleFormat::Ass).unwrap();
+    ///
+    /// assert_eq!(track.to_srt(), "1\n00:00:01,000 --> 00:00:02,500\nHi, you\nthere\n");
+    /// ```
+    pub fn to_srt(&self) -> String {
+        self.cues
+            .iter()
+            .enumerate()
+            .map(|(i, cue)| {
+                format!(
+                    "{}\n{} --> {}\n{}\n",
+                    i + 1,
+                    format_timestamp(cue.start, ','),
+                    format_timestamp(cue.end, ','),
+                    cue.text
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn to_ass(&self) -> String {
+        let mut ass = String::from(ASS_HEADER);
+
+        for cue in &self.cues {
+            ass.push_str(&format!(
+                "Dialogue: 0,{},{},Default,,0,0,0,,{}\n",
+                format_ass_timestamp(cue.start),
+                format_ass_timestamp(cue.end),
+                strip_tags(&cue.text).replace('\n', "\\N")
+            ));
+        }
+
+        ass
+    }
+}
+
+const ASS_HEADER: &str = "[Script Info]
+ScriptType: v4.00+
+PlayResX: 1920
+PlayResY: 1080
+
+[V4+ Styles]
+Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding
+Style: Default,Arial,64,&H00FFFFFF,&H000000FF,&H00000000,&H80000000,0,0,0,0,100,100,0,0,1,3,0,2,60,60,50,1
+
+[Events]
+Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text
+";
+
+/// Parses the blank line separated cue blocks shared by WebVTT and SRT
+fn parse_blocks(text: &str) -> anyhow::Result<Vec<Cue>> {
+    let mut cues = vec![];
+
+    for block in text.split("\n\n") {
+        let mut lines = block.lines().skip_while(|line| !line.contains("-->"));
+
+        let Some(timing) = lines.next() else {
+            // Headers, NOTE, STYLE and REGION blocks have no timing line
+            continue;
+        };
+
+        let (start, end) = timing
+            .split_once("-->")
+            .ok_or_else(|| anyhow::anyhow!("Invalid cue timing: {}", timing))?;
+
+        // WebVTT cue settings follow the end timestamp
+        let end = end.split_whitespace().next().unwrap_or_default();
+
+        cues.push(Cue {
+            start: parse_timestamp(start.trim())?,
+            end: parse_timestamp(end)?,
+            text: lines.collect::<Vec<_>>().join("\n"),
+        });
+    }
+
+    Ok(cues)
+}
+
+fn parse_ass(text: &str) -> anyhow::Result<Vec<Cue>> {
+    let mut cues = vec![];
+    let mut fields: Vec<String> = vec![];
+
+    for line in text.lines() {
+        if let Some(format) = line.strip_prefix("Format:") {
+            fields = format.split(',').map(|s| s.trim().to_lowercase()).collect();
+        } else if let Some(dialogue) = line.strip_prefix("Dialogue:") {
+            let position = |name: &str| {
+                fields
+                    .iter()
+                    .position(|field| field == name)
+                    .ok_or_else(|| anyhow::anyhow!("Missing `{}` in the ASS event format", name))
+            };
+
+            let (start, end, text) = (position("start")?, position("end")?, position("text")?);
+
+            // Text is always the last field and may itself contain commas
+            let values: Vec<&str> = dialogue.trim_start().splitn(fields.len(), ',').collect();
+
+            if values.len() != fields.len() {
+                anyhow::bail!("Invalid ASS dialogue: {}", line);
+            }
+
+            cues.push(Cue {
+                start: parse_timestamp(values[start])?,
+                end: parse_timestamp(values[end])?,
+                text: strip_ass_overrides(values[text])
+                    .replace("\\N", "\n")
+                    .replace("\\n", "\n")
+                    .replace("\\h", " "),
+            });
+        }
+    }
+
+    Ok(cues)
+}
+
+/// Parses `hh:mm:ss.mmm`, `mm:ss.mmm`, `hh:mm:ss,mmm` and ASS `h:mm:ss.cc` timestamps
+fn parse_timestamp(timestamp: &str) -> anyhow::Result<Duration> {
+    let invalid = || anyhow::anyhow!("Invalid timestamp: {}", timestamp);
+
+    let (clock, fraction) = timestamp
+        .trim()
+        .split_once(['.', ','])
+        .unwrap_or((timestamp.trim(), "0"));
+
+    let mut seconds = 0u64;
+    for part in clock.split(':') {
+        seconds = seconds * 60 + part.parse::<u64>().map_err(|_| invalid())?;
+    }
+
+    if fraction.is_empty() || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+
+    // Scale centiseconds (ASS) and milliseconds alike
+    let millis = format!("{:0<3}", fraction)[..3]
+        .parse::<u64>()
+        .map_err(|_| invalid())?;
+
+    Ok(Duration::from_millis(seconds * 1000 + millis))
+}
+
+fn format_timestamp(duration: Duration, separator: char) -> String {
+    let millis = duration.as_millis();
+
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        separator,
+        millis % 1000
+    )
+}
+
+fn format_ass_timestamp(duration: Duration) -> String {
+    let centis = duration.as_millis() / 10;
+
+    format!(
+        "{}:{:02}:{:02}.{:02}",
+        centis / 360_000,
+        centis / 6000 % 60,
+        centis / 100 % 60,
+        centis % 100
+    )
+}
+
+/// Removes `{\...}` override blocks from ASS text
+fn strip_ass_overrides(text: &str) -> String {
+    strip_between(text, '{', '}')
+}
+
+/// Removes `<i>`-style markup from WebVTT and SRT text
+fn strip_tags(text: &str) -> String {
+    strip_between(text, '<', '>')
+}
+
+fn strip_between(text: &str, open: char, close: char) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut depth = 0;
+
+    for c in text.chars() {
+        match c {
+            c if c == open => depth += 1,
+            c if c == close && depth > 0 => depth -= 1,
+            c if depth == 0 => stripped.push(c),
+            _ => {}
+        }
+    }
+
+    stripped
+}
+
+const LANGUAGES: &[(&str, &str)] = &[
+    ("arabic", "ar"),
+    ("bengali", "bn"),
+    ("bulgarian", "bg"),
+    ("burmese", "my"),
+    ("cantonese", "yue"),
+    ("catalan", "ca"),
+    ("chinese", "zh"),
+    ("croatian", "hr"),
+    ("czech", "cs"),
+    ("danish", "da"),
+    ("dutch", "nl"),
+    ("english", "en"),
+    ("estonian", "et"),
+    ("filipino", "fil"),
+    ("finnish", "fi"),
+    ("french", "fr"),
+    ("german", "de"),
+    ("greek", "el"),
+    ("hebrew", "he"),
+    ("hindi", "hi"),
+    ("hungarian", "hu"),
+    ("icelandic", "is"),
+    ("indonesian", "id"),
+    ("italian", "it"),
+    ("japanese", "ja"),
+    ("khmer", "km"),
+    ("korean", "ko"),
+    ("latvian", "lv"),
+    ("lithuanian", "lt"),
+    ("malay", "ms"),
+    ("mandarin", "zh"),
+    ("norwegian", "no"),
+    ("norwegian bokmal", "nb"),
+    ("persian", "fa"),
+    ("polish", "pl"),
+    ("portuguese", "pt"),
+    ("romanian", "ro"),
+    ("russian", "ru"),
+    ("serbian", "sr"),
+    ("slovak", "sk"),
+    ("slovenian", "sl"),
+    ("spanish", "es"),
+    ("swedish", "sv"),
+    ("tagalog", "tl"),
+    ("tamil", "ta"),
+    ("telugu", "te"),
+    ("thai", "th"),
+    ("turkish", "tr"),
+    ("ukrainian", "uk"),
+    ("urdu", "ur"),
+    ("vietnamese", "vi"),
+];
+
+const SUBTAGS: &[(&str, &str)] = &[
+    ("latin america", "419"),
+    ("latin american", "419"),
+    ("latam", "419"),
+    ("brazil", "BR"),
+    ("brazilian", "BR"),
+    ("portugal", "PT"),
+    ("european", "PT"),
+    ("spain", "ES"),
+    ("castilian", "ES"),
+    ("mexico", "MX"),
+    ("canada", "CA"),
+    ("canadian", "CA"),
+    ("france", "FR"),
+    ("united states", "US"),
+    ("us", "US"),
+    ("united kingdom", "GB"),
+    ("uk", "GB"),
+    ("hong kong", "HK"),
+    ("taiwan", "TW"),
+    ("simplified", "Hans"),
+    ("traditional", "Hant"),
+];
+
+/// Turns a subtitle label such as `"English - 2"` or `"Spanish (Latin America)"` into a BCP-47 language tag.
+/// Returns `None` when the language is not recognised.
+/// ```
+/// use consumet::utils::subtitles::normalize_lang;
+///
+/// assert_eq!(normalize_lang("English - 2"), Some("en".to_owned()));
+/// assert_eq!(normalize_lang("Spanish (Latin America)"), Some("es-419".to_owned()));
+/// assert_eq!(normalize_lang("Portuguese - Brazilian"), Some("pt-BR".to_owned()));
+/// assert_eq!(normalize_lang("Chinese (Traditional)"), Some("zh-Hant".to_owned()));
+/// assert_eq!(normalize_lang("pt-br"), Some("pt-BR".to_owned()));
+/// assert_eq!(normalize_lang("Klingon"), None);
+/// ```
+pub fn normalize_lang(label: &str) -> Option<String> {
+    let label = label.trim().to_lowercase();
+
+    // Already a tag, e.g. "en" or "pt-br"
+    if let Some(tag) = normalize_tag(&label) {
+        return Some(tag);
+    }
+
+    let parts: Vec<&str> = label
+        .split(['(', ')', '-', ','])
+        .map(|part| part.trim())
+        .filter(|part| !part.is_empty() && !part.chars().all(|c| c.is_ascii_digit()))
+        .collect();
+
+    let (name, qualifiers) = parts.split_first()?;
+
+    let language = LANGUAGES
+        .iter()
+        .find(|(language, _)| language == name)
+        .map(|(_, code)| *code)?;
+
+    let subtags = qualifiers.iter().filter_map(|qualifier| {
+        SUBTAGS
+            .iter()
+            .find(|(subtag, _)| subtag == qualifier)
+            .map(|(_, code)| *code)
+    });
+
+    Some(
+        std::iter::once(language)
+            .chain(subtags)
+            .collect::<Vec<_>>()
+            .join("-"),
+    )
+}
+
+fn normalize_tag(label: &str) -> Option<String> {
+    let mut parts = label.split(['-', '_']);
+    let language = parts.next()?;
+
+    if !(2..=3).contains(&language.len())
+        || !language.chars().all(|c| c.is_ascii_alphabetic())
+        || !LANGUAGES.iter().any(|(_, code)| *code == language)
+    {
+        return None;
+    }
+
+    let mut tag = vec![language.to_owned()];
+
+    for part in parts {
+        match part.len() {
+            2 if part.chars().all(|c| c.is_ascii_alphabetic()) => tag.push(part.to_uppercase()),
+            3 if part.chars().all(|c| c.is_ascii_digit()) => tag.push(part.to_owned()),
+            4 => {
+                let mut script = part.chars();
+                tag.push(
+                    script
+                        .next()?
+                        .to_uppercase()
+                        .chain(script)
+                        .collect::<String>(),
+                )
+            }
+            _ => return None,
+        }
+    }
+
+    Some(tag.join("-"))
+}
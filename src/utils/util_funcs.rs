@@ -1,7 +1,8 @@
-use chrono::{DateTime, Datelike, Local, Utc};
+use chrono::{DateTime, Datelike, Local, Utc, Weekday};
+use serde::{Deserialize, Serialize};
 
 /// The User-Agent used in HTTP requests in some parser implmentations
-/// ```
+/// ```no_run
 /// use consumet::utils::util_funcs::USER_AGENT;
 /// use reqwest::{Client, header};
 ///
@@ -22,7 +23,11 @@ use chrono::{DateTime, Datelike, Local, Utc};
 /// ```
 pub const USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/83.0.4103.116 Safari/537.36";
 
+/// How many requests providers send at once when fetching the details of several results
+pub const DEFAULT_CONCURRENCY: usize = 8;
+
 /// Days enum used for the get_day function
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub enum Days {
     Sunday,
     Monday,
@@ -33,6 +38,71 @@ pub enum Days {
     Saturday,
 }
 
+/// Parses full or abbreviated day names, ignoring case
+/// ```
+/// use consumet::utils::util_funcs::Days;
+///
+/// assert_eq!("Saturday".parse::<Days>().unwrap(), Days::Saturday);
+/// assert_eq!("tue".parse::<Days>().unwrap(), Days::Tuesday);
+/// assert_eq!("Mon.".parse::<Days>().unwrap(), Days::Monday);
+/// assert_eq!("Mon".parse::<Days>().unwrap(), Days::Monday);
+/// assert!("mo.".parse::<Days>().is_err());
+/// assert!("mo".parse::<Days>().is_err());
+/// assert!("Someday".parse::<Days>().is_err());
+/// ```
+impl std::str::FromStr for Days {
+    type Err = anyhow::Error;
+
+    fn from_str(day: &str) -> Result<Self, Self::Err> {
+        let day = day.trim().to_lowercase();
+        // "Mon." and "Mon" are the same abbreviation
+        let name = day.strip_suffix('.').unwrap_or(&day);
+
+        let days = [
+            ("sunday", Self::Sunday),
+            ("monday", Self::Monday),
+            ("tuesday", Self::Tuesday),
+            ("wednesday", Self::Wednesday),
+            ("thursday", Self::Thursday),
+            ("friday", Self::Friday),
+            ("saturday", Self::Saturday),
+        ];
+
+        days.into_iter()
+            .find(|(full, _)| name.len() >= 3 && full.starts_with(name))
+            .map(|(_, value)| value)
+            .ok_or_else(|| anyhow::anyhow!("Unknown day: {}", day))
+    }
+}
+
+impl From<Days> for Weekday {
+    fn from(day: Days) -> Self {
+        match day {
+            Days::Sunday => Self::Sun,
+            Days::Monday => Self::Mon,
+            Days::Tuesday => Self::Tue,
+            Days::Wednesday => Self::Wed,
+            Days::Thursday => Self::Thu,
+            Days::Friday => Self::Fri,
+            Days::Saturday => Self::Sat,
+        }
+    }
+}
+
+impl From<Weekday> for Days {
+    fn from(day: Weekday) -> Self {
+        match day {
+            Weekday::Sun => Self::Sunday,
+            Weekday::Mon => Self::Monday,
+            Weekday::Tue => Self::Tuesday,
+            Weekday::Wed => Self::Wednesday,
+            Weekday::Thu => Self::Thursday,
+            Weekday::Fri => Self::Friday,
+            Weekday::Sat => Self::Saturday,
+        }
+    }
+}
+
 /// Utility Functions for some parsers
 pub trait UtilFuncs {
     /// Pass in a author string and get a Vector
@@ -176,6 +246,30 @@ pub fn convert_duration(milliseconds: i64) -> String {
     datetime.format("PT%HH%MM%SS").to_string()
 }
 
+/// Percent-encodes a value for a URL query, keeping only unreserved characters as is
+/// ```
+/// use consumet::utils::util_funcs::encode_query;
+///
+/// assert_eq!(encode_query("one piece"), "one%20piece");
+/// assert_eq!(
+///     encode_query("Kaguya-sama: Love is War?"),
+///     "Kaguya-sama%3A%20Love%20is%20War%3F"
+/// );
+/// assert_eq!(encode_query("K-On! & #1+"), "K-On%21%20%26%20%231%2B");
+/// assert_eq!(encode_query("進撃"), "%E9%80%B2%E6%92%83");
+/// ```
+pub fn encode_query(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                char::from(byte).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
 impl UtilFuncs for str {
     fn split_author(&self) -> Vec<String> {
         let mut res: Vec<String> = Vec::new();